@@ -0,0 +1,95 @@
+use anyhow::{format_err, Result};
+
+use rustfst::arpa::{arpa_to_fst, ArpaBackoff, ArpaToFstConfig, OovPolicy, ParsedArpa};
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct Arpa2FstAlgorithm {
+    path_in: String,
+    path_out: String,
+    path_read_symbols: Option<String>,
+    path_write_symbols: Option<String>,
+    backoff: ArpaBackoff,
+    oov_policy: OovPolicy,
+    bos_symbol: String,
+    eos_symbol: String,
+}
+
+impl UnaryFstAlgorithm for Arpa2FstAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "arpa2fst".to_string()
+    }
+
+    fn read(&self) -> Result<VectorFst<TropicalWeight>> {
+        let arpa = ParsedArpa::from_path(self.get_path_in())?;
+        let mut config = ArpaToFstConfig::default()
+            .with_backoff(self.backoff.clone())
+            .with_oov_policy(self.oov_policy)
+            .with_bos_symbol(self.bos_symbol.as_str())
+            .with_eos_symbol(self.eos_symbol.as_str());
+        if let Some(path) = &self.path_read_symbols {
+            config = config.with_symbol_table(SymbolTable::read_text(path)?);
+        }
+        let (fst, _) = arpa_to_fst(&arpa, &config)?;
+        Ok(fst)
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+        Ok(fst)
+    }
+
+    fn write(&self, fst: &VectorFst<TropicalWeight>) -> Result<()> {
+        if let Some(path) = &self.path_write_symbols {
+            fst.input_symbols()
+                .ok_or_else(|| format_err!("Missing word symbol table"))?
+                .write_text(path)?;
+        }
+        fst.write(self.get_path_out())
+    }
+}
+
+impl Arpa2FstAlgorithm {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        path_read_symbols: Option<&str>,
+        path_write_symbols: Option<&str>,
+        backoff: &str,
+        phi_symbol: &str,
+        oov_policy: &str,
+        bos_symbol: &str,
+        eos_symbol: &str,
+    ) -> Result<Self> {
+        let backoff = match backoff {
+            "epsilon" => ArpaBackoff::Epsilon,
+            "phi" => ArpaBackoff::Phi(phi_symbol.to_string()),
+            _ => return Err(format_err!("Unknown backoff type : {}", backoff)),
+        };
+        let oov_policy = match oov_policy {
+            "add" => OovPolicy::Add,
+            "skip" => OovPolicy::Skip,
+            "error" => OovPolicy::Error,
+            _ => return Err(format_err!("Unknown OOV policy : {}", oov_policy)),
+        };
+        Ok(Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            path_read_symbols: path_read_symbols.map(|s| s.to_string()),
+            path_write_symbols: path_write_symbols.map(|s| s.to_string()),
+            backoff,
+            oov_policy,
+            bos_symbol: bos_symbol.to_string(),
+            eos_symbol: eos_symbol.to_string(),
+        })
+    }
+}
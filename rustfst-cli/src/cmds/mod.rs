@@ -1,3 +1,4 @@
+pub mod arpa2fst;
 pub mod compose;
 pub mod connect;
 pub mod determinize;
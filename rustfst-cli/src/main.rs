@@ -6,6 +6,7 @@ use clap::{Arg, ArgAction, Command};
 use log::error;
 
 use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::cmds::arpa2fst::Arpa2FstAlgorithm;
 use crate::cmds::compose::ComposeAlgorithm;
 use crate::cmds::connect::ConnectAlgorithm;
 use crate::cmds::determinize::DeterminizeAlgorithm;
@@ -192,6 +193,60 @@ fn main() {
     );
     app = app.subcommand(two_in_one_out_options(compose_cmd));
 
+    // Arpa2Fst
+    let arpa2fst_cmd = Command::new("arpa2fst")
+        .about("Converts an ARPA language model to a grammar FST.")
+        .arg(
+            Arg::new("backoff")
+                .long("backoff")
+                .value_parser(["epsilon", "phi"])
+                .default_value("epsilon")
+                .help("Label of the backoff transitions.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("phi_symbol")
+                .long("phi_symbol")
+                .default_value("#phi")
+                .help("Symbol used on the backoff transitions with --backoff=phi.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("oov_policy")
+                .long("oov_policy")
+                .value_parser(["add", "skip", "error"])
+                .default_value("add")
+                .help("Behaviour for words missing from --read_symbols.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("read_symbols")
+                .long("read_symbols")
+                .help("Path to an existing word symbol table in text format.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("write_symbols")
+                .long("write_symbols")
+                .help("Path where to write the word symbol table in text format.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("bos")
+                .long("bos")
+                .default_value("<s>")
+                .help("Begin-of-sentence symbol.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("eos")
+                .long("eos")
+                .default_value("</s>")
+                .help("End-of-sentence symbol.")
+                .action(ArgAction::Set),
+        );
+    app = app.subcommand(one_in_one_out_options(arpa2fst_cmd));
+
     let matches = app.get_matches();
 
     let env = env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "debug");
@@ -294,6 +349,18 @@ fn handle(matches: clap::ArgMatches) -> Result<()> {
             m.get_one::<String>("compose_type").unwrap(),
         )
         .run_cli_or_bench(m),
+        Some(("arpa2fst", m)) => Arpa2FstAlgorithm::new(
+            m.get_one::<String>("in.fst").unwrap(),
+            m.get_one::<String>("out.fst").unwrap(),
+            m.get_one::<String>("read_symbols").map(|s| s.as_str()),
+            m.get_one::<String>("write_symbols").map(|s| s.as_str()),
+            m.get_one::<String>("backoff").unwrap(),
+            m.get_one::<String>("phi_symbol").unwrap(),
+            m.get_one::<String>("oov_policy").unwrap(),
+            m.get_one::<String>("bos").unwrap(),
+            m.get_one::<String>("eos").unwrap(),
+        )?
+        .run_cli_or_bench(m),
         Some((s, _)) => Err(format_err!("Unknown subcommand {}.", s)),
         None => Err(format_err!("Unknown None")),
     }
@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Result};
+
+use super::EnumConversionError;
+use crate::fst::CFst;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::closure::{closure, ClosureType};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::TropicalWeight;
+
+#[derive(RawPointerConverter)]
+pub struct CClosureType(pub usize);
+
+impl AsRust<ClosureType> for CClosureType {
+    fn as_rust(&self) -> Result<ClosureType, AsRustError> {
+        match self.0 {
+            0 => Ok(ClosureType::ClosureStar),
+            1 => Ok(ClosureType::ClosurePlus),
+            _ => Err(AsRustError::Other(Box::new(EnumConversionError {}))),
+        }
+    }
+}
+
+impl CDrop for CClosureType {
+    fn do_drop(&mut self) -> Result<(), CDropError> {
+        Ok(())
+    }
+}
+
+impl CReprOf<ClosureType> for CClosureType {
+    fn c_repr_of(value: ClosureType) -> Result<CClosureType, CReprOfError> {
+        let variant = match value {
+            ClosureType::ClosureStar => 0,
+            ClosureType::ClosurePlus => 1,
+        };
+        Ok(CClosureType(variant))
+    }
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_closure(
+    ptr: *mut CFst,
+    closure_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        closure(vec_fst, CClosureType(closure_type).as_rust()?);
+        Ok(())
+    })
+}
@@ -0,0 +1,30 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::{get, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::condense;
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::TropicalWeight;
+
+/// # Safety
+///
+/// The pointers should be valid. The SCC mapping must be freed with `rustfst_destroy_int_array`.
+#[no_mangle]
+pub unsafe extern "C" fn fst_condense(
+    ptr: *const CFst,
+    res_fst: *mut *const CFst,
+    scc: *mut *const CArray<i32>,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let vec_fst: &VectorFst<TropicalWeight> = fst
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let (scc_mapping, res): (Vec<i32>, VectorFst<TropicalWeight>) = condense(vec_fst)?;
+        unsafe { *scc = CArray::<i32>::c_repr_of(scc_mapping)?.into_raw_pointer() };
+        unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        Ok(())
+    })
+}
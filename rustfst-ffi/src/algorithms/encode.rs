@@ -0,0 +1,69 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::encode::{decode, encode, EncodeTable, EncodeType};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::TropicalWeight;
+
+#[derive(RawPointerConverter)]
+pub struct CEncodeTable(pub EncodeTable<TropicalWeight>);
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_encode(
+    ptr: *mut CFst,
+    encode_weights: bool,
+    encode_labels: bool,
+    table: *mut *const CEncodeTable,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let encode_type = EncodeType::from_bools(encode_weights, encode_labels)?;
+        let encode_table = encode(vec_fst, encode_type)?;
+        unsafe { *table = CEncodeTable(encode_table).into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_decode(
+    ptr: *mut CFst,
+    table: *const CEncodeTable,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        // The table stays owned by the caller so that it can decode several FSTs.
+        let encode_table = get!(CEncodeTable, table);
+        decode(vec_fst, encode_table)?;
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_encode_table_destroy(table: *mut CEncodeTable) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if table.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(table) });
+        Ok(())
+    })
+}
@@ -1,18 +1,27 @@
+pub mod closure;
 pub mod compose;
 pub mod concat;
+pub mod condense;
 pub mod connect;
 pub mod determinize;
+pub mod encode;
 pub mod inversion;
 pub mod isomorphic;
 mod minimize;
 pub mod optimize;
 pub mod project;
+pub mod push;
 pub mod randgen;
 pub mod replace;
 pub mod reverse;
+pub mod reweight;
 pub mod rm_epsilon;
+pub mod rm_final_epsilon;
+pub mod shortest_distance;
 pub mod shortest_path;
+pub mod state_sort;
 pub mod top_sort;
+pub mod tr_map;
 pub mod tr_sort;
 pub mod tr_unique;
 pub mod union;
@@ -10,7 +10,7 @@ use rustfst::fst_impls::VectorFst;
 use rustfst::semirings::TropicalWeight;
 
 #[derive(RawPointerConverter)]
-pub struct CProjectType(usize);
+pub struct CProjectType(pub usize);
 
 impl AsRust<ProjectType> for CProjectType {
     fn as_rust(&self) -> Result<ProjectType, AsRustError> {
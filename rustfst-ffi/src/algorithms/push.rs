@@ -0,0 +1,196 @@
+use anyhow::anyhow;
+
+use super::reweight::CReweightType;
+use crate::fst::CFst;
+use crate::{get, get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::{
+    push, push_weights, push_weights_with_config, push_with_config, PushConfig, PushType,
+    PushWeightsConfig,
+};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::TropicalWeight;
+
+#[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
+#[target_type(PushWeightsConfig)]
+#[repr(C)]
+pub struct CPushWeightsConfig {
+    delta: f32,
+    remove_total_weight: bool,
+}
+
+#[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
+#[target_type(PushConfig)]
+#[repr(C)]
+pub struct CPushConfig {
+    delta: f32,
+}
+
+fn push_type_from_bits(push_type: u32) -> anyhow::Result<PushType> {
+    PushType::from_bits(push_type).ok_or_else(|| anyhow!("Invalid push type : {}", push_type))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_weights_config_new(
+    delta: libc::c_float,
+    remove_total_weight: bool,
+    config: *mut *const CPushWeightsConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let push_weights_config = CPushWeightsConfig {
+            delta,
+            remove_total_weight,
+        };
+        unsafe { *config = push_weights_config.into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_weights_config_destroy(
+    ptr: *mut CPushWeightsConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_config_new(
+    delta: libc::c_float,
+    config: *mut *const CPushConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let push_config = CPushConfig { delta };
+        unsafe { *config = push_config.into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_config_destroy(ptr: *mut CPushConfig) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_weights(
+    ptr: *mut CFst,
+    reweight_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        push_weights(vec_fst, CReweightType(reweight_type).as_rust()?)?;
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_weights_with_config(
+    ptr: *mut CFst,
+    reweight_type: libc::size_t,
+    config: *const CPushWeightsConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let config = unsafe {
+            <CPushWeightsConfig as ffi_convert::RawBorrow<CPushWeightsConfig>>::raw_borrow(config)?
+        };
+        push_weights_with_config(
+            vec_fst,
+            CReweightType(reweight_type).as_rust()?,
+            config.as_rust()?,
+        )?;
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push(
+    ptr: *const CFst,
+    reweight_type: libc::size_t,
+    push_type: u32,
+    res_fst: *mut *const CFst,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let vec_fst: &VectorFst<TropicalWeight> = fst
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let res: VectorFst<TropicalWeight> = push(
+            vec_fst,
+            CReweightType(reweight_type).as_rust()?,
+            push_type_from_bits(push_type)?,
+        )?;
+        unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_push_with_config(
+    ptr: *const CFst,
+    reweight_type: libc::size_t,
+    push_type: u32,
+    config: *const CPushConfig,
+    res_fst: *mut *const CFst,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let vec_fst: &VectorFst<TropicalWeight> = fst
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let config =
+            unsafe { <CPushConfig as ffi_convert::RawBorrow<CPushConfig>>::raw_borrow(config)? };
+        let res: VectorFst<TropicalWeight> = push_with_config(
+            vec_fst,
+            CReweightType(reweight_type).as_rust()?,
+            push_type_from_bits(push_type)?,
+            config.as_rust()?,
+        )?;
+        unsafe { *res_fst = CFst(Box::new(res)).into_raw_pointer() };
+        Ok(())
+    })
+}
@@ -0,0 +1,62 @@
+use anyhow::{anyhow, Result};
+
+use super::EnumConversionError;
+use crate::fst::CFst;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::{reweight, ReweightType};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::{Semiring, TropicalWeight};
+
+#[derive(RawPointerConverter)]
+pub struct CReweightType(pub usize);
+
+impl AsRust<ReweightType> for CReweightType {
+    fn as_rust(&self) -> Result<ReweightType, AsRustError> {
+        match self.0 {
+            0 => Ok(ReweightType::ReweightToInitial),
+            1 => Ok(ReweightType::ReweightToFinal),
+            _ => Err(AsRustError::Other(Box::new(EnumConversionError {}))),
+        }
+    }
+}
+
+impl CDrop for CReweightType {
+    fn do_drop(&mut self) -> Result<(), CDropError> {
+        Ok(())
+    }
+}
+
+impl CReprOf<ReweightType> for CReweightType {
+    fn c_repr_of(value: ReweightType) -> Result<CReweightType, CReprOfError> {
+        let variant = match value {
+            ReweightType::ReweightToInitial => 0,
+            ReweightType::ReweightToFinal => 1,
+        };
+        Ok(CReweightType(variant))
+    }
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_reweight(
+    ptr: *mut CFst,
+    potentials: *const CArray<f32>,
+    reweight_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let potentials: Vec<f32> = unsafe { CArray::raw_borrow(potentials)? }.as_rust()?;
+        let potentials: Vec<TropicalWeight> =
+            potentials.into_iter().map(TropicalWeight::new).collect();
+        let reweight_type = CReweightType(reweight_type).as_rust()?;
+        reweight(vec_fst, &potentials, reweight_type)?;
+        Ok(())
+    })
+}
@@ -0,0 +1,102 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::{get, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::{
+    shortest_distance, shortest_distance_with_config, ShortestDistanceConfig,
+};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::{Semiring, TropicalWeight};
+
+#[derive(AsRust, CReprOf, CDrop, RawPointerConverter)]
+#[target_type(ShortestDistanceConfig)]
+#[repr(C)]
+pub struct CShortestDistanceConfig {
+    delta: f32,
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_shortest_distance_config_new(
+    delta: libc::c_float,
+    config: *mut *const CShortestDistanceConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let shortest_distance_config = CShortestDistanceConfig { delta };
+        unsafe { *config = shortest_distance_config.into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_shortest_distance_config_destroy(
+    ptr: *mut CShortestDistanceConfig,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        if ptr.is_null() {
+            return Ok(());
+        }
+
+        drop(unsafe { Box::from_raw(ptr) });
+        Ok(())
+    })
+}
+
+fn distances_to_c_array(
+    distances: Vec<TropicalWeight>,
+    out: *mut *const CArray<f32>,
+) -> anyhow::Result<()> {
+    let distances: Vec<f32> = distances.into_iter().map(|w| *w.value()).collect();
+    unsafe { *out = CArray::<f32>::c_repr_of(distances)?.into_raw_pointer() };
+    Ok(())
+}
+
+/// # Safety
+///
+/// The pointers should be valid. The distances must be freed with `rustfst_destroy_float_array`.
+#[no_mangle]
+pub unsafe extern "C" fn fst_shortest_distance(
+    ptr: *const CFst,
+    reverse: bool,
+    distances: *mut *const CArray<f32>,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let vec_fst: &VectorFst<TropicalWeight> = fst
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        distances_to_c_array(shortest_distance(vec_fst, reverse)?, distances)
+    })
+}
+
+/// # Safety
+///
+/// The pointers should be valid. The distances must be freed with `rustfst_destroy_float_array`.
+#[no_mangle]
+pub unsafe extern "C" fn fst_shortest_distance_with_config(
+    ptr: *const CFst,
+    reverse: bool,
+    config: *const CShortestDistanceConfig,
+    distances: *mut *const CArray<f32>,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, ptr);
+        let vec_fst: &VectorFst<TropicalWeight> = fst
+            .downcast_ref()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let config = unsafe {
+            <CShortestDistanceConfig as ffi_convert::RawBorrow<CShortestDistanceConfig>>::raw_borrow(config)?
+        };
+        distances_to_c_array(
+            shortest_distance_with_config(vec_fst, reverse, config.as_rust()?)?,
+            distances,
+        )
+    })
+}
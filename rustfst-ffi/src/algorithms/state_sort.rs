@@ -0,0 +1,29 @@
+use anyhow::anyhow;
+
+use crate::fst::CFst;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::state_sort;
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::TropicalWeight;
+use rustfst::StateId;
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_state_sort(
+    ptr: *mut CFst,
+    order: *const CArray<u32>,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get_mut!(CFst, ptr);
+        let vec_fst: &mut VectorFst<TropicalWeight> = fst
+            .downcast_mut()
+            .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+        let order: Vec<StateId> = unsafe { CArray::raw_borrow(order)? }.as_rust()?;
+        state_sort(vec_fst, &order)?;
+        Ok(())
+    })
+}
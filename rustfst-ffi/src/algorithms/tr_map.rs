@@ -0,0 +1,118 @@
+use anyhow::{anyhow, Result};
+
+use crate::fst::CFst;
+use crate::{get_mut, wrap, RUSTFST_FFI_RESULT};
+
+use ffi_convert::*;
+use rustfst::algorithms::tr_mappers::{
+    IdentityTrMapper, InputEpsilonMapper, InvertMapper, InvertWeightMapper, OutputEpsilonMapper,
+    PlusMapper, ProjectMapper, QuantizeMapper, RmWeightMapper, TimesMapper,
+};
+use rustfst::algorithms::{tr_map, TrMapper};
+use rustfst::fst_impls::VectorFst;
+use rustfst::semirings::TropicalWeight;
+
+use super::project::CProjectType;
+
+unsafe fn apply_tr_mapper<M: TrMapper<TropicalWeight>>(ptr: *mut CFst, mapper: M) -> Result<()> {
+    let fst = get_mut!(CFst, ptr);
+    let vec_fst: &mut VectorFst<TropicalWeight> = fst
+        .downcast_mut()
+        .ok_or_else(|| anyhow!("Could not downcast to vector FST"))?;
+    tr_map(vec_fst, &mapper)
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_identity(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, IdentityTrMapper {}))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_input_epsilon(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, InputEpsilonMapper {}))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_output_epsilon(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, OutputEpsilonMapper {}))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_invert(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, InvertMapper {}))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_invert_weight(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, InvertWeightMapper {}))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_rm_weight(ptr: *mut CFst) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, RmWeightMapper {}))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_plus(
+    ptr: *mut CFst,
+    value: libc::c_float,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, PlusMapper::<TropicalWeight>::new(value)))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_times(
+    ptr: *mut CFst,
+    value: libc::c_float,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, TimesMapper::<TropicalWeight>::new(value)))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_quantize(
+    ptr: *mut CFst,
+    delta: libc::c_float,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| apply_tr_mapper(ptr, QuantizeMapper::new(delta)))
+}
+
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn fst_tr_map_project(
+    ptr: *mut CFst,
+    project_type: libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let project_type = CProjectType(project_type).as_rust()?;
+        apply_tr_mapper(ptr, ProjectMapper::new(project_type))
+    })
+}
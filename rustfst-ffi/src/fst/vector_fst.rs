@@ -1,9 +1,10 @@
 use super::*;
 use crate::get_symt;
-use anyhow::{anyhow, format_err};
+use crate::CLabel;
+use anyhow::{anyhow, bail, format_err};
 use ffi_convert::CArray;
-use rustfst::fst_traits::ExpandedFst;
-use rustfst::DrawingConfig;
+use rustfst::fst_traits::{CoreFst, ExpandedFst};
+use rustfst::{DrawingConfig, Label, Tr};
 use std::ffi::CString;
 
 /// # Safety
@@ -352,3 +353,198 @@ pub unsafe extern "C" fn vec_fst_from_bytes(
         Ok(())
     })
 }
+
+unsafe fn slice_from_raw<'a, T>(ptr: *const T, len: usize, name: &str) -> Result<&'a [T]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        bail!("`{}` is null but {} elements are expected", name, len);
+    }
+    Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+}
+
+unsafe fn slice_from_raw_mut<'a, T>(ptr: *mut T, len: usize, name: &str) -> Result<&'a mut [T]> {
+    if len == 0 {
+        return Ok(&mut []);
+    }
+    if ptr.is_null() {
+        bail!("`{}` is null but {} elements are expected", name, len);
+    }
+    Ok(unsafe { std::slice::from_raw_parts_mut(ptr, len) })
+}
+
+/// Number of offsets of the CSR layout, one more than the number of states.
+fn num_offsets(num_states: usize) -> Result<usize> {
+    num_states
+        .checked_add(1)
+        .ok_or_else(|| anyhow!("Too many states : {}", num_states))
+}
+
+/// Builds a VectorFst from contiguous arrays in CSR layout: the trs leaving the state `s` are
+/// stored at the indices `offsets[s]..offsets[s + 1]` of `ilabels`, `olabels`, `weights` and
+/// `nextstates`. `offsets` contains `num_states + 1` elements and `final_weights` contains
+/// `num_states` elements, the non-final states having an infinite final weight (`Zero()`).
+/// `start` may be null if the FST has no start state.
+///
+/// # Safety
+///
+/// The pointers should be valid and point to arrays of the expected sizes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn vec_fst_from_arrays(
+    num_states: libc::size_t,
+    start: *const CStateId,
+    final_weights: *const libc::c_float,
+    offsets: *const libc::size_t,
+    ilabels: *const CLabel,
+    olabels: *const CLabel,
+    weights: *const libc::c_float,
+    nextstates: *const CStateId,
+    ptr: *mut *const CFst,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let num_offsets = num_offsets(num_states)?;
+        let final_weights = unsafe { slice_from_raw(final_weights, num_states, "final_weights")? };
+        let offsets = unsafe { slice_from_raw(offsets, num_offsets, "offsets")? };
+        let num_trs = offsets[num_states];
+        let ilabels = unsafe { slice_from_raw(ilabels, num_trs, "ilabels")? };
+        let olabels = unsafe { slice_from_raw(olabels, num_trs, "olabels")? };
+        let weights = unsafe { slice_from_raw(weights, num_trs, "weights")? };
+        let nextstates = unsafe { slice_from_raw(nextstates, num_trs, "nextstates")? };
+
+        if offsets[0] != 0 {
+            bail!("The first offset should be 0, found {}", offsets[0]);
+        }
+        if let Some(s) = (0..num_states).find(|&s| offsets[s] > offsets[s + 1]) {
+            bail!(
+                "Offsets should be non-decreasing, found {} followed by {} for state {}",
+                offsets[s],
+                offsets[s + 1],
+                s
+            );
+        }
+        if let Some(&n) = nextstates.iter().find(|&&n| n as usize >= num_states) {
+            bail!(
+                "Invalid nextstate {} for a FST with {} states",
+                n,
+                num_states
+            );
+        }
+
+        let mut vec_fst = VectorFst::<TropicalWeight>::new();
+        vec_fst.add_states(num_states);
+        if !start.is_null() {
+            vec_fst.set_start(unsafe { *start } as StateId)?;
+        }
+        for s in 0..num_states {
+            let trs = (offsets[s]..offsets[s + 1])
+                .map(|i| {
+                    Tr::new(
+                        ilabels[i] as Label,
+                        olabels[i] as Label,
+                        weights[i],
+                        nextstates[i] as StateId,
+                    )
+                })
+                .collect();
+            unsafe { vec_fst.set_trs_unchecked(s as StateId, trs) };
+            let final_weight = TropicalWeight::new(final_weights[s]);
+            if !final_weight.is_zero() {
+                unsafe { vec_fst.set_final_unchecked(s as StateId, final_weight) };
+            }
+        }
+
+        unsafe { *ptr = CFst(Box::new(vec_fst)).into_raw_pointer() };
+        Ok(())
+    })
+}
+
+/// Total number of trs of a VectorFst, i.e the size of the arrays filled by `vec_fst_to_arrays`.
+///
+/// # Safety
+///
+/// The pointers should be valid.
+#[no_mangle]
+pub unsafe extern "C" fn vec_fst_total_num_trs(
+    fst: *const CFst,
+    num_trs: *mut libc::size_t,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, fst);
+        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
+        let res = vec_fst
+            .states_range()
+            .map(|s| unsafe { vec_fst.num_trs_unchecked(s) })
+            .sum();
+        unsafe { *num_trs = res };
+        Ok(())
+    })
+}
+
+/// Exports a VectorFst into contiguous arrays allocated by the caller, using the same CSR layout
+/// as `vec_fst_from_arrays`. `final_weights` must have `num_states` elements, `offsets`
+/// `num_states + 1` elements and the other arrays `num_trs` elements, as returned by
+/// `vec_fst_num_states` and `vec_fst_total_num_trs`.
+///
+/// # Safety
+///
+/// The pointers should be valid and point to arrays of the expected sizes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn vec_fst_to_arrays(
+    fst: *const CFst,
+    num_states: libc::size_t,
+    num_trs: libc::size_t,
+    final_weights: *mut libc::c_float,
+    offsets: *mut libc::size_t,
+    ilabels: *mut CLabel,
+    olabels: *mut CLabel,
+    weights: *mut libc::c_float,
+    nextstates: *mut CStateId,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        let fst = get!(CFst, fst);
+        let vec_fst = as_fst!(VectorFst<TropicalWeight>, fst);
+        if num_states != vec_fst.num_states() {
+            bail!(
+                "Arrays sized for {} states but the FST has {} states",
+                num_states,
+                vec_fst.num_states()
+            );
+        }
+
+        let final_weights =
+            unsafe { slice_from_raw_mut(final_weights, num_states, "final_weights")? };
+        let offsets = unsafe { slice_from_raw_mut(offsets, num_offsets(num_states)?, "offsets")? };
+        let ilabels = unsafe { slice_from_raw_mut(ilabels, num_trs, "ilabels")? };
+        let olabels = unsafe { slice_from_raw_mut(olabels, num_trs, "olabels")? };
+        let weights = unsafe { slice_from_raw_mut(weights, num_trs, "weights")? };
+        let nextstates = unsafe { slice_from_raw_mut(nextstates, num_trs, "nextstates")? };
+
+        let mut idx = 0;
+        offsets[0] = 0;
+        for s in vec_fst.states_range() {
+            let trs = unsafe { vec_fst.get_trs_unchecked(s) };
+            if idx + trs.len() > num_trs {
+                bail!("Arrays sized for {} trs but the FST has more", num_trs);
+            }
+            for tr in trs.trs() {
+                ilabels[idx] = tr.ilabel as CLabel;
+                olabels[idx] = tr.olabel as CLabel;
+                weights[idx] = *tr.weight.value();
+                nextstates[idx] = tr.nextstate as CStateId;
+                idx += 1;
+            }
+            offsets[s as usize + 1] = idx;
+            final_weights[s as usize] = *unsafe { vec_fst.final_weight_unchecked(s) }
+                .unwrap_or_else(TropicalWeight::zero)
+                .value();
+        }
+        if idx != num_trs {
+            bail!("Arrays sized for {} trs but the FST has {}", num_trs, idx);
+        }
+
+        Ok(())
+    })
+}
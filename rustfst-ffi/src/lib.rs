@@ -14,7 +14,7 @@ use std::ffi::CString;
 use std::sync::Arc;
 
 use anyhow::Result;
-use ffi_convert::{CReprOf, RawPointerConverter};
+use ffi_convert::{CArray, CReprOf, RawPointerConverter};
 
 #[cfg(feature = "rustfst-state-label-u32")]
 pub type CLabel = libc::c_uint;
@@ -34,6 +34,54 @@ pub enum RUSTFST_FFI_RESULT {
     RUSTFST_FFI_RESULT_OK = 0,
     /// The function returned an error
     RUSTFST_FFI_RESULT_KO = 1,
+    /// The input couldn't be parsed
+    RUSTFST_FFI_RESULT_PARSE_ERROR = 2,
+    /// A state doesn't exist in the FST
+    RUSTFST_FFI_RESULT_INVALID_STATE_ID = 3,
+    /// The FST doesn't have the properties required by the algorithm
+    RUSTFST_FFI_RESULT_PROPERTY_VIOLATION = 4,
+    /// A symbol table is missing or incompatible
+    RUSTFST_FFI_RESULT_INCOMPATIBLE_SYMBOLS = 5,
+    /// One of the limits of the algorithm has been hit
+    RUSTFST_FFI_RESULT_LIMIT_EXCEEDED = 6,
+    /// An I/O error occurred
+    RUSTFST_FFI_RESULT_IO_ERROR = 7,
+    /// The FST must be sorted
+    RUSTFST_FFI_RESULT_NOT_SORTED = 8,
+    /// The FST is not functional
+    RUSTFST_FFI_RESULT_NON_FUNCTIONAL = 9,
+}
+
+impl RUSTFST_FFI_RESULT {
+    fn from_error(err: &anyhow::Error) -> Self {
+        match rustfst::Error::find(err) {
+            Some(rustfst::Error::Parse(_)) => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_PARSE_ERROR,
+            Some(rustfst::Error::InvalidStateId(_)) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_INVALID_STATE_ID
+            }
+            Some(rustfst::Error::PropertyViolation(_)) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_PROPERTY_VIOLATION
+            }
+            Some(rustfst::Error::IncompatibleSymbols(_)) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_INCOMPATIBLE_SYMBOLS
+            }
+            Some(rustfst::Error::LimitExceeded(_)) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_LIMIT_EXCEEDED
+            }
+            Some(rustfst::Error::Io(_)) => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_IO_ERROR,
+            Some(rustfst::Error::NotSorted(_)) => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_NOT_SORTED,
+            Some(rustfst::Error::NonFunctional(_)) => {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_NON_FUNCTIONAL
+            }
+            _ if err
+                .chain()
+                .any(|e| e.downcast_ref::<std::io::Error>().is_some()) =>
+            {
+                RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_IO_ERROR
+            }
+            _ => RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_KO,
+        }
+    }
 }
 
 thread_local! {
@@ -48,8 +96,9 @@ pub fn wrap<F: FnOnce() -> Result<()>>(func: F) -> RUSTFST_FFI_RESULT {
             if std::env::var("AMSTRAM_FFI_ERROR_STDERR").is_ok() {
                 eprintln!("{}", msg);
             }
+            let result = RUSTFST_FFI_RESULT::from_error(&e);
             LAST_ERROR.with(|p| *p.borrow_mut() = Some(msg));
-            RUSTFST_FFI_RESULT::RUSTFST_FFI_RESULT_KO
+            result
         }
     }
 }
@@ -84,6 +133,26 @@ pub unsafe extern "C" fn rustfst_destroy_string(string: *mut libc::c_char) -> RU
     })
 }
 
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn rustfst_destroy_float_array(
+    array: *mut CArray<f32>,
+) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        CArray::drop_raw_pointer(array)?;
+        Ok(())
+    })
+}
+
+#[allow(clippy::missing_safety_doc)]
+#[no_mangle]
+pub unsafe extern "C" fn rustfst_destroy_int_array(array: *mut CArray<i32>) -> RUSTFST_FFI_RESULT {
+    wrap(|| {
+        CArray::drop_raw_pointer(array)?;
+        Ok(())
+    })
+}
+
 macro_rules! get_mut {
     ($typ:ty,$opaque:ident) => {{
         &mut unsafe { <$typ as ffi_convert::RawBorrowMut<$typ>>::raw_borrow_mut($opaque) }?.0
@@ -6,6 +6,17 @@ from .fst.vector_fst import VectorFst
 from .fst.const_fst import ConstFst
 from .iterators import TrsIterator, MutableTrsIterator, StateIterator
 from .drawing_config import DrawingConfig
+from .exceptions import (
+    RustfstError,
+    ParseError,
+    InvalidStateError,
+    PropertyError,
+    SymbolTableError,
+    LimitExceededError,
+    RustfstIOError,
+    NotSortedError,
+    NonFunctionalError,
+)
 
 __all__ = [
     "Tr",
@@ -18,4 +29,13 @@ __all__ = [
     "MutableTrsIterator",
     "StateIterator",
     "DrawingConfig",
+    "RustfstError",
+    "ParseError",
+    "InvalidStateError",
+    "PropertyError",
+    "SymbolTableError",
+    "LimitExceededError",
+    "RustfstIOError",
+    "NotSortedError",
+    "NonFunctionalError",
 ]
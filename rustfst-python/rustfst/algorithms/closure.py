@@ -0,0 +1,42 @@
+from __future__ import annotations
+import ctypes
+from enum import Enum
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+class ClosureType(Enum):
+    """
+    Different types of Kleene closure.
+    """
+
+    CLOSURE_STAR = 0
+    """
+    Kleene star : the empty string is accepted.
+    """
+    CLOSURE_PLUS = 1
+    """
+    Kleene plus : at least one repetition is required.
+    """
+
+
+def closure(fst: VectorFst, closure_type: ClosureType) -> VectorFst:
+    """
+    Compute the Kleene closure of an Fst in-place.
+    Args:
+      fst: Fst on which to apply the closure.
+      closure_type: Star or plus closure.
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = lib.fst_closure(fst.ptr, ctypes.c_size_t(closure_type.value))
+    err_msg = "Error during closure"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
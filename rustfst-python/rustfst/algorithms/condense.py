@@ -0,0 +1,34 @@
+from __future__ import annotations
+import ctypes
+from typing import List, Tuple
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+    CInt32Array,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+def condense(fst: VectorFst) -> Tuple[List[int], VectorFst]:
+    """
+    Condense each strongly connected component of an Fst to a single state.
+    The transitions between SCCs are retained and the ones within SCCs are dropped.
+    Args:
+      fst: Fst to condense.
+    Returns:
+      The SCC of each state of the input Fst, which is also its state in the
+      condensed Fst, and the newly-created acyclic condensed Fst.
+    """
+
+    condensed_fst = ctypes.c_void_p()
+    scc = ctypes.POINTER(CInt32Array)()
+    ret_code = lib.fst_condense(fst.ptr, ctypes.byref(condensed_fst), ctypes.byref(scc))
+    err_msg = "Error during condense"
+    check_ffi_error(ret_code, err_msg)
+
+    scc_list = scc.contents.to_pylist()
+    lib.rustfst_destroy_int_array(scc)
+
+    return scc_list, VectorFst(ptr=condensed_fst)
@@ -0,0 +1,67 @@
+from __future__ import annotations
+import ctypes
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+class EncodeTable:
+    """
+    Table storing the mapping between the encoded labels and the original
+    (input label, output label, weight) triplets. It is returned by `encode`
+    and must be used to `decode` the Fst.
+    """
+
+    def __init__(self, ptr):
+        self.ptr = ptr
+
+    def __del__(self):
+        lib.fst_encode_table_destroy(self.ptr)
+
+
+def encode(
+    fst: VectorFst, encode_weights: bool = False, encode_labels: bool = True
+) -> EncodeTable:
+    """
+    Encode the labels and/or weights of an Fst in-place so that it can be
+    processed as an unweighted acceptor.
+    Args:
+      fst: Fst to encode.
+      encode_weights: Whether the weights are encoded.
+      encode_labels: Whether the pairs of labels are encoded.
+    Returns:
+      The EncodeTable needed to decode the Fst.
+    """
+
+    table = ctypes.c_void_p()
+    ret_code = lib.fst_encode(
+        fst.ptr,
+        ctypes.c_bool(encode_weights),
+        ctypes.c_bool(encode_labels),
+        ctypes.byref(table),
+    )
+    err_msg = "Error during encode"
+    check_ffi_error(ret_code, err_msg)
+
+    return EncodeTable(ptr=table)
+
+
+def decode(fst: VectorFst, encode_table: EncodeTable) -> VectorFst:
+    """
+    Decode an Fst in-place that has been encoded with `encode`.
+    Args:
+      fst: Fst to decode.
+      encode_table: Table returned by `encode`.
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = lib.fst_decode(fst.ptr, encode_table.ptr)
+    err_msg = "Error during decode"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
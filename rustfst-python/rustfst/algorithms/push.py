@@ -0,0 +1,180 @@
+from __future__ import annotations
+import ctypes
+from enum import IntFlag
+from typing import Optional
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.algorithms.reweight import ReweightType
+from rustfst.fst.vector_fst import VectorFst
+
+KDELTA = 1.0 / 1024.0
+
+
+class PushType(IntFlag):
+    """
+    Flags defining what is pushed by `push`. They can be combined with `|`.
+    """
+
+    PUSH_WEIGHTS = 0b1
+    """
+    Push the weights.
+    """
+    PUSH_LABELS = 0b10
+    """
+    Push the output labels.
+    """
+    REMOVE_TOTAL_WEIGHT = 0b100
+    """
+    Remove the total weight of the Fst once pushed.
+    """
+    REMOVE_COMMON_AFFIX = 0b1000
+    """
+    Remove the common prefix (or suffix) of the labels once pushed.
+    """
+
+
+class PushWeightsConfig:
+    """
+    Configuration for the `push_weights` operation.
+    Args:
+      delta: Difference in weights considered significant.
+      remove_total_weight: Remove the total weight of the Fst once pushed.
+    """
+
+    def __init__(
+        self, delta: Optional[float] = None, remove_total_weight: bool = False
+    ):
+        if delta is None:
+            delta = KDELTA
+
+        config = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.fst_push_weights_config_new(
+            ctypes.c_float(delta),
+            ctypes.c_bool(remove_total_weight),
+            ctypes.byref(config),
+        )
+        err_msg = "Error creating PushWeightsConfig"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = config
+
+    def __del__(self):
+        lib.fst_push_weights_config_destroy(self.ptr)
+
+
+class PushConfig:
+    """
+    Configuration for the `push` operation.
+    Args:
+      delta: Difference in weights considered significant.
+    """
+
+    def __init__(self, delta: Optional[float] = None):
+        if delta is None:
+            delta = KDELTA
+
+        config = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.fst_push_config_new(
+            ctypes.c_float(delta),
+            ctypes.byref(config),
+        )
+        err_msg = "Error creating PushConfig"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = config
+
+    def __del__(self):
+        lib.fst_push_config_destroy(self.ptr)
+
+
+def push_weights(fst: VectorFst, reweight_type: ReweightType) -> VectorFst:
+    """
+    Push the weights of an Fst in-place toward the initial state or the final states.
+    Args:
+      fst: Fst whose weights are pushed.
+      reweight_type: Push toward the initial state or toward the final states.
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = lib.fst_push_weights(fst.ptr, ctypes.c_size_t(reweight_type.value))
+    err_msg = "Error during push_weights"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
+
+
+def push_weights_with_config(
+    fst: VectorFst, reweight_type: ReweightType, config: PushWeightsConfig
+) -> VectorFst:
+    """
+    Push the weights of an Fst in-place toward the initial state or the final states.
+    Args:
+      fst: Fst whose weights are pushed.
+      reweight_type: Push toward the initial state or toward the final states.
+      config: Configuration for the push_weights operation.
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = lib.fst_push_weights_with_config(
+        fst.ptr, ctypes.c_size_t(reweight_type.value), config.ptr
+    )
+    err_msg = "Error during push_weights"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
+
+
+def push(fst: VectorFst, reweight_type: ReweightType, push_type: PushType) -> VectorFst:
+    """
+    Push the weights and/or the labels of an Fst toward the initial state or the final states.
+    Args:
+      fst: Fst to push.
+      reweight_type: Push toward the initial state or toward the final states.
+      push_type: What to push, e.g `PushType.PUSH_WEIGHTS | PushType.PUSH_LABELS`.
+    Returns:
+      Newly-created Fst with the pushed weights and/or labels.
+    """
+
+    pushed_fst = ctypes.c_void_p()
+    ret_code = lib.fst_push(
+        fst.ptr,
+        ctypes.c_size_t(reweight_type.value),
+        ctypes.c_uint32(int(push_type)),
+        ctypes.byref(pushed_fst),
+    )
+    err_msg = "Error during push"
+    check_ffi_error(ret_code, err_msg)
+
+    return VectorFst(ptr=pushed_fst)
+
+
+def push_with_config(
+    fst: VectorFst, reweight_type: ReweightType, push_type: PushType, config: PushConfig
+) -> VectorFst:
+    """
+    Push the weights and/or the labels of an Fst toward the initial state or the final states.
+    Args:
+      fst: Fst to push.
+      reweight_type: Push toward the initial state or toward the final states.
+      push_type: What to push, e.g `PushType.PUSH_WEIGHTS | PushType.PUSH_LABELS`.
+      config: Configuration for the push operation.
+    Returns:
+      Newly-created Fst with the pushed weights and/or labels.
+    """
+
+    pushed_fst = ctypes.c_void_p()
+    ret_code = lib.fst_push_with_config(
+        fst.ptr,
+        ctypes.c_size_t(reweight_type.value),
+        ctypes.c_uint32(int(push_type)),
+        config.ptr,
+        ctypes.byref(pushed_fst),
+    )
+    err_msg = "Error during push"
+    check_ffi_error(ret_code, err_msg)
+
+    return VectorFst(ptr=pushed_fst)
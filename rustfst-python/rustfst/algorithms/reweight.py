@@ -0,0 +1,50 @@
+from __future__ import annotations
+import ctypes
+from enum import Enum
+from typing import List
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+    CFloatArray,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+class ReweightType(Enum):
+    """
+    Direction in which the weights are reweighted or pushed.
+    """
+
+    REWEIGHT_TO_INITIAL = 0
+    """
+    Reweight toward the initial state.
+    """
+    REWEIGHT_TO_FINAL = 1
+    """
+    Reweight toward the final states.
+    """
+
+
+def reweight(
+    fst: VectorFst, potentials: List[float], reweight_type: ReweightType
+) -> VectorFst:
+    """
+    Reweight an FST according to the potentials in-place. The path weights
+    are preserved up to the potential of the initial state (or the final states).
+    Args:
+      fst: Fst to reweight.
+      potentials: Potential of each state, e.g the result of `shortest_distance`.
+      reweight_type: Reweight toward the initial state or toward the final states.
+    Returns:
+      fst: Same FST, modified in place
+    """
+    array = CFloatArray.from_pylist(potentials)
+    ret_code = lib.fst_reweight(
+        fst.ptr, ctypes.byref(array), ctypes.c_size_t(reweight_type.value)
+    )
+    err_msg = "Error during reweight"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -0,0 +1,24 @@
+from __future__ import annotations
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+def rm_final_epsilon(fst: VectorFst) -> VectorFst:
+    """
+    Remove final states that have epsilon-only input transitions in-place,
+    moving their final weight to their predecessors.
+    Args:
+      fst: Fst to remove final epsilons from
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = lib.fst_rm_final_epsilon(fst.ptr)
+    err_msg = "Error during rm_final_epsilon"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -0,0 +1,88 @@
+from __future__ import annotations
+import ctypes
+from typing import List, Optional
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+    CFloatArray,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+KSHORTESTDELTA = 1e-6
+
+
+class ShortestDistanceConfig:
+    """
+    Configuration for the shortest-distance operation.
+    Args:
+      delta: Difference in weights considered significant.
+    """
+
+    def __init__(self, delta: Optional[float] = None):
+        if delta is None:
+            delta = KSHORTESTDELTA
+
+        config = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.fst_shortest_distance_config_new(
+            ctypes.c_float(delta),
+            ctypes.byref(config),
+        )
+        err_msg = "Error creating ShortestDistanceConfig"
+        check_ffi_error(ret_code, err_msg)
+        self.ptr = config
+
+    def __del__(self):
+        lib.fst_shortest_distance_config_destroy(self.ptr)
+
+
+def _distances_to_list(distances) -> List[float]:
+    res = distances.contents.to_pylist()
+    lib.rustfst_destroy_float_array(distances)
+    return res
+
+
+def shortest_distance(fst: VectorFst, reverse: bool = False) -> List[float]:
+    """
+    Compute the shortest distance from the initial state to every state.
+    The shortest distance from `p` to `q` is the ⊕-sum of the weights
+    of all the paths between `p` and `q`.
+    Args:
+      fst: Fst
+      reverse: If true, compute the shortest distance from every state to the final states.
+    Returns:
+      The shortest distance of each state.
+    """
+
+    distances = ctypes.POINTER(CFloatArray)()
+    ret_code = lib.fst_shortest_distance(
+        fst.ptr, ctypes.c_bool(reverse), ctypes.byref(distances)
+    )
+    err_msg = "Error computing shortest distance"
+    check_ffi_error(ret_code, err_msg)
+
+    return _distances_to_list(distances)
+
+
+def shortest_distance_with_config(
+    fst: VectorFst, reverse: bool, config: ShortestDistanceConfig
+) -> List[float]:
+    """
+    Compute the shortest distance from the initial state to every state.
+    Args:
+      fst: Fst
+      reverse: If true, compute the shortest distance from every state to the final states.
+      config: Configuration for the shortest-distance operation.
+    Returns:
+      The shortest distance of each state.
+    """
+
+    distances = ctypes.POINTER(CFloatArray)()
+    ret_code = lib.fst_shortest_distance_with_config(
+        fst.ptr, ctypes.c_bool(reverse), config.ptr, ctypes.byref(distances)
+    )
+    err_msg = "Error computing shortest distance"
+    check_ffi_error(ret_code, err_msg)
+
+    return _distances_to_list(distances)
@@ -0,0 +1,29 @@
+from __future__ import annotations
+import ctypes
+from typing import List
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+    CUInt32Array,
+)
+
+from rustfst.fst.vector_fst import VectorFst
+
+
+def state_sort(fst: VectorFst, order: List[int]) -> VectorFst:
+    """
+    Sort the states of an Fst in-place. The state `s` is renumbered `order[s]`.
+    Args:
+      fst: Fst whose states are sorted.
+      order: New id of each state. Must be a permutation of the states.
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    array = CUInt32Array.from_pylist(order)
+    ret_code = lib.fst_state_sort(fst.ptr, ctypes.byref(array))
+    err_msg = "Error during state_sort"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -0,0 +1,156 @@
+from __future__ import annotations
+import ctypes
+from abc import ABC, abstractmethod
+from typing import Optional
+
+from rustfst.ffi_utils import (
+    lib,
+    check_ffi_error,
+)
+
+from rustfst.algorithms.project import ProjectType
+from rustfst.fst.vector_fst import VectorFst
+
+KDELTA = 1.0 / 1024.0
+
+
+class TrMapper(ABC):
+    """
+    Base class of the mappers that can be applied to the transitions and
+    final weights of an Fst with `tr_map`.
+    """
+
+    @abstractmethod
+    def _map(self, fst: VectorFst) -> int:
+        pass
+
+
+class IdentityTrMapper(TrMapper):
+    """
+    Mapper that returns its input.
+    """
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_identity(fst.ptr)
+
+
+class InputEpsilonMapper(TrMapper):
+    """
+    Mapper that replaces the input labels with epsilon.
+    """
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_input_epsilon(fst.ptr)
+
+
+class OutputEpsilonMapper(TrMapper):
+    """
+    Mapper that replaces the output labels with epsilon.
+    """
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_output_epsilon(fst.ptr)
+
+
+class InvertMapper(TrMapper):
+    """
+    Mapper that swaps the input and output labels.
+    """
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_invert(fst.ptr)
+
+
+class InvertWeightMapper(TrMapper):
+    """
+    Mapper that replaces each weight with its inverse.
+    """
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_invert_weight(fst.ptr)
+
+
+class RmWeightMapper(TrMapper):
+    """
+    Mapper that replaces the non-zero weights with `One()`.
+    """
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_rm_weight(fst.ptr)
+
+
+class PlusMapper(TrMapper):
+    """
+    Mapper that ⊕-adds a constant to all the weights.
+    Args:
+      value: Weight added to all the weights.
+    """
+
+    def __init__(self, value: float):
+        self.value = value
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_plus(fst.ptr, ctypes.c_float(self.value))
+
+
+class TimesMapper(TrMapper):
+    """
+    Mapper that ⊗-multiplies all the weights by a constant.
+    Args:
+      value: Weight by which all the weights are multiplied.
+    """
+
+    def __init__(self, value: float):
+        self.value = value
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_times(fst.ptr, ctypes.c_float(self.value))
+
+
+class QuantizeMapper(TrMapper):
+    """
+    Mapper that quantizes all the weights.
+    Args:
+      delta: Quantization step.
+    """
+
+    def __init__(self, delta: Optional[float] = None):
+        if delta is None:
+            delta = KDELTA
+        self.delta = delta
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_quantize(fst.ptr, ctypes.c_float(self.delta))
+
+
+class ProjectMapper(TrMapper):
+    """
+    Mapper that projects the labels of the transitions.
+    Args:
+      project_type: Whether to keep the input labels or the output labels.
+    """
+
+    def __init__(self, project_type: ProjectType):
+        self.project_type = project_type
+
+    def _map(self, fst: VectorFst) -> int:
+        return lib.fst_tr_map_project(
+            fst.ptr, ctypes.c_size_t(self.project_type.value)
+        )
+
+
+def tr_map(fst: VectorFst, mapper: TrMapper) -> VectorFst:
+    """
+    Apply a mapper to all the transitions and final weights of an Fst in-place.
+    Args:
+      fst: Fst to modify.
+      mapper: Mapper to apply, e.g `QuantizeMapper()` or `TimesMapper(2.0)`.
+    Returns:
+      fst: Same FST, modified in place
+    """
+
+    ret_code = mapper._map(fst)
+    err_msg = "Error during tr_map"
+    check_ffi_error(ret_code, err_msg)
+
+    return fst
@@ -0,0 +1,51 @@
+class RustfstError(ValueError):
+    """
+    Base class of the errors raised by rustfst. It inherits from `ValueError`
+    so that existing code catching `ValueError` keeps working.
+    """
+
+
+class ParseError(RustfstError):
+    """The input (binary or text FST, symbol table, ...) couldn't be parsed."""
+
+
+class InvalidStateError(RustfstError):
+    """The state doesn't exist in the FST."""
+
+
+class PropertyError(RustfstError):
+    """The FST doesn't have the properties required by the algorithm."""
+
+
+class SymbolTableError(RustfstError):
+    """A symbol table is missing or doesn't contain a label or a symbol."""
+
+
+class LimitExceededError(RustfstError):
+    """One of the limits of the algorithm has been hit or it has been cancelled."""
+
+
+class RustfstIOError(RustfstError, OSError):
+    """An I/O error occurred while reading or writing a file."""
+
+
+class NotSortedError(PropertyError):
+    """The FST must be sorted on its input or output labels, e.g for composition."""
+
+
+class NonFunctionalError(PropertyError):
+    """The FST is not functional, e.g when determinizing it as a functional FST."""
+
+
+# Maps the result codes returned by the FFI to the exception to raise.
+FFI_ERRORS = {
+    1: RustfstError,
+    2: ParseError,
+    3: InvalidStateError,
+    4: PropertyError,
+    5: SymbolTableError,
+    6: LimitExceededError,
+    7: RustfstIOError,
+    8: NotSortedError,
+    9: NonFunctionalError,
+}
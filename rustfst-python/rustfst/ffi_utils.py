@@ -7,12 +7,19 @@ from ctypes import (
     Structure,
     POINTER,
     c_int32,
+    c_uint32,
     c_uint8,
+    c_float,
+    c_size_t,
+    sizeof,
 )
 
+import sys
 from pathlib import Path
 from typing import Union
 
+from rustfst.exceptions import FFI_ERRORS, RustfstError
+
 dylib_dir = Path(__file__).parent
 dylib_files = list(dylib_dir.glob("*.so")) or list(dylib_dir.glob("*.cpython-*.so"))
 if not dylib_files:
@@ -34,6 +41,83 @@ class CBuffer(Structure):
     _fields_ = [("data", POINTER(c_uint8)), ("size", c_int32)]
 
 
+class CFloatArray(Structure):
+    _fields_ = [("data", POINTER(c_float)), ("size", c_size_t)]
+
+    @classmethod
+    def from_pylist(cls, values):
+        return cls((c_float * len(values))(*values), len(values))
+
+    def to_pylist(self):
+        return [self.data[i] for i in range(self.size)]
+
+
+class CInt32Array(Structure):
+    _fields_ = [("data", POINTER(c_int32)), ("size", c_size_t)]
+
+    def to_pylist(self):
+        return [self.data[i] for i in range(self.size)]
+
+
+class CUInt32Array(Structure):
+    _fields_ = [("data", POINTER(c_uint32)), ("size", c_size_t)]
+
+    @classmethod
+    def from_pylist(cls, values):
+        return cls((c_uint32 * len(values))(*values), len(values))
+
+
+def buffer_to_c_array(data, c_type, typecodes: str, name: str):
+    """
+    View a one-dimensional object supporting the buffer protocol (e.g a NumPy
+    array or an `array.array`) as a ctypes array without copying it. The buffer is
+    copied in one go if it is read-only or not contiguous. NumPy arrays of another
+    numeric type are converted with `numpy.ascontiguousarray`, other buffers must
+    have one of the `typecodes`. Sequences not supporting the buffer protocol
+    (e.g lists) are converted element by element.
+    """
+    try:
+        view = memoryview(data)
+    except TypeError:
+        return (c_type * len(data))(*data)
+    if view.ndim != 1:
+        raise ValueError(f"`{name}` should be one-dimensional")
+    if view.format.lstrip("@=<") not in typecodes or view.itemsize != sizeof(c_type):
+        view = memoryview(_convert_numpy_array(data, c_type, name))
+    array_type = c_type * len(view)
+    if not view.c_contiguous:
+        return array_type.from_buffer_copy(view.tobytes())
+    if view.readonly:
+        return array_type.from_buffer_copy(view)
+    return array_type.from_buffer(view)
+
+
+def _convert_numpy_array(data, c_type, name: str):
+    # NumPy is only used when the caller already passes NumPy arrays.
+    numpy = sys.modules.get("numpy")
+    if numpy is None or not isinstance(data, numpy.ndarray):
+        raise TypeError(
+            f"`{name}` has an unsupported item type '{memoryview(data).format}', "
+            f"expected an array of {c_type.__name__}"
+        )
+    dtype = numpy.dtype(c_type)
+    if dtype.kind in "iu":
+        # Signed and unsigned integers are converted both ways, the range check below
+        # rejects the values that don't fit.
+        allowed = data.dtype.kind in "iu"
+    else:
+        allowed = numpy.can_cast(data.dtype, dtype, casting="same_kind")
+    if not allowed:
+        raise TypeError(
+            f"`{name}` has an unsupported dtype {data.dtype}, expected {dtype}"
+        )
+    if dtype.kind in "iu" and data.size > 0:
+        info = numpy.iinfo(dtype)
+        if int(data.min()) < info.min or int(data.max()) > info.max:
+            raise ValueError(f"`{name}` has values out of the range of {dtype}")
+    return numpy.ascontiguousarray(data, dtype=dtype)
+
+
 @contextmanager
 def string_pointer(ptr):
     try:
@@ -49,4 +133,5 @@ def check_ffi_error(exit_code, error_context_msg):
                 ffi_error_message = string_at(ptr).decode("utf8")
             else:
                 ffi_error_message = "see stderr"
-        raise ValueError(f"{error_context_msg}: {ffi_error_message}")
+        exception = FFI_ERRORS.get(exit_code, RustfstError)
+        raise exception(f"{error_context_msg}: {ffi_error_message}")
@@ -1,10 +1,12 @@
 from __future__ import annotations
+import array
 import ctypes
 
 from rustfst.string_paths_iterator import StringPathsIterator
 from rustfst.ffi_utils import (
     lib,
     check_ffi_error,
+    buffer_to_c_array,
 )
 
 from rustfst.fst import Fst
@@ -16,7 +18,7 @@ from rustfst.weight import weight_one
 from typing import Optional, Union, TYPE_CHECKING
 from pathlib import Path
 
-from typing import List, Tuple
+from typing import Any, List, NamedTuple, Tuple
 
 if TYPE_CHECKING:
     from rustfst.algorithms.compose import ComposeConfig
@@ -24,6 +26,32 @@ if TYPE_CHECKING:
     from rustfst.algorithms.minimize import MinimizeConfig
     from rustfst.algorithms.project import ProjectType
     from rustfst.algorithms.shortest_path import ShortestPathConfig
+    from rustfst.algorithms.closure import ClosureType
+    from rustfst.algorithms.encode import EncodeTable
+    from rustfst.algorithms.push import PushType
+    from rustfst.algorithms.reweight import ReweightType
+    from rustfst.algorithms.tr_map import TrMapper
+
+_LABEL_TYPECODES = "IL"
+_WEIGHT_TYPECODES = "f"
+_OFFSET_TYPECODES = "QLN"
+_OFFSET_TYPECODE = "Q" if ctypes.sizeof(ctypes.c_size_t) == 8 else "I"
+
+
+class FstArrays(NamedTuple):
+    """
+    Contiguous arrays describing a `VectorFst` in CSR layout. The trs leaving the state `s`
+    are stored at the indices `offsets[s]:offsets[s + 1]` of `ilabels`, `olabels`,
+    `weights` and `nextstates`. Non-final states have an infinite final weight.
+    """
+
+    start: Optional[int]
+    final_weights: Any
+    offsets: Any
+    ilabels: Any
+    olabels: Any
+    weights: Any
+    nextstates: Any
 
 
 class VectorFst(Fst):
@@ -404,6 +432,124 @@ class VectorFst(Fst):
 
         return bool(is_equal.value)
 
+    @classmethod
+    def from_arrays(
+        cls,
+        final_weights,
+        offsets,
+        ilabels,
+        olabels,
+        weights,
+        nextstates,
+        start: Optional[int] = None,
+    ) -> VectorFst:
+        """
+        Build a `VectorFst` in one call from contiguous arrays in CSR layout, e.g NumPy
+        arrays. The trs leaving the state `s` are stored at the indices
+        `offsets[s]:offsets[s + 1]` of `ilabels`, `olabels`, `weights` and `nextstates`.
+
+        The arrays are read through the buffer protocol, without copy when they
+        already have the expected types: `uint32` for the labels and states,
+        `float32` for the weights and `uint64` for the offsets. NumPy arrays of
+        other numeric types are converted, lists and tuples are accepted too.
+        Args:
+            final_weights: Final weight of each state, `inf` for non-final states.
+            offsets: Offset of the first tr of each state, with `num_states + 1` elements.
+            ilabels: Input label of each tr.
+            olabels: Output label of each tr.
+            weights: Weight of each tr.
+            nextstates: Destination state of each tr.
+            start: Start state, if any.
+        Returns:
+            The newly-created `VectorFst`.
+        Raises:
+            ValueError: The arrays are not consistent.
+            TypeError: One of the arrays has an unsupported item type.
+        """
+        num_states = len(final_weights)
+        if len(offsets) != num_states + 1:
+            raise ValueError(
+                f"`offsets` should have {num_states + 1} elements, found {len(offsets)}"
+            )
+        num_trs = len(ilabels)
+        for name, values in (
+            ("olabels", olabels),
+            ("weights", weights),
+            ("nextstates", nextstates),
+        ):
+            if len(values) != num_trs:
+                raise ValueError(
+                    f"`{name}` should have {num_trs} elements, found {len(values)}"
+                )
+        if offsets[num_states] != num_trs:
+            raise ValueError(
+                f"The last offset should be {num_trs}, found {offsets[num_states]}"
+            )
+
+        c_start = None if start is None else ctypes.byref(ctypes.c_uint32(start))
+        fst = ctypes.pointer(ctypes.c_void_p())
+        ret_code = lib.vec_fst_from_arrays(
+            ctypes.c_size_t(num_states),
+            c_start,
+            buffer_to_c_array(
+                final_weights, ctypes.c_float, _WEIGHT_TYPECODES, "final_weights"
+            ),
+            buffer_to_c_array(offsets, ctypes.c_size_t, _OFFSET_TYPECODES, "offsets"),
+            buffer_to_c_array(ilabels, ctypes.c_uint32, _LABEL_TYPECODES, "ilabels"),
+            buffer_to_c_array(olabels, ctypes.c_uint32, _LABEL_TYPECODES, "olabels"),
+            buffer_to_c_array(weights, ctypes.c_float, _WEIGHT_TYPECODES, "weights"),
+            buffer_to_c_array(
+                nextstates, ctypes.c_uint32, _LABEL_TYPECODES, "nextstates"
+            ),
+            ctypes.byref(fst),
+        )
+        err_msg = "`from_arrays` failed"
+        check_ffi_error(ret_code, err_msg)
+
+        return cls(ptr=fst)
+
+    def to_arrays(self) -> FstArrays:
+        """
+        Export the `VectorFst` into contiguous arrays in CSR layout, see `from_arrays`.
+        The arrays are `array.array` objects supporting the buffer protocol, they can
+        be wrapped without copy with `numpy.frombuffer`.
+        Returns:
+            The start state and the arrays describing the Fst.
+        """
+        num_states = self.num_states()
+        num_trs = ctypes.c_size_t()
+        ret_code = lib.vec_fst_total_num_trs(self.ptr, ctypes.byref(num_trs))
+        err_msg = "Error getting the number of trs"
+        check_ffi_error(ret_code, err_msg)
+        num_trs = num_trs.value
+        offset_size = ctypes.sizeof(ctypes.c_size_t)
+
+        arrays = FstArrays(
+            start=self.start(),
+            final_weights=array.array("f", bytes(4 * num_states)),
+            offsets=array.array(_OFFSET_TYPECODE, bytes(offset_size * (num_states + 1))),
+            ilabels=array.array("I", bytes(4 * num_trs)),
+            olabels=array.array("I", bytes(4 * num_trs)),
+            weights=array.array("f", bytes(4 * num_trs)),
+            nextstates=array.array("I", bytes(4 * num_trs)),
+        )
+
+        ret_code = lib.vec_fst_to_arrays(
+            self.ptr,
+            ctypes.c_size_t(num_states),
+            ctypes.c_size_t(num_trs),
+            (ctypes.c_float * num_states).from_buffer(arrays.final_weights),
+            (ctypes.c_size_t * (num_states + 1)).from_buffer(arrays.offsets),
+            (ctypes.c_uint32 * num_trs).from_buffer(arrays.ilabels),
+            (ctypes.c_uint32 * num_trs).from_buffer(arrays.olabels),
+            (ctypes.c_float * num_trs).from_buffer(arrays.weights),
+            (ctypes.c_uint32 * num_trs).from_buffer(arrays.nextstates),
+        )
+        err_msg = "`to_arrays` failed"
+        check_ffi_error(ret_code, err_msg)
+
+        return arrays
+
     def copy(self) -> VectorFst:
         """
         Returns:
@@ -739,6 +885,157 @@ class VectorFst(Fst):
 
         return invert(self)
 
+    def closure(self, closure_type: Union[ClosureType, None] = None) -> VectorFst:
+        """
+        Compute the Kleene closure of the Fst in-place.
+        Args:
+            closure_type: Star or plus closure. Defaults to star.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.closure import closure, ClosureType  # noqa: W0621
+
+        if closure_type is None:
+            closure_type = ClosureType.CLOSURE_STAR
+        return closure(self, closure_type)
+
+    def push_weights(
+        self, reweight_type: Union[ReweightType, None] = None
+    ) -> VectorFst:
+        """
+        Push the weights of the Fst in-place.
+        Args:
+            reweight_type: Push toward the initial state (default) or toward the final states.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.push import push_weights
+        from rustfst.algorithms.reweight import ReweightType  # noqa: W0621
+
+        if reweight_type is None:
+            reweight_type = ReweightType.REWEIGHT_TO_INITIAL
+        return push_weights(self, reweight_type)
+
+    def push(
+        self,
+        reweight_type: Union[ReweightType, None] = None,
+        push_type: Union[PushType, None] = None,
+    ) -> VectorFst:
+        """
+        Push the weights and/or the labels of the Fst.
+        Args:
+            reweight_type: Push toward the initial state (default) or toward the final states.
+            push_type: What to push. Defaults to the weights.
+        Returns:
+            Newly-created Fst with the pushed weights and/or labels.
+        """
+        from rustfst.algorithms.push import push, PushType  # noqa: W0621
+        from rustfst.algorithms.reweight import ReweightType  # noqa: W0621
+
+        if reweight_type is None:
+            reweight_type = ReweightType.REWEIGHT_TO_INITIAL
+        if push_type is None:
+            push_type = PushType.PUSH_WEIGHTS
+        return push(self, reweight_type, push_type)
+
+    def encode(
+        self, encode_weights: bool = False, encode_labels: bool = True
+    ) -> EncodeTable:
+        """
+        Encode the labels and/or weights of the Fst in-place.
+        Args:
+            encode_weights: Whether the weights are encoded.
+            encode_labels: Whether the pairs of labels are encoded.
+        Returns:
+            The EncodeTable needed to decode the Fst.
+        """
+        from rustfst.algorithms.encode import encode
+
+        return encode(self, encode_weights, encode_labels)
+
+    def decode(self, encode_table: EncodeTable) -> VectorFst:
+        """
+        Decode the Fst in-place.
+        Args:
+            encode_table: Table returned by `encode`.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.encode import decode
+
+        return decode(self, encode_table)
+
+    def shortest_distance(self, reverse: bool = False) -> List[float]:
+        """
+        Compute the shortest distance from the initial state to every state.
+        Args:
+            reverse: If true, compute the shortest distance from every state to the final states.
+        Returns:
+            The shortest distance of each state.
+        """
+        from rustfst.algorithms.shortest_distance import shortest_distance
+
+        return shortest_distance(self, reverse)
+
+    def rm_final_epsilon(self) -> VectorFst:
+        """
+        Remove final states that have epsilon-only input transitions in-place.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.rm_final_epsilon import rm_final_epsilon
+
+        return rm_final_epsilon(self)
+
+    def reweight(
+        self, potentials: List[float], reweight_type: ReweightType
+    ) -> VectorFst:
+        """
+        Reweight the Fst in-place according to the potentials.
+        Args:
+            potentials: Potential of each state.
+            reweight_type: Reweight toward the initial state or toward the final states.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.reweight import reweight
+
+        return reweight(self, potentials, reweight_type)
+
+    def state_sort(self, order: List[int]) -> VectorFst:
+        """
+        Sort the states of the Fst in-place. The state `s` is renumbered `order[s]`.
+        Args:
+            order: New id of each state.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.state_sort import state_sort
+
+        return state_sort(self, order)
+
+    def condense(self) -> Tuple[List[int], VectorFst]:
+        """
+        Condense each strongly connected component of the Fst to a single state.
+        Returns:
+            The SCC of each state and the newly-created condensed Fst.
+        """
+        from rustfst.algorithms.condense import condense
+
+        return condense(self)
+
+    def tr_map(self, mapper: TrMapper) -> VectorFst:
+        """
+        Apply a mapper to all the transitions and final weights of the Fst in-place.
+        Args:
+            mapper: Mapper to apply.
+        Returns:
+            self
+        """
+        from rustfst.algorithms.tr_map import tr_map
+
+        return tr_map(self, mapper)
+
     def __add__(self, other: VectorFst) -> VectorFst:
         """
         `fst_1 + fst_2` is a shortcut to perform the concatenation of `fst_1` and `fst_2`.
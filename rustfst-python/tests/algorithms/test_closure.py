@@ -0,0 +1,43 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.closure import ClosureType
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s2, 1.0)
+
+    fst.add_tr(s1, Tr(1, 2, 2.0, s2))
+
+    return fst
+
+
+def test_closure_plus():
+    fst = build_fst()
+
+    rv = fst.closure(ClosureType.CLOSURE_PLUS)
+    assert rv == fst
+
+    expected_fst = build_fst()
+    expected_fst.add_tr(1, Tr(0, 0, 1.0, 0))
+
+    assert fst == expected_fst
+
+
+def test_closure_star():
+    fst = build_fst()
+
+    fst.closure()
+
+    expected_fst = build_fst()
+    expected_fst.add_tr(1, Tr(0, 0, 1.0, 0))
+    s3 = expected_fst.add_state()
+    expected_fst.set_start(s3)
+    expected_fst.set_final(s3, 0.0)
+    expected_fst.add_tr(s3, Tr(0, 0, 0.0, 0))
+
+    assert fst == expected_fst
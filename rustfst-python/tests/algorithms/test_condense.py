@@ -0,0 +1,33 @@
+from rustfst import VectorFst, Tr
+
+
+def test_condense():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 1.0)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s2, Tr(2, 2, 2.0, s1))
+    fst.add_tr(s2, Tr(3, 3, 3.0, s3))
+
+    scc, condensed_fst = fst.condense()
+
+    assert len(scc) == 3
+    assert scc[0] == scc[1]
+    assert scc[0] != scc[2]
+
+    # Expected FST
+    expected_fst = VectorFst()
+    for _ in range(2):
+        expected_fst.add_state()
+
+    expected_fst.set_start(scc[0])
+    expected_fst.set_final(scc[2], 1.0)
+    expected_fst.add_tr(scc[0], Tr(3, 3, 3.0, scc[2]))
+
+    assert condensed_fst == expected_fst
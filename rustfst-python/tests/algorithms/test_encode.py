@@ -0,0 +1,61 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.encode import encode, decode
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 1.5)
+
+    fst.add_tr(s1, Tr(1, 2, 1.0, s2))
+    fst.add_tr(s1, Tr(3, 2, 2.0, s2))
+    fst.add_tr(s2, Tr(1, 2, 3.0, s3))
+
+    return fst
+
+
+def test_encode_decode_labels():
+    fst = build_fst()
+
+    table = fst.encode()
+    for state in fst.states():
+        for tr in fst.trs(state):
+            assert tr.ilabel == tr.olabel
+    assert [tr.ilabel for tr in fst.trs(0)] == [1, 2]
+    assert [tr.weight for tr in fst.trs(0)] == [1.0, 2.0]
+
+    rv = fst.decode(table)
+    assert rv == fst
+    assert fst == build_fst()
+
+
+def test_encode_decode_weights_and_labels():
+    fst = build_fst()
+
+    table = encode(fst, encode_weights=True, encode_labels=True)
+    for state in fst.states():
+        for tr in fst.trs(state):
+            assert tr.ilabel == tr.olabel
+            assert tr.weight == 0.0
+    assert fst.final(2) == 0.0
+
+    # The table can be used to decode several FSTs.
+    fst_copy = fst.copy()
+    decode(fst, table)
+    decode(fst_copy, table)
+    assert fst == build_fst()
+    assert fst_copy == build_fst()
+
+
+def test_encode_nothing():
+    fst = build_fst()
+    try:
+        encode(fst, encode_weights=False, encode_labels=False)
+        assert False
+    except ValueError:
+        pass
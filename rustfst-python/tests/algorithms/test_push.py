@@ -0,0 +1,98 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.push import (
+    PushType,
+    PushWeightsConfig,
+    push,
+    push_weights_with_config,
+)
+from rustfst.algorithms.reweight import ReweightType
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 3.0)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s2, Tr(2, 2, 2.0, s3))
+
+    return fst
+
+
+def build_expected_fst(start_weight):
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+    s3 = expected_fst.add_state()
+
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s3, 0.0)
+
+    expected_fst.add_tr(s1, Tr(1, 1, start_weight, s2))
+    expected_fst.add_tr(s2, Tr(2, 2, 0.0, s3))
+
+    return expected_fst
+
+
+def test_push_weights():
+    fst = build_fst()
+
+    rv = fst.push_weights()
+    assert rv == fst
+
+    assert fst == build_expected_fst(6.0)
+
+
+def test_push_weights_remove_total_weight():
+    fst = build_fst()
+    config = PushWeightsConfig(remove_total_weight=True)
+
+    push_weights_with_config(fst, ReweightType.REWEIGHT_TO_INITIAL, config)
+
+    assert fst == build_expected_fst(0.0)
+
+
+def test_push_to_final():
+    fst = build_fst()
+
+    pushed_fst = fst.push(ReweightType.REWEIGHT_TO_FINAL)
+
+    expected_fst = VectorFst()
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+    s3 = expected_fst.add_state()
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s3, 6.0)
+    expected_fst.add_tr(s1, Tr(1, 1, 0.0, s2))
+    expected_fst.add_tr(s2, Tr(2, 2, 0.0, s3))
+
+    assert pushed_fst == expected_fst
+    assert fst == build_fst()
+
+
+def test_push_labels():
+    fst = VectorFst()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+    fst.set_start(s1)
+    fst.set_final(s3, 0.0)
+    fst.add_tr(s1, Tr(1, 0, 1.0, s2))
+    fst.add_tr(s2, Tr(2, 3, 2.0, s3))
+
+    pushed_fst = push(
+        fst,
+        ReweightType.REWEIGHT_TO_INITIAL,
+        PushType.PUSH_WEIGHTS | PushType.PUSH_LABELS,
+    )
+
+    trs = list(pushed_fst.trs(s1))
+    assert len(trs) == 1
+    assert trs[0].olabel == 3
+    assert trs[0].weight == 3.0
@@ -0,0 +1,51 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.reweight import ReweightType
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 3.0)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s1, Tr(2, 2, 4.0, s3))
+    fst.add_tr(s2, Tr(3, 3, 2.0, s3))
+
+    return fst
+
+
+def test_reweight_to_initial():
+    fst = build_fst()
+    potentials = fst.shortest_distance(reverse=True)
+
+    rv = fst.reweight(potentials, ReweightType.REWEIGHT_TO_INITIAL)
+    assert rv == fst
+
+    # Reweighting with the distances to the final states is pushing the weights.
+    expected_fst = build_fst()
+    expected_fst.push_weights()
+
+    assert fst == expected_fst
+
+
+def test_reweight_to_final():
+    fst = build_fst()
+
+    fst.reweight([0.0, 1.0, 2.0], ReweightType.REWEIGHT_TO_FINAL)
+
+    expected_fst = VectorFst()
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+    s3 = expected_fst.add_state()
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s3, 5.0)
+    expected_fst.add_tr(s1, Tr(1, 1, 0.0, s2))
+    expected_fst.add_tr(s1, Tr(2, 2, 2.0, s3))
+    expected_fst.add_tr(s2, Tr(3, 3, 1.0, s3))
+
+    assert fst == expected_fst
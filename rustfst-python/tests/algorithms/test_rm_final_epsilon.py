@@ -0,0 +1,31 @@
+from rustfst import VectorFst, Tr
+
+
+def test_rm_final_epsilon():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 3.0)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s2, Tr(0, 0, 2.0, s3))
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+
+    expected_fst.set_start(s1)
+    expected_fst.set_final(s2, 5.0)
+
+    expected_fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+
+    rv = fst.rm_final_epsilon()
+    assert rv == fst
+
+    assert expected_fst == fst
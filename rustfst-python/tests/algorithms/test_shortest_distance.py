@@ -0,0 +1,38 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.shortest_distance import (
+    ShortestDistanceConfig,
+    shortest_distance_with_config,
+)
+
+
+def build_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 0.5)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s1, Tr(2, 2, 5.0, s3))
+    fst.add_tr(s2, Tr(3, 3, 2.0, s3))
+
+    return fst
+
+
+def test_shortest_distance():
+    fst = build_fst()
+    assert fst.shortest_distance() == [0.0, 1.0, 3.0]
+
+
+def test_shortest_distance_reverse():
+    fst = build_fst()
+    assert fst.shortest_distance(reverse=True) == [3.5, 2.5, 0.5]
+
+
+def test_shortest_distance_with_config():
+    fst = build_fst()
+    config = ShortestDistanceConfig(delta=1e-3)
+    assert shortest_distance_with_config(fst, False, config) == [0.0, 1.0, 3.0]
@@ -0,0 +1,45 @@
+from rustfst import VectorFst, Tr
+
+
+def test_state_sort():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 1.0)
+
+    fst.add_tr(s1, Tr(1, 1, 1.0, s2))
+    fst.add_tr(s2, Tr(2, 2, 2.0, s3))
+
+    # Expected FST
+    expected_fst = VectorFst()
+
+    s1 = expected_fst.add_state()
+    s2 = expected_fst.add_state()
+    s3 = expected_fst.add_state()
+
+    expected_fst.set_start(s3)
+    expected_fst.set_final(s2, 1.0)
+
+    expected_fst.add_tr(s1, Tr(2, 2, 2.0, s2))
+    expected_fst.add_tr(s3, Tr(1, 1, 1.0, s1))
+
+    rv = fst.state_sort([2, 0, 1])
+    assert rv == fst
+
+    assert expected_fst == fst
+
+
+def test_state_sort_wrong_order():
+    fst = VectorFst()
+    fst.add_state()
+    fst.add_state()
+
+    try:
+        fst.state_sort([0])
+        assert False
+    except ValueError:
+        pass
@@ -0,0 +1,77 @@
+from rustfst import VectorFst, Tr
+from rustfst.algorithms.project import ProjectType
+from rustfst.algorithms.tr_map import (
+    IdentityTrMapper,
+    InputEpsilonMapper,
+    InvertMapper,
+    InvertWeightMapper,
+    OutputEpsilonMapper,
+    PlusMapper,
+    ProjectMapper,
+    QuantizeMapper,
+    RmWeightMapper,
+    TimesMapper,
+    tr_map,
+)
+
+
+def build_fst(weights=(1.0, 2.0), final_weight=3.0, labels=((1, 2), (3, 4))):
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, final_weight)
+
+    fst.add_tr(s1, Tr(labels[0][0], labels[0][1], weights[0], s2))
+    fst.add_tr(s2, Tr(labels[1][0], labels[1][1], weights[1], s3))
+
+    return fst
+
+
+def test_tr_map_identity():
+    fst = build_fst()
+
+    rv = fst.tr_map(IdentityTrMapper())
+    assert rv == fst
+
+    assert fst == build_fst()
+
+
+def test_tr_map_labels():
+    fst = tr_map(build_fst(), InputEpsilonMapper())
+    assert fst == build_fst(labels=((0, 2), (0, 4)))
+
+    fst = tr_map(build_fst(), OutputEpsilonMapper())
+    assert fst == build_fst(labels=((1, 0), (3, 0)))
+
+    fst = tr_map(build_fst(), InvertMapper())
+    assert fst == build_fst(labels=((2, 1), (4, 3)))
+
+    fst = tr_map(build_fst(), ProjectMapper(ProjectType.PROJECT_OUTPUT))
+    assert fst == build_fst(labels=((2, 2), (4, 4)))
+
+
+def test_tr_map_weights():
+    fst = tr_map(build_fst(), TimesMapper(2.0))
+    assert fst == build_fst(weights=(3.0, 4.0), final_weight=5.0)
+
+    # Plus is the min in the tropical semiring.
+    fst = tr_map(build_fst(), PlusMapper(1.5))
+    assert fst == build_fst(weights=(1.0, 1.5), final_weight=1.5)
+
+    fst = tr_map(build_fst(), RmWeightMapper())
+    assert fst == build_fst(weights=(0.0, 0.0), final_weight=0.0)
+
+    fst = tr_map(build_fst(), InvertWeightMapper())
+    assert fst == build_fst(weights=(-1.0, -2.0), final_weight=-3.0)
+
+
+def test_tr_map_quantize():
+    fst = build_fst(weights=(1.1, 2.4), final_weight=2.9)
+
+    fst.tr_map(QuantizeMapper(delta=0.5))
+
+    assert fst == build_fst(weights=(1.0, 2.5), final_weight=3.0)
@@ -0,0 +1,105 @@
+from pathlib import Path
+from tempfile import TemporaryDirectory
+
+import pytest
+
+from rustfst import (
+    VectorFst,
+    Tr,
+    RustfstError,
+    ParseError,
+    InvalidStateError,
+    SymbolTableError,
+    RustfstIOError,
+    PropertyError,
+    NotSortedError,
+    NonFunctionalError,
+)
+from rustfst.algorithms.compose import compose
+from rustfst.algorithms.determinize import (
+    DeterminizeConfig,
+    DeterminizeType,
+    determinize_with_config,
+)
+
+
+def test_invalid_state_error():
+    fst = VectorFst()
+    fst.add_state()
+
+    with pytest.raises(InvalidStateError):
+        fst.set_start(3)
+
+    with pytest.raises(InvalidStateError):
+        fst.add_tr(2, Tr(1, 1, 1.0, 0))
+
+    # Backward compatibility : the errors are still ValueErrors.
+    with pytest.raises(ValueError):
+        fst.set_final(5)
+
+
+def test_io_and_parse_errors():
+    with TemporaryDirectory() as tmp_dir:
+        path = Path(tmp_dir) / "fst.bin"
+
+        with pytest.raises(RustfstIOError):
+            VectorFst.read(path)
+
+        with pytest.raises(OSError):
+            VectorFst.read(path)
+
+        path.write_bytes(b"not an fst")
+        with pytest.raises(ParseError):
+            VectorFst.read(path)
+
+
+def test_symbol_table_error():
+    fst = VectorFst()
+    s0 = fst.add_state()
+    s1 = fst.add_state()
+    fst.set_start(s0)
+    fst.add_tr(s0, Tr(1, 1, 1.0, s1))
+    fst.set_final(s1)
+
+    with pytest.raises(SymbolTableError):
+        fst.string_paths()
+
+
+def test_non_functional_error():
+    fst = VectorFst()
+    s0 = fst.add_state()
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    fst.set_start(s0)
+    fst.add_tr(s0, Tr(1, 2, 1.0, s1))
+    fst.add_tr(s0, Tr(1, 3, 1.0, s2))
+    fst.set_final(s1)
+    fst.set_final(s2)
+
+    config = DeterminizeConfig(DeterminizeType.DETERMINIZE_FUNCTIONAL)
+    with pytest.raises(NonFunctionalError):
+        determinize_with_config(fst, config)
+
+
+def test_not_sorted_error():
+    fst1 = VectorFst()
+    s0 = fst1.add_state()
+    s1 = fst1.add_state()
+    fst1.set_start(s0)
+    fst1.add_tr(s0, Tr(1, 3, 1.0, s1))
+    fst1.add_tr(s0, Tr(2, 2, 1.0, s1))
+    fst1.set_final(s1)
+
+    fst2 = fst1.copy()
+    fst2.add_tr(s0, Tr(1, 1, 1.0, s1))
+
+    with pytest.raises(NotSortedError):
+        compose(fst1, fst2)
+
+    with pytest.raises(PropertyError):
+        compose(fst1, fst2)
+
+
+def test_base_class():
+    with pytest.raises(RustfstError):
+        VectorFst().set_start(0)
@@ -1,3 +1,4 @@
+from array import array
 from pathlib import Path
 
 from rustfst import VectorFst, Tr, SymbolTable, ConstFst
@@ -284,6 +285,164 @@ def test_fst_io_bytes():
     assert fst_loaded == fst
 
 
+def build_csr_fst():
+    fst = VectorFst()
+
+    s1 = fst.add_state()
+    s2 = fst.add_state()
+    s3 = fst.add_state()
+
+    fst.set_start(s1)
+    fst.set_final(s3, 0.5)
+
+    fst.add_tr(s1, Tr(1, 2, 1.0, s2))
+    fst.add_tr(s1, Tr(3, 4, 2.0, s3))
+    fst.add_tr(s2, Tr(5, 6, 3.0, s3))
+    fst.add_tr(s3, Tr(0, 0, 4.0, s1))
+
+    return fst
+
+
+def test_fst_from_arrays():
+    fst = VectorFst.from_arrays(
+        final_weights=array("f", [float("inf"), float("inf"), 0.5]),
+        offsets=array("Q", [0, 2, 3, 4]),
+        ilabels=array("I", [1, 3, 5, 0]),
+        olabels=array("I", [2, 4, 6, 0]),
+        weights=array("f", [1.0, 2.0, 3.0, 4.0]),
+        nextstates=array("I", [1, 2, 2, 0]),
+        start=0,
+    )
+
+    assert fst == build_csr_fst()
+
+
+def test_fst_from_arrays_converts_sequences():
+    fst = VectorFst.from_arrays(
+        final_weights=[float("inf"), float("inf"), 0.5],
+        offsets=[0, 2, 3, 4],
+        ilabels=memoryview(array("I", [1, 3, 5, 0])).toreadonly(),
+        olabels=[2, 4, 6, 0],
+        # Not contiguous.
+        weights=memoryview(array("f", [1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 4.0, 0.0]))[::2],
+        nextstates=(1, 2, 2, 0),
+        start=0,
+    )
+
+    assert fst == build_csr_fst()
+
+
+def test_fst_from_arrays_unsupported_types():
+    arrays = dict(
+        final_weights=array("f", [float("inf"), float("inf"), 0.5]),
+        offsets=array("Q", [0, 2, 3, 4]),
+        ilabels=array("I", [1, 3, 5, 0]),
+        olabels=array("I", [2, 4, 6, 0]),
+        weights=array("f", [1.0, 2.0, 3.0, 4.0]),
+        nextstates=array("I", [1, 2, 2, 0]),
+    )
+    for name, values in (
+        # Signed labels could be negative.
+        ("ilabels", array("i", [1, 3, 5, 0])),
+        ("nextstates", array("i", [1, 2, 2, 0])),
+        ("weights", array("d", [1.0, 2.0, 3.0, 4.0])),
+    ):
+        with pytest.raises(TypeError):
+            VectorFst.from_arrays(**dict(arrays, **{name: values}))
+
+
+def test_fst_from_arrays_numpy():
+    np = pytest.importorskip("numpy")
+
+    fst = VectorFst.from_arrays(
+        final_weights=np.array([np.inf, np.inf, 0.5]),
+        offsets=np.array([0, 2, 3, 4]),
+        # int64 narrowed to uint32.
+        ilabels=np.array([1, 3, 5, 0], dtype=np.int64),
+        # Not contiguous, already uint32.
+        olabels=np.array([2, 0, 4, 0, 6, 0, 0, 0], dtype=np.uint32)[::2],
+        # float64 narrowed to float32.
+        weights=np.array([1.0, 2.0, 3.0, 4.0], dtype=np.float64),
+        # Not contiguous and converted.
+        nextstates=np.array([1, 0, 2, 0, 2, 0, 0, 0], dtype=np.int64)[::2],
+        start=0,
+    )
+
+    assert fst == build_csr_fst()
+
+
+def test_fst_from_arrays_numpy_errors():
+    np = pytest.importorskip("numpy")
+
+    arrays = dict(
+        final_weights=np.array([np.inf, np.inf, 0.5], dtype=np.float32),
+        offsets=np.array([0, 2, 3, 4], dtype=np.uint64),
+        ilabels=np.array([1, 3, 5, 0], dtype=np.uint32),
+        olabels=np.array([2, 4, 6, 0], dtype=np.uint32),
+        weights=np.array([1.0, 2.0, 3.0, 4.0], dtype=np.float32),
+        nextstates=np.array([1, 2, 2, 0], dtype=np.uint32),
+    )
+    for name, values in (
+        ("ilabels", np.array([-1, 3, 5, 0], dtype=np.int64)),
+        ("olabels", np.array([2**32, 4, 6, 0], dtype=np.int64)),
+    ):
+        with pytest.raises(ValueError):
+            VectorFst.from_arrays(**dict(arrays, **{name: values}))
+    for name, values in (
+        ("ilabels", np.array([1.0, 3.0, 5.0, 0.0])),
+        ("offsets", np.array([0.0, 2.0, 3.0, 4.0])),
+        ("weights", np.array([1.0, 2.0, 3.0, 4.0], dtype=np.complex64)),
+    ):
+        with pytest.raises(TypeError):
+            VectorFst.from_arrays(**dict(arrays, **{name: values}))
+
+
+def test_fst_to_arrays():
+    fst = build_csr_fst()
+
+    arrays = fst.to_arrays()
+
+    assert arrays.start == 0
+    assert list(arrays.final_weights) == [float("inf"), float("inf"), 0.5]
+    assert list(arrays.offsets) == [0, 2, 3, 4]
+    assert list(arrays.ilabels) == [1, 3, 5, 0]
+    assert list(arrays.olabels) == [2, 4, 6, 0]
+    assert list(arrays.weights) == [1.0, 2.0, 3.0, 4.0]
+    assert list(arrays.nextstates) == [1, 2, 2, 0]
+
+    assert VectorFst.from_arrays(*arrays[1:], start=arrays.start) == fst
+
+
+def test_fst_arrays_empty():
+    arrays = VectorFst().to_arrays()
+    assert list(arrays.offsets) == [0]
+
+    fst = VectorFst.from_arrays([], [0], [], [], [], [])
+    assert fst.num_states() == 0
+
+
+def test_fst_from_arrays_errors():
+    with pytest.raises(ValueError):
+        # Wrong number of offsets.
+        VectorFst.from_arrays([0.0], [0], [], [], [], [])
+
+    with pytest.raises(ValueError):
+        # Arrays of different sizes.
+        VectorFst.from_arrays([0.0], [0, 1], [1], [1, 2], [0.0], [0])
+
+    with pytest.raises(ValueError):
+        # Decreasing offsets.
+        VectorFst.from_arrays([0.0, 0.0], [0, 1, 0], [], [], [], [])
+
+    with pytest.raises(ValueError):
+        # Invalid nextstate.
+        VectorFst.from_arrays([0.0], [0, 1], [1], [1], [0.0], [1])
+
+    with pytest.raises(ValueError):
+        # Invalid start state.
+        VectorFst.from_arrays([0.0], [0, 0], [], [], [], [], start=1)
+
+
 def test_fst_unset_final():
     fst = VectorFst()
 
@@ -33,6 +33,8 @@
 #include "fst_018/fst_018.h"
 #include "fst_019/fst_019.h"
 #include "fst_020/fst_020.h"
+#include "fst_021/fst_021.h"
+#include "fst_022/fst_022.h"
 
 #include "symt_000/symt_000.h"
 #include "symt_001/symt_001.h"
@@ -1088,6 +1090,16 @@ void compute_fst_data(const F& fst_test_data, const string fst_name) {
     std::ofstream strm_aligned((dir_path + "raw_const_aligned.fst").c_str(), std::ios_base::out | std::ios_base::binary);
     raw_const_fst.Write(strm_aligned, write_opts);
 
+    // Lookahead MatcherFst, stored in the add-on format.
+    fst::MatcherFst<
+      fst::ConstFst<typename F::MyArc>,
+      fst::LabelLookAheadMatcher<fst::SortedMatcher<fst::ConstFst<typename F::MyArc>>, fst::olabel_lookahead_flags>,
+      fst::olabel_lookahead_fst_type,
+      fst::LabelLookAheadRelabeler<typename F::MyArc>
+    > raw_olabel_lookahead_fst(raw_fst);
+    data["raw_olabel_lookahead_bin_path"] = "raw_olabel_lookahead.fst";
+    raw_olabel_lookahead_fst.Write(dir_path + "raw_olabel_lookahead.fst");
+
     std::cout << "Invert" << std::endl;
     compute_fst_invert(raw_fst, data, dir_path);
 
@@ -1328,6 +1340,12 @@ int main() {
         auto w2 = GW(SW(2), W(3.1));
         compute_weight_data(w1, w2, "weight_012");
     }
+    compute_weight_data(
+        fst::TropicalWeightTpl<double>(1.2),
+        fst::TropicalWeightTpl<double>(3.2),
+        "weight_013"
+    );
+    compute_weight_data(fst::Log64Weight(1.2), fst::Log64Weight(3.2), "weight_014");
 
     compute_fst_data(FstTestData000(), "fst_000");
     compute_fst_data(FstTestData001(), "fst_001");
@@ -1350,4 +1368,6 @@ int main() {
     compute_fst_data(FstTestData018(), "fst_018");
     compute_fst_data(FstTestData019(), "fst_019");
     compute_fst_data(FstTestData020(), "fst_020");
+    compute_fst_data(FstTestData021(), "fst_021");
+    compute_fst_data(FstTestData022(), "fst_022");
 }
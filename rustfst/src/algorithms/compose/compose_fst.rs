@@ -13,6 +13,7 @@ use crate::algorithms::compose::{
 use crate::algorithms::lazy::{
     FstCache, LazyFst, SerializableCache, SerializableLazyFst, SimpleVecCache,
 };
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::parsers::SerializeBinary;
@@ -158,6 +159,11 @@ where
         Self::new_with_options(fst1, fst2, ComposeFstOpOptions::default())
     }
 
+    /// Bounds the number of states and transitions computed, see `AlgorithmLimits`.
+    pub fn with_limits(self, limits: AlgorithmLimits) -> Self {
+        ComposeFst(self.0.with_limits(limits))
+    }
+
     /// Turns the Lazy FST into a static one.
     pub fn compute<F: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F> {
         self.0.compute()
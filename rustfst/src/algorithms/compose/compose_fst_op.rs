@@ -15,12 +15,13 @@ use crate::algorithms::compose::matchers::{IterItemMatcher, MatcherFlags};
 use crate::algorithms::compose::matchers::{MatchType, Matcher, REQUIRE_PRIORITY};
 use crate::algorithms::compose::{ComposeFstOpOptions, ComposeStateTuple};
 use crate::algorithms::lazy::{AccessibleOpState, FstOp, SerializableOpState, StateTable};
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::mutable_properties::compose_properties;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::Fst;
 use crate::parsers::SerializeBinary;
 use crate::semirings::Semiring;
-use crate::{StateId, Tr, Trs, TrsVec, EPS_LABEL, NO_LABEL};
+use crate::{Error, StateId, Tr, Trs, TrsVec, EPS_LABEL, NO_LABEL};
 
 #[derive(Debug, Clone)]
 pub struct ComposeFstOpState<T: Hash + Eq + Clone> {
@@ -84,6 +85,7 @@ where
     properties: FstProperties,
     fst1: B1,
     fst2: B2,
+    limits: AlgorithmLimits,
 }
 
 impl<W, F1, F2, B1, B2, M1, M2, CFB> Clone for ComposeFstOp<W, F1, F2, B1, B2, M1, M2, CFB>
@@ -105,6 +107,7 @@ where
             properties: self.properties,
             fst1: self.fst1.clone(),
             fst2: self.fst2.clone(),
+            limits: self.limits.clone(),
         }
     }
 }
@@ -163,6 +166,7 @@ where
             properties,
             fst1,
             fst2,
+            limits: AlgorithmLimits::default(),
         })
     }
 
@@ -170,12 +174,18 @@ where
         if matcher1.flags().contains(MatcherFlags::REQUIRE_MATCH)
             && matcher1.match_type(true)? != MatchType::MatchOutput
         {
-            bail!("ComposeFst: 1st argument cannot perform required matching (sort?)")
+            return Err(Error::NotSorted(
+                "ComposeFst: 1st argument cannot perform required matching (sort?)".to_string(),
+            )
+            .into());
         }
         if matcher2.flags().contains(MatcherFlags::REQUIRE_MATCH)
             && matcher2.match_type(true)? != MatchType::MatchInput
         {
-            bail!("ComposeFst: 2nd argument cannot perform required matching (sort?)")
+            return Err(Error::NotSorted(
+                "ComposeFst: 2nd argument cannot perform required matching (sort?)".to_string(),
+            )
+            .into());
         }
 
         let type1 = matcher1.match_type(false)?;
@@ -191,7 +201,7 @@ where
         } else if matcher2.match_type(true)? == MatchType::MatchInput {
             MatchType::MatchInput
         } else {
-            bail!("ComposeFst: 1st argument cannot match on output labels and 2nd argument cannot match on input labels (sort?).")
+            return Err(Error::NotSorted("ComposeFst: 1st argument cannot match on output labels and 2nd argument cannot match on input labels (sort?).".to_string()).into());
         };
         Ok(mt)
     }
@@ -404,6 +414,7 @@ where
     }
 
     fn compute_trs(&self, state: StateId) -> Result<TrsVec<W>> {
+        self.limits.check_interrupted()?;
         let tuple = self.compose_state.state_table.find_tuple(state);
         let s1 = tuple.s1;
         let s2 = tuple.s2;
@@ -451,4 +462,8 @@ where
     fn properties(&self) -> FstProperties {
         self.properties
     }
+
+    fn set_limits(&mut self, limits: AlgorithmLimits) {
+        self.limits = limits;
+    }
 }
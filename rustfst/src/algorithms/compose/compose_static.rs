@@ -10,6 +10,7 @@ use crate::algorithms::compose::compose_filters::{
 };
 use crate::algorithms::compose::matchers::{Matcher, SigmaMatcher, SortedMatcher};
 use crate::algorithms::compose::ComposeFst;
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_traits::{AllocableFst, ExpandedFst, Fst, MutableFst};
 use crate::prelude::compose::matchers::{MatchType, MatcherRewriteMode};
 use crate::prelude::compose::ComposeFstOpOptions;
@@ -114,7 +115,8 @@ macro_rules! run_compose {
         $f1: ty, $f2: ty,
         $builder: tt,
         $matcher1: expr, $matcher1_ty: ty,
-        $matcher2: expr, $matcher2_ty: ty
+        $matcher2: expr, $matcher2_ty: ty,
+        $limits: expr
     ) => {{
         let compose_fst_op_opts = ComposeFstOpOptions::new($matcher1, $matcher2, None, None);
         ComposeFst::<
@@ -127,6 +129,7 @@ macro_rules! run_compose {
             _,
             $builder<_, _, _, _, _, $matcher1_ty, $matcher2_ty>,
         >::new_with_options($fst1, $fst2, compose_fst_op_opts)?
+        .with_limits($limits)
         .compute()?
     }};
 }
@@ -134,28 +137,28 @@ macro_rules! run_compose {
 macro_rules! compose_generate_matchers {
     (
         $fst1: expr, $fst2: expr, $f1: ty, $f2: ty,
-        $builder: tt, $matcher1_enum: expr, $matcher2_enum: expr
+        $builder: tt, $matcher1_enum: expr, $matcher2_enum: expr, $limits: expr
     ) => {
         {
             match ($matcher1_enum, $matcher2_enum) {
                 (MatcherEnum::SortedMatcher(m1), MatcherEnum::SortedMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SortedMatcher<_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SortedMatcher<_,_,_>, $limits
                     )
                 },
                 (MatcherEnum::SigmaMatcher(m1), MatcherEnum::SortedMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SortedMatcher<_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SortedMatcher<_,_,_>, $limits
                     )
                 },
                 (MatcherEnum::SortedMatcher(m1), MatcherEnum::SigmaMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SigmaMatcher<_,_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SortedMatcher<_, _, _>, Some(m2), SigmaMatcher<_,_,_,_>, $limits
                     )
                 },
                 (MatcherEnum::SigmaMatcher(m1), MatcherEnum::SigmaMatcher(m2)) => {
                     run_compose!(
-                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SigmaMatcher<_,_,_,_>
+                        $fst1.borrow(), $fst2.borrow(), $f1, $f2, $builder, Some(m1), SigmaMatcher<_, _, _, _>, Some(m2), SigmaMatcher<_,_,_,_>, $limits
                     )
                 }
             }
@@ -174,6 +177,24 @@ pub fn compose_with_config<
     fst1: B1,
     fst2: B2,
     config: ComposeConfig,
+) -> Result<F3> {
+    compose_with_limits(fst1, fst2, config, AlgorithmLimits::default())
+}
+
+/// Same as `compose_with_config` but stops with a `LimitExceeded` error as soon as
+/// one of the `limits` is hit while composing.
+pub fn compose_with_limits<
+    W: Semiring,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+    B1: Borrow<F1> + Debug + Clone,
+    B2: Borrow<F2> + Debug + Clone,
+    F3: MutableFst<W> + AllocableFst<W>,
+>(
+    fst1: B1,
+    fst2: B2,
+    config: ComposeConfig,
+    limits: AlgorithmLimits,
 ) -> Result<F3> {
     let matcher1 = config
         .matcher1_config
@@ -185,7 +206,9 @@ pub fn compose_with_config<
     let mut ofst: F3 = match config.compose_filter {
         ComposeFilterEnum::AutoFilter => {
             if config.matcher1_config.empty() && config.matcher2_config.empty() {
-                ComposeFst::new_auto(fst1, fst2)?.compute()?
+                ComposeFst::new_auto(fst1, fst2)?
+                    .with_limits(limits)
+                    .compute()?
             } else {
                 bail!("Custom MatcherConfig not supported with AutoFilter")
             }
@@ -198,7 +221,8 @@ pub fn compose_with_config<
                 F2,
                 NullComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                limits
             )
         }
         ComposeFilterEnum::SequenceFilter => {
@@ -209,7 +233,8 @@ pub fn compose_with_config<
                 F2,
                 SequenceComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                limits
             )
         }
         ComposeFilterEnum::AltSequenceFilter => {
@@ -220,7 +245,8 @@ pub fn compose_with_config<
                 F2,
                 AltSequenceComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                limits
             )
         }
         ComposeFilterEnum::MatchFilter => {
@@ -231,7 +257,8 @@ pub fn compose_with_config<
                 F2,
                 MatchComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                limits
             )
         }
         ComposeFilterEnum::NoMatchFilter => {
@@ -242,7 +269,8 @@ pub fn compose_with_config<
                 F2,
                 NoMatchComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                limits
             )
         }
         ComposeFilterEnum::TrivialFilter => {
@@ -253,7 +281,8 @@ pub fn compose_with_config<
                 F2,
                 TrivialComposeFilterBuilder,
                 matcher1,
-                matcher2
+                matcher2,
+                limits
             )
         }
     };
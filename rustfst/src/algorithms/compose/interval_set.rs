@@ -1,10 +1,19 @@
-use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashSet;
+use std::io::Write;
 use std::slice::Iter as IterSlice;
 use std::vec::IntoIter as IntoIterVec;
+
+use anyhow::Result;
+use nom::combinator::verify;
+use nom::multi::count;
+use nom::IResult;
+use serde::{Deserialize, Serialize};
 use superslice::Ext;
 
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::{parse_bin_i32, parse_bin_i64, write_bin_i32, write_bin_i64};
+
 /// Half-open integral interval [a, b) of signed integers of type T.
 #[derive(PartialEq, Clone, Eq, Debug, Serialize, Deserialize)]
 pub struct IntInterval {
@@ -16,6 +25,17 @@ impl IntInterval {
     pub fn new(begin: usize, end: usize) -> Self {
         Self { begin, end }
     }
+
+    pub(crate) fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, begin) = verify(parse_bin_i32, |v: &i32| *v >= 0)(i)?;
+        let (i, end) = verify(parse_bin_i32, |v: &i32| *v >= begin)(i)?;
+        Ok((i, Self::new(begin as usize, end as usize)))
+    }
+
+    pub(crate) fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_i32(file, self.begin as i32)?;
+        write_bin_i32(file, self.end as i32)
+    }
 }
 
 // Not using default implementation to make sure that begin is compared first
@@ -101,6 +121,30 @@ impl IntoIterator for IntervalSet {
 }
 
 impl IntervalSet {
+    // Same layout as the OpenFST IntervalSet : the intervals followed by the count, -1 if unknown.
+    pub(crate) fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, n) = verify(parse_bin_i64, |v: &i64| *v >= 0)(i)?;
+        let (i, intervals) = count(IntInterval::parse_binary, n as usize)(i)?;
+        let (i, c) = parse_bin_i32(i)?;
+        Ok((
+            i,
+            Self {
+                intervals: VectorIntervalStore {
+                    intervals,
+                    count: if c >= 0 { Some(c as usize) } else { None },
+                },
+            },
+        ))
+    }
+
+    pub(crate) fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_i64(file, self.len() as i64)?;
+        for interval in self.iter() {
+            interval.write_binary(file)?;
+        }
+        write_bin_i32(file, self.count().map(|c| c as i32).unwrap_or(-1))
+    }
+
     pub fn len(&self) -> usize {
         self.intervals.len()
     }
@@ -1,8 +1,12 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::io::Write;
 use std::sync::Arc;
 
 use anyhow::Result;
+use nom::combinator::verify;
+use nom::multi::count;
+use nom::IResult;
 
 use crate::algorithms::compose::{IntervalSet, StateReachable};
 use crate::algorithms::tr_compares::{ILabelCompare, OLabelCompare};
@@ -10,9 +14,19 @@ use crate::algorithms::{fst_convert_from_ref, tr_sort};
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst};
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::{
+    parse_bin_i32, parse_bin_i64, parse_bin_u8, write_bin_i32, write_bin_i64, write_bin_u8,
+};
 use crate::semirings::Semiring;
-use crate::{Label, StateId, Tr, Trs, EPS_LABEL, NO_LABEL, UNASSIGNED};
-
+use crate::{Error, Label, StateId, Tr, Trs, EPS_LABEL, NO_LABEL, UNASSIGNED};
+
+/// Reachability data computed by [`LabelReachable`] : for each state, the set of labels that
+/// can be read first on a path leaving it.
+///
+/// Labels are identified by an index assigned during the computation, so labels must be
+/// mapped with [`label2index`](Self::label2index) or [`relabel`](Self::relabel) before
+/// being queried.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LabelReachableData {
     reach_input: bool,
@@ -22,6 +36,8 @@ pub struct LabelReachableData {
 }
 
 impl LabelReachableData {
+    /// Creates empty data, for input labels if `reach_input` is set and output labels
+    /// otherwise.
     pub fn new(reach_input: bool) -> Self {
         Self {
             reach_input,
@@ -31,24 +47,30 @@ impl LabelReachableData {
         }
     }
 
+    /// Indices of the labels reachable from state `s`.
     pub fn interval_set(&self, s: StateId) -> Result<&IntervalSet> {
         self.interval_sets
             .get(s as usize)
             .ok_or_else(|| format_err!("Missing state {}", s))
     }
 
+    /// Index standing for the final states, see [`LabelReachable::reach_final`].
     pub fn final_label(&self) -> Label {
         self.final_label
     }
 
+    /// Index of each label found in the FST.
     pub fn label2index(&self) -> &HashMap<Label, Label> {
         &self.label2index
     }
 
+    /// Whether the data is about input labels rather than output labels.
     pub fn reach_input(&self) -> bool {
         self.reach_input
     }
 
+    /// Returns the index of `label`, assigning a new one if it hasn't been seen yet. Epsilon is
+    /// left unchanged.
     pub fn relabel(&mut self, label: Label) -> Label {
         if label == EPS_LABEL {
             return EPS_LABEL;
@@ -60,6 +82,8 @@ impl LabelReachableData {
             .or_insert_with(|| n as Label + 1)
     }
 
+    /// Replaces the input labels of `fst` by their index if `relabel_input` is set, its
+    /// output labels otherwise, then sorts its transitions on these labels.
     pub fn relabel_fst<W: Semiring, F: MutableFst<W>>(
         &mut self,
         fst: &mut F,
@@ -114,8 +138,92 @@ impl LabelReachableData {
 
         pairs
     }
+
+    // Same layout as the OpenFST LabelReachableData. The relabeling data is always written.
+    pub(crate) fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, reach_input) = parse_bin_u8(i)?;
+        let (i, keep_relabel_data) = parse_bin_u8(i)?;
+        let (i, label2index) = if keep_relabel_data != 0 {
+            let (i, n) = verify(parse_bin_i64, |v: &i64| *v >= 0)(i)?;
+            let (i, pairs) = count(
+                |i| {
+                    let (i, label) = parse_bin_i32(i)?;
+                    let (i, index) = parse_bin_i32(i)?;
+                    Ok((i, (label as Label, index as Label)))
+                },
+                n as usize,
+            )(i)?;
+            (i, pairs.into_iter().collect())
+        } else {
+            (i, HashMap::new())
+        };
+        let (i, final_label) = parse_bin_i32(i)?;
+        let (i, n) = verify(parse_bin_i64, |v: &i64| *v >= 0)(i)?;
+        let (i, interval_sets) = count(IntervalSet::parse_binary, n as usize)(i)?;
+        Ok((
+            i,
+            Self {
+                reach_input: reach_input != 0,
+                final_label: final_label as Label,
+                label2index,
+                interval_sets,
+            },
+        ))
+    }
+
+    pub(crate) fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_u8(file, self.reach_input as u8)?;
+        write_bin_u8(file, 1)?;
+        write_bin_i64(file, self.label2index.len() as i64)?;
+        let mut pairs: Vec<_> = self.label2index.iter().collect();
+        pairs.sort();
+        for (label, index) in pairs {
+            write_bin_i32(file, *label as i32)?;
+            write_bin_i32(file, *index as i32)?;
+        }
+        write_bin_i32(file, self.final_label as i32)?;
+        write_bin_i64(file, self.interval_sets.len() as i64)?;
+        for interval_set in self.interval_sets.iter() {
+            interval_set.write_binary(file)?;
+        }
+        Ok(())
+    }
 }
 
+/// Tests which labels can be read first from a given state : a label can be reached from a
+/// state if it is on a transition at the end of a path of epsilon transitions leaving it.
+/// Only input labels are considered if `reach_input` is set, output labels otherwise.
+///
+/// Labels are queried through their index in [`LabelReachableData::label2index`]. This is what
+/// the lookahead matchers use to discard transitions that can't lead to a successful match.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::visitors::LabelReachable;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(4);
+/// fst.set_start(0)?;
+/// fst.emplace_tr(0, 0, 0, 1.0, 1)?;
+/// fst.emplace_tr(1, 1, 1, 1.0, 2)?;
+/// fst.emplace_tr(2, 2, 2, 1.0, 3)?;
+/// fst.set_final(3, 1.0)?;
+///
+/// let reachable = LabelReachable::new(&fst, true)?;
+/// let index = |label| reachable.data().label2index()[&label];
+/// // Label 1 can be read from state 0 after an epsilon transition, but not label 2.
+/// assert!(reachable.reach_label(0, index(1))?);
+/// assert!(!reachable.reach_label(0, index(2))?);
+/// assert!(reachable.reach_label(2, index(2))?);
+/// // Only state 3 reaches a final state without reading any label.
+/// assert!(!reachable.reach_final(2)?);
+/// assert!(reachable.reach_final(3)?);
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct LabelReachable {
     data: Arc<LabelReachableData>,
@@ -123,6 +231,8 @@ pub struct LabelReachable {
 }
 
 impl LabelReachable {
+    /// Computes the labels reachable from each state of `fst`, see
+    /// [`compute_data`](Self::compute_data).
     pub fn new<W: Semiring, F: Fst<W>>(fst: &F, reach_input: bool) -> Result<Self> {
         let data = Self::compute_data(fst, reach_input)?;
 
@@ -132,6 +242,9 @@ impl LabelReachable {
         })
     }
 
+    /// Computes the input labels reachable from each state of `fst` if `reach_input` is set,
+    /// the output labels otherwise. Final states are reachable through a dedicated label, see
+    /// [`LabelReachableData::final_label`].
     pub fn compute_data<W: Semiring, F: Fst<W>>(
         fst: &F,
         reach_input: bool,
@@ -149,6 +262,7 @@ impl LabelReachable {
         Ok(data)
     }
 
+    /// Creates a `LabelReachable` from previously computed data.
     pub fn new_from_data(data: Arc<LabelReachableData>) -> Self {
         Self {
             data,
@@ -156,10 +270,12 @@ impl LabelReachable {
         }
     }
 
+    /// Reachability data of the FST.
     pub fn data(&self) -> &Arc<LabelReachableData> {
         &self.data
     }
 
+    /// Whether input labels are considered rather than output labels.
     pub fn reach_input(&self) -> bool {
         self.data.reach_input
     }
@@ -272,6 +388,9 @@ impl LabelReachable {
         Ok(())
     }
 
+    /// Sets the FST whose transitions are passed to [`reach`](Self::reach) : its input labels
+    /// are used if `reach_input` is set, its output labels otherwise. It must be sorted on
+    /// these labels.
     pub fn reach_init<W: Semiring, F: Fst<W>>(&mut self, fst: &F, reach_input: bool) -> Result<()> {
         self.reach_fst_input = reach_input;
 
@@ -284,13 +403,16 @@ impl LabelReachable {
         let props = fst.properties_check(true_prop)?;
 
         if !props.contains(true_prop) {
-            bail!("LabelReachable::ReachInit: Fst is not sorted")
+            return Err(Error::NotSorted(
+                "LabelReachable::ReachInit: Fst is not sorted".to_string(),
+            )
+            .into());
         }
         Ok(())
     }
 
-    // Can reach this label from current state?
-    // Original labels must be transformed by the Relabel methods above.
+    /// Whether the label with index `label` can be reached from `current_state`. Epsilon is
+    /// never reachable.
     pub fn reach_label(&self, current_state: StateId, label: Label) -> Result<bool> {
         if label == EPS_LABEL {
             return Ok(false);
@@ -301,7 +423,7 @@ impl LabelReachable {
             .member(label as usize))
     }
 
-    // Can reach final state (via epsilon transitions) from this state?
+    /// Whether a final state can be reached from `current_state` through epsilon transitions.
     pub fn reach_final(&self, current_state: StateId) -> Result<bool> {
         Ok(self
             .data
@@ -309,6 +431,13 @@ impl LabelReachable {
             .member(self.data.final_label() as usize))
     }
 
+    /// Finds the transitions among `trs[aiter_begin..aiter_end]` whose label can be reached
+    /// from `current_state`. Their labels must be indices and they must be sorted as required
+    /// by [`reach_init`](Self::reach_init).
+    ///
+    /// Returns the positions of the first reachable transition and past the last one, along
+    /// with the sum of the weights of the reachable transitions if `compute_weight` is set, or
+    /// `None` if no transition is reachable.
     pub fn reach<'a, W: Semiring + 'a, T: Trs<W>>(
         &self,
         current_state: StateId,
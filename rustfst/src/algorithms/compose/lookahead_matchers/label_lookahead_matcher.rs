@@ -1,16 +1,20 @@
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::io::Write;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 use anyhow::Result;
+use nom::IResult;
 
 use crate::algorithms::compose::lookahead_matchers::{
-    LookAheadMatcherData, LookaheadMatcher, MatcherFlagsTrait,
+    LabelLookAheadRelabeler, LookAheadMatcherData, LookaheadMatcher, MatcherFlagsTrait,
+    SerializableLookaheadMatcher,
 };
 use crate::algorithms::compose::matchers::{MatchType, Matcher, MatcherFlags};
 use crate::algorithms::compose::{LabelReachable, LabelReachableData};
-use crate::fst_traits::Fst;
+use crate::fst_traits::{Fst, MutableFst};
+use crate::parsers::nom_utils::NomCustomError;
 use crate::semirings::Semiring;
 use crate::{Label, StateId, Tr, Trs, EPS_LABEL};
 
@@ -227,3 +231,39 @@ where
         la_matcher_data.default_lookahead_prefix(tr)
     }
 }
+
+impl<W, F, B, M, MFT> SerializableLookaheadMatcher<W, F, B>
+    for LabelLookAheadMatcher<W, F, B, M, MFT>
+where
+    W: Semiring + 'static,
+    F: Fst<W>,
+    B: Borrow<F> + Debug + Clone,
+    M: Matcher<W, F, B>,
+    MFT: MatcherFlagsTrait,
+{
+    fn fst_type() -> String {
+        if MFT::flags().contains(MatcherFlags::INPUT_LOOKAHEAD_MATCHER) {
+            "ilabel_lookahead".to_string()
+        } else {
+            "olabel_lookahead".to_string()
+        }
+    }
+
+    fn init_data<F2: MutableFst<W>>(
+        fst: &mut F2,
+    ) -> Result<(Option<Self::MatcherData>, Option<Self::MatcherData>)> {
+        let imatcher_data = Self::create_data::<F2, _>(&*fst, MatchType::MatchInput)?;
+        let omatcher_data = Self::create_data::<F2, _>(&*fst, MatchType::MatchOutput)?;
+        let mut data = (imatcher_data, omatcher_data);
+        LabelLookAheadRelabeler::init(fst, &mut data)?;
+        Ok(data)
+    }
+
+    fn parse_data(i: &[u8]) -> IResult<&[u8], Self::MatcherData, NomCustomError<&[u8]>> {
+        LabelReachableData::parse_binary(i)
+    }
+
+    fn write_data<O: Write>(data: &Self::MatcherData, output: &mut O) -> Result<()> {
+        data.write_binary(output)
+    }
+}
@@ -1,8 +1,10 @@
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::io::Write;
 use std::sync::Arc;
 
 use anyhow::Result;
+use nom::IResult;
 
 pub use label_lookahead_matcher::LabelLookAheadMatcher;
 pub(super) use label_lookahead_relabeler::LabelLookAheadRelabeler;
@@ -11,7 +13,8 @@ pub use trivial_lookahead_matcher::TrivialLookAheadMatcher;
 
 use crate::algorithms::compose::matchers::MatcherFlags;
 use crate::algorithms::compose::matchers::{MatchType, Matcher};
-use crate::fst_traits::Fst;
+use crate::fst_traits::{Fst, MutableFst};
+use crate::parsers::nom_utils::NomCustomError;
 use crate::semirings::Semiring;
 use crate::{Label, StateId, Tr, NO_STATE_ID};
 
@@ -24,6 +27,26 @@ pub trait MatcherFlagsTrait: Debug + Clone {
     fn flags() -> MatcherFlags;
 }
 
+/// Flags of the label lookahead matchers used by the OpenFST `ilabel_lookahead` FST type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ILabelLookAheadFlags {}
+
+impl MatcherFlagsTrait for ILabelLookAheadFlags {
+    fn flags() -> MatcherFlags {
+        MatcherFlags::ILABEL_LOOKAHEAD_FLAGS
+    }
+}
+
+/// Flags of the label lookahead matchers used by the OpenFST `olabel_lookahead` FST type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OLabelLookAheadFlags {}
+
+impl MatcherFlagsTrait for OLabelLookAheadFlags {
+    fn flags() -> MatcherFlags {
+        MatcherFlags::OLABEL_LOOKAHEAD_FLAGS
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LookAheadMatcherData<W: Semiring> {
     pub lookahead_weight: W,
@@ -100,3 +123,24 @@ pub trait LookaheadMatcher<W: Semiring, F: Fst<W>, B: Borrow<F>>: Matcher<W, F,
     fn lookahead_label(&self, state: StateId, label: Label) -> Result<bool>;
     fn lookahead_prefix(&self, tr: &mut Tr<W>, la_matcher_data: &LookAheadMatcherData<W>) -> bool;
 }
+
+/// Lookahead matchers whose data can be stored along with the FST in a `MatcherFst` and
+/// serialized in the corresponding OpenFST binary format.
+pub trait SerializableLookaheadMatcher<W: Semiring, F: Fst<W>, B: Borrow<F>>:
+    LookaheadMatcher<W, F, B>
+{
+    /// Type of the `MatcherFst` in the OpenFST binary format, e.g `olabel_lookahead`.
+    fn fst_type() -> String;
+
+    /// Computes the data of the input and output matchers of `fst`, relabeling it if needed.
+    #[allow(clippy::type_complexity)]
+    fn init_data<F2: MutableFst<W>>(
+        fst: &mut F2,
+    ) -> Result<(Option<Self::MatcherData>, Option<Self::MatcherData>)>;
+
+    /// Parses the data of one matcher in binary format.
+    fn parse_data(i: &[u8]) -> IResult<&[u8], Self::MatcherData, NomCustomError<&[u8]>>;
+
+    /// Writes the data of one matcher in binary format.
+    fn write_data<O: Write>(data: &Self::MatcherData, output: &mut O) -> Result<()>;
+}
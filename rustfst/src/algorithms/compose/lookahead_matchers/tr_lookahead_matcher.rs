@@ -1,19 +1,22 @@
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::io::Write;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 use anyhow::Result;
+use nom::IResult;
 
 use crate::algorithms::compose::lookahead_matchers::{
-    LookAheadMatcherData, LookaheadMatcher, MatcherFlagsTrait,
+    LookAheadMatcherData, LookaheadMatcher, MatcherFlagsTrait, SerializableLookaheadMatcher,
 };
 use crate::algorithms::compose::matchers::{IterItemMatcher, MatchType, Matcher, MatcherFlags};
-use crate::fst_traits::Fst;
+use crate::fst_traits::{Fst, MutableFst};
+use crate::parsers::nom_utils::NomCustomError;
 use crate::semirings::Semiring;
 use crate::{Label, StateId, Tr, Trs, EPS_LABEL, NO_LABEL};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TrLookAheadMatcher<W, F, B, M, MFT>
 where
     W: Semiring,
@@ -248,3 +251,30 @@ where
         la_matcher_data.default_lookahead_prefix(tr)
     }
 }
+
+impl<W, F, B, M, MFT> SerializableLookaheadMatcher<W, F, B> for TrLookAheadMatcher<W, F, B, M, MFT>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug + Clone,
+    M: Matcher<W, F, B>,
+    MFT: MatcherFlagsTrait,
+{
+    fn fst_type() -> String {
+        "arc_lookahead".to_string()
+    }
+
+    fn init_data<F2: MutableFst<W>>(
+        _fst: &mut F2,
+    ) -> Result<(Option<Self::MatcherData>, Option<Self::MatcherData>)> {
+        Ok((None, None))
+    }
+
+    fn parse_data(i: &[u8]) -> IResult<&[u8], Self::MatcherData, NomCustomError<&[u8]>> {
+        Ok((i, ()))
+    }
+
+    fn write_data<O: Write>(_data: &Self::MatcherData, _output: &mut O) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,20 +1,36 @@
 use std::borrow::Borrow;
 use std::fmt::Debug;
+use std::io::Write;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 use anyhow::Result;
+use nom::combinator::verify;
+use nom::IResult;
 
-use crate::algorithms::compose::lookahead_matchers::{LabelLookAheadRelabeler, LookaheadMatcher};
+use crate::algorithms::compose::lookahead_matchers::{
+    LabelLookAheadRelabeler, LookaheadMatcher, SerializableLookaheadMatcher,
+};
 use crate::algorithms::compose::matchers::MatchType;
 use crate::algorithms::compose::FstAddOn;
 use crate::algorithms::compose::LabelReachableData;
+use crate::fst_impls::VectorFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{
-    CoreFst, ExpandedFst, Fst, FstIntoIterator, FstIterator, MutableFst, StateIterator,
+    CoreFst, ExpandedFst, Fst, FstIntoIterator, FstIterator, MutableFst, SerializableFst,
+    StateIterator,
 };
-use crate::semirings::Semiring;
-use crate::{StateId, SymbolTable};
+use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::nom_utils::{binary_parse_error, NomCustomError};
+use crate::parsers::text_fst::ParsedTextFst;
+use crate::parsers::{parse_bin_i32, parse_bin_u8, write_bin_i32, write_bin_u8};
+use crate::semirings::{Semiring, SerializableSemiring};
+use crate::{StateId, SymbolTable, Tr};
+
+// Identifies stream data as an add-on FST.
+static ADD_ON_MAGIC_NUMBER: i32 = 446_681_434;
+static ADD_ON_FILE_VERSION: i32 = 1;
+static ADD_ON_MIN_FILE_VERSION: i32 = 1;
 
 type InnerFstAddOn<F, T> = FstAddOn<F, (Option<Arc<T>>, Option<Arc<T>>)>;
 
@@ -93,6 +109,30 @@ where
     }
 }
 
+impl<W, F, B, M> MatcherFst<W, F, B, M, M::MatcherData>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: SerializableLookaheadMatcher<W, F, B>,
+{
+    /// Builds a `MatcherFst` from a mutable FST, relabeling it if needed, then converts it
+    /// to the underlying FST type. Useful to build a `MatcherFst` over a `ConstFst` which is
+    /// the layout expected by OpenFST for the lookahead FST types.
+    pub fn from_fst<F2: MutableFst<W>>(mut fst: F2) -> Result<Self>
+    where
+        F: From<F2>,
+    {
+        let add_on = M::init_data(&mut fst)?;
+        let add_on = (add_on.0.map(Arc::new), add_on.1.map(Arc::new));
+        Ok(Self {
+            fst_add_on: FstAddOn::new(fst.into(), add_on),
+            matcher: PhantomData,
+            w: PhantomData,
+        })
+    }
+}
+
 impl<W: Semiring, F: CoreFst<W>, B: Borrow<F>, M, T> CoreFst<W> for MatcherFst<W, F, B, M, T> {
     type TRS = <FstAddOn<F, T> as CoreFst<W>>::TRS;
 
@@ -220,3 +260,204 @@ where
         self.fst_add_on.fst_into_iter()
     }
 }
+
+impl<W, F, B, M> SerializableFst<W> for MatcherFst<W, F, B, M, M::MatcherData>
+where
+    W: SerializableSemiring,
+    F: SerializableFst<W> + From<VectorFst<W>>,
+    B: Borrow<F> + Debug + PartialEq + Clone,
+    M: SerializableLookaheadMatcher<W, F, B> + Debug + Clone + PartialEq,
+    M::MatcherData: Debug + PartialEq,
+{
+    fn fst_type() -> String {
+        M::fst_type()
+    }
+
+    fn load(data: &[u8]) -> Result<Self> {
+        let (_, parsed_fst) = Self::parse_binary(data)
+            .map_err(|e| binary_parse_error(e, &format!("{} MatcherFst", Self::fst_type())))?;
+        Ok(parsed_fst)
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, _hdr) = FstHeader::parse(
+            i,
+            ADD_ON_MIN_FILE_VERSION,
+            Self::fst_type(),
+            Tr::<W>::tr_type(),
+        )?;
+        let (i, _) = verify(parse_bin_i32, |v: &i32| *v == ADD_ON_MAGIC_NUMBER)(i)?;
+        let (i, fst) = F::parse_binary(i)?;
+        let (i, have_add_on) = parse_bin_u8(i)?;
+        let (i, add_on) = if have_add_on != 0 {
+            let (i, imatcher_data) = parse_matcher_data::<W, F, B, M>(i)?;
+            let (i, omatcher_data) = parse_matcher_data::<W, F, B, M>(i)?;
+            (i, (imatcher_data, omatcher_data))
+        } else {
+            (i, (None, None))
+        };
+        Ok((
+            i,
+            Self {
+                fst_add_on: FstAddOn::new(fst, add_on),
+                matcher: PhantomData,
+                w: PhantomData,
+            },
+        ))
+    }
+
+    fn store<O: Write>(&self, mut output: O) -> Result<()> {
+        // As in OpenFST, the symbol tables are only stored in the header of the underlying FST.
+        let hdr = FstHeader {
+            magic_number: FST_MAGIC_NUMBER,
+            fst_type: OpenFstString::new(Self::fst_type()),
+            tr_type: OpenFstString::new(Tr::<W>::tr_type()),
+            version: ADD_ON_FILE_VERSION,
+            flags: FstFlags::empty(),
+            properties: self.properties().bits(),
+            start: -1,
+            num_states: 0,
+            num_trs: 0,
+            isymt: None,
+            osymt: None,
+        };
+        hdr.write(&mut output)?;
+        write_bin_i32(&mut output, ADD_ON_MAGIC_NUMBER)?;
+        self.fst().store(&mut output)?;
+
+        write_bin_u8(&mut output, 1)?;
+        for data in [&self.addon().0, &self.addon().1] {
+            if let Some(data) = data {
+                write_bin_u8(&mut output, 1)?;
+                M::write_data(data, &mut output)?;
+            } else {
+                write_bin_u8(&mut output, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn from_parsed_fst_text(parsed_fst_text: ParsedTextFst<W>) -> Result<Self> {
+        Self::from_fst(VectorFst::from_parsed_fst_text(parsed_fst_text)?)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_matcher_data<W, F, B, M>(
+    i: &[u8],
+) -> IResult<&[u8], Option<Arc<M::MatcherData>>, NomCustomError<&[u8]>>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: SerializableLookaheadMatcher<W, F, B>,
+{
+    let (i, have_data) = parse_bin_u8(i)?;
+    if have_data != 0 {
+        let (i, data) = M::parse_data(i)?;
+        Ok((i, Some(Arc::new(data))))
+    } else {
+        Ok((i, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::compose::lookahead_matchers::{
+        LabelLookAheadMatcher, OLabelLookAheadFlags, TrLookAheadMatcher,
+    };
+    use crate::algorithms::compose::matchers::SortedMatcher;
+    use crate::fst_impls::ConstFst;
+    use crate::semirings::TropicalWeight;
+
+    type OLabelLookAheadFst<F> = MatcherFst<
+        TropicalWeight,
+        F,
+        Arc<F>,
+        LabelLookAheadMatcher<
+            TropicalWeight,
+            F,
+            Arc<F>,
+            SortedMatcher<TropicalWeight, F, Arc<F>>,
+            OLabelLookAheadFlags,
+        >,
+        LabelReachableData,
+    >;
+
+    type ArcLookAheadFst<F> = MatcherFst<
+        TropicalWeight,
+        F,
+        Arc<F>,
+        TrLookAheadMatcher<
+            TropicalWeight,
+            F,
+            Arc<F>,
+            SortedMatcher<TropicalWeight, F, Arc<F>>,
+            OLabelLookAheadFlags,
+        >,
+        (),
+    >;
+
+    fn transducer() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 5, 0.5, 1))?;
+        fst.add_tr(0, Tr::new(2, 6, 1.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 0, 0.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 7, 0.0, 3))?;
+        fst.set_final(3, 0.0)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_olabel_lookahead_fst_bin_round_trip() -> Result<()> {
+        let fst: OLabelLookAheadFst<ConstFst<_>> = MatcherFst::from_fst(transducer()?)?;
+        assert!(fst.data(MatchType::MatchInput).is_none());
+        assert!(fst.data(MatchType::MatchOutput).is_some());
+
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        let loaded = OLabelLookAheadFst::<ConstFst<_>>::load(&data)?;
+        assert_eq!(loaded, fst);
+
+        // Same for a VectorFst based MatcherFst.
+        let fst = OLabelLookAheadFst::<VectorFst<_>>::new(transducer()?)?;
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        let loaded = OLabelLookAheadFst::<VectorFst<_>>::load(&data)?;
+        assert_eq!(loaded, fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_olabel_lookahead_fst_header() -> Result<()> {
+        let fst: OLabelLookAheadFst<ConstFst<_>> = MatcherFst::from_fst(transducer()?)?;
+        let mut data = vec![];
+        fst.store(&mut data)?;
+
+        let (i, hdr) = FstHeader::parse(&data, 1, "olabel_lookahead", "standard").unwrap();
+        assert_eq!(hdr.version, ADD_ON_FILE_VERSION);
+        let (i, magic_number) = parse_bin_i32(i).unwrap();
+        assert_eq!(magic_number, ADD_ON_MAGIC_NUMBER);
+        let (_, inner) = ConstFst::<TropicalWeight>::parse_binary(i).unwrap();
+        assert_eq!(&inner, fst.fst());
+
+        // Can't be loaded as another lookahead type.
+        assert!(ArcLookAheadFst::<ConstFst<_>>::load(&data).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arc_lookahead_fst_bin_round_trip() -> Result<()> {
+        let fst: ArcLookAheadFst<ConstFst<_>> = MatcherFst::from_fst(transducer()?)?;
+        assert_eq!(fst.addon(), &(None, None));
+
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        let loaded = ArcLookAheadFst::<ConstFst<_>>::load(&data)?;
+        assert_eq!(loaded, fst);
+        Ok(())
+    }
+}
@@ -4,8 +4,8 @@ pub use self::compose_fst_op::{ComposeFstOp, ComposeFstOpState};
 pub use self::compose_fst_op_options::ComposeFstOpOptions;
 pub use self::compose_state_tuple::ComposeStateTuple;
 pub use self::compose_static::{
-    compose, compose_with_config, ComposeConfig, ComposeFilterEnum, MatcherConfig,
-    SigmaMatcherConfig,
+    compose, compose_with_config, compose_with_limits, ComposeConfig, ComposeFilterEnum,
+    MatcherConfig, SigmaMatcherConfig,
 };
 pub use self::interval_reach_visitor::IntervalReachVisitor;
 pub use self::interval_set::{IntInterval, IntervalSet};
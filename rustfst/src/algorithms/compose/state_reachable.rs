@@ -5,7 +5,7 @@ use crate::algorithms::dfs_visit::dfs_visit;
 use crate::algorithms::tr_filters::AnyTrFilter;
 use crate::fst_impls::VectorFst;
 use crate::fst_traits::{CoreFst, ExpandedFst};
-use crate::StateId;
+use crate::{Error, StateId};
 
 use crate::fst_properties::FstProperties;
 use crate::semirings::Semiring;
@@ -13,16 +13,41 @@ use anyhow::Result;
 
 static UNASSIGNED: usize = usize::MAX;
 
-// Tests reachability of final states from a given state. To test for
-// reachability from a state s, first do SetState(s). Then a final state f can
-// be reached from state s of FST iff Reach(f) is true. The input can be cyclic,
-// but no cycle may contain a final state.
+/// Tests reachability of final states from a given state : a final state `f` can be reached
+/// from a state `s` iff `reach(s, f)` is true. The input can be cyclic, but no cycle may
+/// contain a final state.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::visitors::StateReachable;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(4);
+/// fst.set_start(0)?;
+/// fst.emplace_tr(0, 1, 1, 1.0, 1)?;
+/// fst.emplace_tr(0, 2, 2, 1.0, 2)?;
+/// fst.emplace_tr(2, 3, 3, 1.0, 3)?;
+/// fst.set_final(1, 1.0)?;
+/// fst.set_final(3, 1.0)?;
+///
+/// let reachable = StateReachable::new(&fst)?;
+/// assert!(reachable.reach(0, 1)?);
+/// assert!(reachable.reach(0, 3)?);
+/// assert!(!reachable.reach(2, 1)?);
+/// assert!(reachable.reach(2, 3)?);
+/// # Ok(())
+/// # }
+/// ```
 pub struct StateReachable {
     pub(crate) isets: Vec<IntervalSet>,
     pub(crate) state2index: Vec<usize>,
 }
 
 impl StateReachable {
+    /// Computes the reachability of the final states of `fst`.
     pub fn new<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> Result<Self> {
         let props = fst.properties_check(FstProperties::ACYCLIC)?;
         let acyclic = props.contains(FstProperties::ACYCLIC);
@@ -33,8 +58,9 @@ impl StateReachable {
         }
     }
 
+    /// Computes the reachability of the final states of a cyclic FST by condensing its SCCs.
     pub fn new_cyclic<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> Result<Self> {
-        let (scc, cfst): (_, VectorFst<_>) = condense(fst).unwrap();
+        let (scc, cfst): (_, VectorFst<_>) = condense(fst)?;
         let reachable = StateReachable::new_acyclic(&cfst);
         let mut nscc = vec![];
 
@@ -66,6 +92,7 @@ impl StateReachable {
         Ok(Self { isets, state2index })
     }
 
+    /// Computes the reachability of the final states of an acyclic FST.
     pub fn new_acyclic<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> Self {
         let mut reach_visitor = IntervalReachVisitor::new(fst);
         dfs_visit(fst, &mut reach_visitor, &AnyTrFilter {}, false);
@@ -75,11 +102,14 @@ impl StateReachable {
         }
     }
 
-    #[allow(unused)]
-    // Can reach this final state from current state?
+    /// Returns whether the final state `s` can be reached from `current_state`.
     pub fn reach(&self, current_state: StateId, s: StateId) -> Result<bool> {
         if let Some(i) = self.state2index.get(s as usize) {
-            Ok(self.isets[current_state as usize].member(*i))
+            let iset = self
+                .isets
+                .get(current_state as usize)
+                .ok_or(Error::InvalidStateId(current_state))?;
+            Ok(iset.member(*i))
         } else {
             bail!("StateReachable: State non-final {}", s)
         }
@@ -0,0 +1,314 @@
+use anyhow::Result;
+
+use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::posteriors;
+use crate::algorithms::top_sort::TopOrderVisitor;
+use crate::algorithms::tr_filters::AnyTrFilter;
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::{Error, Label, StateId, Tr, Trs, EPS_LABEL};
+
+/// A competing hypothesis inside a slot of a `ConfusionNetwork`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlotEntry {
+    /// Word label. `EPS_LABEL` stands for the deletion hypothesis.
+    pub label: Label,
+    /// Posterior probability of the word in this slot.
+    pub posterior: f32,
+}
+
+/// Word confusion network (also known as sausage) built from a lattice.
+///
+/// A confusion network is a linear sequence of slots, each slot containing the set of competing
+/// words at that position along with their posterior probabilities. The posteriors of a slot sum
+/// to one, the missing mass being assigned to an epsilon entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfusionNetwork {
+    slots: Vec<Vec<SlotEntry>>,
+}
+
+impl ConfusionNetwork {
+    /// Slots of the confusion network. In each slot, entries are sorted by decreasing posterior.
+    pub fn slots(&self) -> &[Vec<SlotEntry>] {
+        &self.slots
+    }
+
+    /// Number of slots in the confusion network.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Whether the confusion network has no slot.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Minimum Bayes risk (consensus) hypothesis: the most likely entry of each slot with
+    /// epsilons removed. The posterior of each entry is its confidence score.
+    pub fn mbr_path(&self) -> Vec<SlotEntry> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.first())
+            .filter(|e| e.label != EPS_LABEL)
+            .cloned()
+            .collect()
+    }
+
+    /// Builds the linear acceptor representing the confusion network. Weights are the negated
+    /// natural logarithm of the posteriors.
+    pub fn to_fst<W: Semiring<Type = f32>>(&self) -> Result<VectorFst<W>> {
+        let mut fst = VectorFst::new();
+        let mut state = fst.add_state();
+        fst.set_start(state)?;
+        for slot in self.slots.iter() {
+            let nextstate = fst.add_state();
+            for entry in slot.iter() {
+                fst.add_tr(
+                    state,
+                    Tr::new(
+                        entry.label,
+                        entry.label,
+                        W::new(-entry.posterior.ln()),
+                        nextstate,
+                    ),
+                )?;
+            }
+            state = nextstate;
+        }
+        fst.set_final(state, W::one())?;
+        Ok(fst)
+    }
+}
+
+/// Converts an acyclic lattice into a word confusion network.
+///
+/// The lattice must be an acceptor whose weights are negated log probabilities
+/// (e.g `TropicalWeight` or `LogWeight`). Transition posteriors are computed by forward-backward
+/// and each non-epsilon transition is aligned to the slot given by the maximum number of words
+/// on a path from the initial state to its origin state. Transitions leaving the same state thus
+/// always compete in the same slot.
+///
+/// An error is returned if the lattice is cyclic, is not an acceptor or has no successful path.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::confusion_network::confusion_network;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut lattice = VectorFst::<TropicalWeight>::new();
+/// let s0 = lattice.add_state();
+/// let s1 = lattice.add_state();
+/// lattice.set_start(s0)?;
+/// lattice.add_tr(s0, Tr::new(1, 1, -(0.7f32.ln()), s1))?;
+/// lattice.add_tr(s0, Tr::new(2, 2, -(0.3f32.ln()), s1))?;
+/// lattice.set_final(s1, TropicalWeight::one())?;
+///
+/// let cn = confusion_network(&lattice)?;
+/// let best = cn.mbr_path();
+/// assert_eq!(best.len(), 1);
+/// assert_eq!(best[0].label, 1);
+/// assert!((best[0].posterior - 0.7).abs() < 1e-5);
+/// # Ok(())
+/// # }
+/// ```
+pub fn confusion_network<W, F>(lattice: &F) -> Result<ConfusionNetwork>
+where
+    W: Semiring<Type = f32>,
+    F: ExpandedFst<W>,
+{
+    let mut known = FstProperties::empty();
+    let props = compute_fst_properties(
+        lattice,
+        FstProperties::ACYCLIC
+            | FstProperties::CYCLIC
+            | FstProperties::ACCEPTOR
+            | FstProperties::NOT_ACCEPTOR,
+        &mut known,
+        true,
+    )?;
+    if !props.contains(FstProperties::ACYCLIC) {
+        return Err(Error::PropertyViolation(
+            "ConfusionNetwork: the lattice must be acyclic".to_string(),
+        )
+        .into());
+    }
+    if !props.contains(FstProperties::ACCEPTOR) {
+        return Err(Error::PropertyViolation(
+            "ConfusionNetwork: the lattice must be an acceptor".to_string(),
+        )
+        .into());
+    }
+
+    let posteriors = posteriors(lattice)?;
+
+    let mut visitor = TopOrderVisitor::new();
+    dfs_visit(lattice, &mut visitor, &AnyTrFilter {}, false);
+    let mut top_order = vec![0 as StateId; visitor.order.len()];
+    for (s, &pos) in visitor.order.iter().enumerate() {
+        top_order[pos as usize] = s as StateId;
+    }
+
+    // Maximum number of words on a path from the initial state to each state.
+    let mut depth = vec![0usize; lattice.num_states()];
+    for &s in top_order.iter() {
+        for tr in lattice.get_trs(s)?.trs() {
+            let d = depth[s as usize] + usize::from(tr.ilabel != EPS_LABEL);
+            if d > depth[tr.nextstate as usize] {
+                depth[tr.nextstate as usize] = d;
+            }
+        }
+    }
+
+    let mut slots: Vec<Vec<SlotEntry>> = vec![];
+    for &s in top_order.iter() {
+        let trs = lattice.get_trs(s)?;
+        for (idx, tr) in trs.trs().iter().enumerate() {
+            if tr.ilabel == EPS_LABEL {
+                continue;
+            }
+            let slot_idx = depth[s as usize];
+            if slots.len() <= slot_idx {
+                slots.resize(slot_idx + 1, vec![]);
+            }
+            let posterior = (-posteriors.tr_posterior(s, idx).unwrap().value()).exp();
+            let slot = &mut slots[slot_idx];
+            match slot.iter_mut().find(|e| e.label == tr.ilabel) {
+                Some(entry) => entry.posterior += posterior,
+                None => slot.push(SlotEntry {
+                    label: tr.ilabel,
+                    posterior,
+                }),
+            }
+        }
+    }
+
+    for slot in slots.iter_mut() {
+        let mass: f32 = slot.iter().map(|e| e.posterior).sum();
+        let eps_posterior = 1.0 - mass;
+        if eps_posterior > crate::KDELTA {
+            slot.push(SlotEntry {
+                label: EPS_LABEL,
+                posterior: eps_posterior,
+            });
+        }
+        slot.sort_by(|a, b| {
+            b.posterior
+                .total_cmp(&a.posterior)
+                .then(a.label.cmp(&b.label))
+        });
+    }
+
+    Ok(ConfusionNetwork { slots })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::{LogWeight, TropicalWeight};
+
+    fn assert_entry(entry: &SlotEntry, label: Label, posterior: f32) {
+        assert_eq!(entry.label, label);
+        assert!(
+            (entry.posterior - posterior).abs() < 1e-5,
+            "{:?} != {}",
+            entry,
+            posterior
+        );
+    }
+
+    #[test]
+    fn test_confusion_network() -> Result<()> {
+        // Paths "1 2" (p=0.6) and "1 3" (p=0.4) going through different states.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, -(0.5f32.ln()), 1))?;
+        fst.add_tr(0, Tr::new(1, 1, -(0.5f32.ln()), 2))?;
+        fst.add_tr(1, Tr::new(2, 2, -(0.8f32.ln()), 3))?;
+        fst.add_tr(1, Tr::new(3, 3, -(0.2f32.ln()), 3))?;
+        fst.add_tr(2, Tr::new(2, 2, -(0.4f32.ln()), 3))?;
+        fst.add_tr(2, Tr::new(3, 3, -(0.6f32.ln()), 3))?;
+        fst.set_final(3, TropicalWeight::one())?;
+
+        let cn = confusion_network(&fst)?;
+        assert_eq!(cn.len(), 2);
+        assert_eq!(cn.slots()[0].len(), 1);
+        assert_entry(&cn.slots()[0][0], 1, 1.0);
+        assert_entry(&cn.slots()[1][0], 2, 0.6);
+        assert_entry(&cn.slots()[1][1], 3, 0.4);
+
+        let best = cn.mbr_path();
+        assert_eq!(best.len(), 2);
+        assert_entry(&best[0], 1, 1.0);
+        assert_entry(&best[1], 2, 0.6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_confusion_network_deletion() -> Result<()> {
+        // Paths "1 2" (p=0.3) and "3" (p=0.7).
+        let mut fst = VectorFst::<LogWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, -(0.3f32.ln()), 1))?;
+        fst.add_tr(1, Tr::new(2, 2, LogWeight::one(), 2))?;
+        fst.add_tr(0, Tr::new(3, 3, -(0.7f32.ln()), 2))?;
+        fst.set_final(2, LogWeight::one())?;
+
+        let cn = confusion_network(&fst)?;
+        assert_eq!(cn.len(), 2);
+        assert_entry(&cn.slots()[0][0], 3, 0.7);
+        assert_entry(&cn.slots()[0][1], 1, 0.3);
+        assert_entry(&cn.slots()[1][0], EPS_LABEL, 0.7);
+        assert_entry(&cn.slots()[1][1], 2, 0.3);
+
+        let best = cn.mbr_path();
+        assert_eq!(best.len(), 1);
+        assert_entry(&best[0], 3, 0.7);
+
+        let sausage: VectorFst<TropicalWeight> = cn.to_fst()?;
+        assert_eq!(sausage.num_states(), 3);
+        assert_eq!(sausage.num_trs(0)?, 2);
+        assert_eq!(sausage.num_trs(1)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_confusion_network_nan_posterior() -> Result<()> {
+        let mut fst = VectorFst::<LogWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, f32::NAN, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, LogWeight::one(), 1))?;
+        fst.set_final(1, LogWeight::one())?;
+
+        let cn = confusion_network(&fst)?;
+        assert_eq!(cn.len(), 1);
+        // The NaN weight makes the total mass, hence every posterior, NaN. Both entries are kept
+        // with no epsilon entry and, being equal under `total_cmp`, are ordered by label.
+        let slot = &cn.slots()[0];
+        assert_eq!(slot.len(), 2);
+        assert_eq!(slot[0].label, 1);
+        assert_eq!(slot[1].label, 2);
+        assert!(slot.iter().all(|e| e.posterior.is_nan()));
+        assert_eq!(slot[0].posterior.to_bits(), slot[1].posterior.to_bits());
+        Ok(())
+    }
+
+    #[test]
+    fn test_confusion_network_cyclic() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(1, Tr::new(2, 2, 1.0, 0))?;
+        fst.set_final(1, TropicalWeight::one())?;
+
+        assert!(confusion_network(&fst).is_err());
+        Ok(())
+    }
+}
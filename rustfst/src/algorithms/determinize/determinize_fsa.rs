@@ -1,6 +1,7 @@
 use crate::algorithms::determinize::divisors::CommonDivisor;
 use crate::algorithms::determinize::DeterminizeFsaOp;
 use crate::algorithms::lazy::{LazyFst, SimpleHashMapCache};
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
@@ -62,7 +63,7 @@ where
     }
 
     fn properties(&self) -> FstProperties {
-        unimplemented!()
+        self.0.properties()
     }
 
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
@@ -154,6 +155,10 @@ where
         Ok(DeterminizeFsa(lazy_fst, PhantomData))
     }
 
+    pub fn with_limits(self, limits: AlgorithmLimits) -> Self {
+        DeterminizeFsa(self.0.with_limits(limits), PhantomData)
+    }
+
     /// Turns the Lazy FST into a static one.
     pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
         self.0.compute()
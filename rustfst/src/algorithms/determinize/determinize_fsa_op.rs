@@ -13,10 +13,11 @@ use crate::algorithms::determinize::{
     DeterminizeElement, DeterminizeStateTable, DeterminizeStateTuple, DeterminizeTr, WeightedSubset,
 };
 use crate::algorithms::lazy::FstOp;
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::Fst;
 use crate::semirings::{DivideType, WeaklyDivisibleSemiring, WeightQuantize};
-use crate::{Label, Semiring, StateId, Tr, Trs, TrsVec};
+use crate::{Error, Label, Semiring, StateId, Tr, Trs, TrsVec};
 
 #[derive(Debug)]
 pub struct DeterminizeFsaOp<W, F, CD, B, BT>
@@ -30,6 +31,7 @@ where
     fst: B,
     state_table: DeterminizeStateTable<W, BT>,
     delta: f32,
+    limits: AlgorithmLimits,
     ghost: PhantomData<(CD, F)>,
 }
 
@@ -58,6 +60,7 @@ where
         let mut label_map: BTreeMap<Label, DeterminizeTr<W>> = BTreeMap::new();
         let src_tuple = self.state_table.find_tuple(state);
         for src_elt in src_tuple.subset.iter() {
+            self.limits.check_interrupted()?;
             for tr in self.fst.borrow().get_trs(src_elt.state)?.trs() {
                 let r = src_elt.weight.times(&tr.weight)?;
 
@@ -122,6 +125,10 @@ where
         // Properties are set for the DeterminizeFst object. DeterminizeFsa shouldn't be used directly
         FstProperties::empty()
     }
+
+    fn set_limits(&mut self, limits: AlgorithmLimits) {
+        self.limits = limits;
+    }
 }
 
 impl<W, F, CD, B, BT> DeterminizeFsaOp<W, F, CD, B, BT>
@@ -134,22 +141,22 @@ where
 {
     pub fn new(fst: B, in_dist: Option<BT>, delta: f32) -> Result<Self> {
         if !fst.borrow().properties().contains(FstProperties::ACCEPTOR) {
-            bail!("DeterminizeFsaImpl : expected acceptor as argument");
+            return Err(Error::PropertyViolation(
+                "DeterminizeFsaImpl : expected acceptor as argument".to_string(),
+            )
+            .into());
         }
         Ok(Self {
             fst,
             state_table: DeterminizeStateTable::new(in_dist),
             delta,
+            limits: AlgorithmLimits::default(),
             ghost: PhantomData,
         })
     }
 
     fn norm_tr(&self, det_tr: &mut DeterminizeTr<W>) -> Result<()> {
-        det_tr
-            .dest_tuple
-            .subset
-            .pairs
-            .sort_by(|a, b| a.state.cmp(&b.state));
+        det_tr.dest_tuple.subset.pairs.sort_by_key(|a| a.state);
 
         for dest_elt in det_tr.dest_tuple.subset.pairs.iter() {
             det_tr.weight = CD::common_divisor(&det_tr.weight, &dest_elt.weight)?;
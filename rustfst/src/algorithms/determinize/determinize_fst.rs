@@ -0,0 +1,354 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::determinize_fst_op::DeterminizeFstOp;
+use crate::algorithms::determinize::{DeterminizeConfig, DeterminizeType};
+use crate::algorithms::lazy::{FstCache, LazyFst, SimpleHashMapCache};
+use crate::algorithms::AlgorithmLimits;
+use crate::fst_properties::mutable_properties::determinize_properties;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::{WeaklyDivisibleSemiring, WeightQuantize};
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerLazyFst<W, F, B, Cache> = LazyFst<W, DeterminizeFstOp<W, F, B>, Cache>;
+
+/// Determinizes a weighted transducer. This version is a Delayed FST : the states are
+/// only determinized when visited.
+///
+/// Acceptors are determinized directly. Transducers are first turned into acceptors over
+/// the Gallic semiring matching the `DeterminizeType`, determinized, then their weights are
+/// lazily factored back into output labels. As for `determinize`, the input is expected to
+/// be functional when using `DeterminizeFunctional`.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::determinize::{DeterminizeConfig, DeterminizeFst, DeterminizeType};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+/// fst.add_tr(0, Tr::new(1, 3, 2.0, 2))?;
+/// fst.set_final(1, 0.0)?;
+/// fst.set_final(2, 0.0)?;
+///
+/// let config = DeterminizeConfig::default().with_det_type(DeterminizeType::DeterminizeDisambiguate);
+/// let det_fst: DeterminizeFst<_, VectorFst<_>, _> = DeterminizeFst::new_with_config(fst, config)?;
+///
+/// // Only the start state and its transitions are computed here.
+/// let start = det_fst.start().unwrap();
+/// assert_eq!(det_fst.get_trs(start)?.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub struct DeterminizeFst<W, F, B, Cache = SimpleHashMapCache<W>>(InnerLazyFst<W, F, B, Cache>)
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static;
+
+impl<W, F, B, Cache> DeterminizeFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W>,
+{
+    pub fn new(fst: B) -> Result<Self>
+    where
+        Cache: Default,
+    {
+        Self::new_with_config(fst, DeterminizeConfig::default())
+    }
+
+    pub fn new_with_config(fst: B, config: DeterminizeConfig) -> Result<Self>
+    where
+        Cache: Default,
+    {
+        Self::new_with_config_and_cache(fst, config, Cache::default())
+    }
+
+    pub fn new_with_config_and_cache(
+        fst: B,
+        config: DeterminizeConfig,
+        fst_cache: Cache,
+    ) -> Result<Self> {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let distinct_psubsequential_labels =
+            config.det_type != DeterminizeType::DeterminizeNonFunctional;
+        let properties = determinize_properties(
+            fst.borrow().properties(),
+            false,
+            distinct_psubsequential_labels,
+        );
+        let fst_op = DeterminizeFstOp::new(fst, config.delta, config.det_type, properties)?;
+        let lazy_fst = LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt);
+        Ok(DeterminizeFst(lazy_fst))
+    }
+
+    /// Bounds the number of states and transitions computed, see `AlgorithmLimits`.
+    pub fn with_limits(self, limits: AlgorithmLimits) -> Self {
+        DeterminizeFst(self.0.with_limits(limits))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+impl<W, F, B, Cache> CoreFst<W> for DeterminizeFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, Cache> StateIterator<'a> for DeterminizeFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerLazyFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for DeterminizeFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerLazyFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, Cache> Fst<W> for DeterminizeFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, Cache> Debug for DeterminizeFst<W, F, B, Cache>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::determinize::determinize_with_config;
+    use crate::algorithms::isomorphic;
+    use crate::algorithms::lazy::SimpleVecCache;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::ExpandedFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::Tr;
+
+    // Non-functional transducer : input "1 2" can be output as "3 4" or "5 4".
+    fn transducer() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 3, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 5, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 4, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(2, 4, 0.5, 3))?;
+        fst.add_tr(3, Tr::new(1, 0, 0.0, 4))?;
+        fst.set_final(3, 0.0)?;
+        fst.set_final(4, 1.0)?;
+        Ok(fst)
+    }
+
+    // Functional transducer where the output label is only known after reading two labels,
+    // or at the end of the input which requires a super-final state.
+    fn functional_transducer() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 5, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 6, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 0, 1.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 0, 0.5, 3))?;
+        fst.set_final(1, 1.0)?;
+        fst.set_final(3, 0.0)?;
+        Ok(fst)
+    }
+
+    fn check_lazy_equals_static(
+        fst: &VectorFst<TropicalWeight>,
+        det_type: DeterminizeType,
+    ) -> Result<()> {
+        let config = DeterminizeConfig::default().with_det_type(det_type);
+        let static_fst: VectorFst<_> = determinize_with_config(fst, config)?;
+        let lazy_fst: DeterminizeFst<_, VectorFst<_>, _> =
+            DeterminizeFst::new_with_config(fst.clone(), config)?;
+        let computed_fst: VectorFst<_> = lazy_fst.compute()?;
+        assert!(
+            isomorphic(&static_fst, &computed_fst)?,
+            "{:?}\n{}\n{}",
+            det_type,
+            static_fst,
+            computed_fst
+        );
+        assert_eq!(lazy_fst.properties(), static_fst.properties());
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_fst_functional() -> Result<()> {
+        check_lazy_equals_static(
+            &functional_transducer()?,
+            DeterminizeType::DeterminizeFunctional,
+        )
+    }
+
+    #[test]
+    fn test_determinize_fst_non_functional() -> Result<()> {
+        check_lazy_equals_static(&transducer()?, DeterminizeType::DeterminizeNonFunctional)
+    }
+
+    #[test]
+    fn test_determinize_fst_disambiguate() -> Result<()> {
+        check_lazy_equals_static(&transducer()?, DeterminizeType::DeterminizeDisambiguate)
+    }
+
+    #[test]
+    fn test_determinize_fst_acceptor() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 2, 1.0, 2))?;
+        fst.set_final(2, TropicalWeight::one())?;
+        check_lazy_equals_static(&fst, DeterminizeType::DeterminizeFunctional)
+    }
+
+    #[test]
+    fn test_determinize_fst_is_lazy() -> Result<()> {
+        let cache = Arc::new(SimpleHashMapCache::default());
+        let fst = DeterminizeFst::<_, VectorFst<_>, _, _>::new_with_config_and_cache(
+            transducer()?,
+            DeterminizeConfig::default().with_det_type(DeterminizeType::DeterminizeDisambiguate),
+            Arc::clone(&cache),
+        )?;
+        let start = fst.start().unwrap();
+        let trs = fst.get_trs(start)?;
+        assert_eq!(trs.len(), 1);
+        assert_eq!(cache.len_trs(), 1);
+
+        let computed: VectorFst<TropicalWeight> = fst.compute()?;
+        assert!(cache.len_trs() > 1);
+        assert_eq!(computed.num_states(), cache.num_known_states());
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_fst_vec_cache() -> Result<()> {
+        let fst: DeterminizeFst<_, VectorFst<_>, _, SimpleVecCache<_>> =
+            DeterminizeFst::new(functional_transducer()?)?;
+        let static_fst: VectorFst<_> =
+            determinize_with_config(&functional_transducer()?, DeterminizeConfig::default())?;
+        assert!(isomorphic(&fst.compute::<VectorFst<_>>()?, &static_fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_fst_sync() {
+        fn is_sync<T: Sync>() {}
+        is_sync::<DeterminizeFst<TropicalWeight, VectorFst<_>, Arc<VectorFst<_>>>>();
+    }
+}
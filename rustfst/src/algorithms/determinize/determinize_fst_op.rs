@@ -0,0 +1,368 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::determinize::divisors::CommonDivisor;
+use crate::algorithms::determinize::{DefaultCommonDivisor, DeterminizeFsa, DeterminizeFsaOp};
+use crate::algorithms::determinize::{DeterminizeType, GallicCommonDivisor};
+use crate::algorithms::factor_weight::factor_iterators::{
+    GallicFactor, GallicFactorMin, GallicFactorRestrict,
+};
+use crate::algorithms::factor_weight::{
+    FactorIterator, FactorWeightFst, FactorWeightOptions, FactorWeightType,
+};
+use crate::algorithms::lazy::{FstOp, LazyFst, SimpleHashMapCache, StateTable};
+use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
+use crate::algorithms::{AlgorithmLimits, FinalTr, ProjectType, WeightConverter};
+use crate::fst_properties::mutable_properties::project_properties;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst};
+use crate::semirings::{
+    GallicWeight, GallicWeightMin, GallicWeightRestrict, Semiring, SemiringProperties,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::{StateId, Tr, Trs, TrsVec, EPS_LABEL, KDELTA};
+
+/// Lazily maps the weights of an FST to Gallic weights, turning it into an acceptor.
+#[derive(Debug)]
+pub struct ToGallicOp<W, G, F, B> {
+    fst: B,
+    properties: FstProperties,
+    ghost: PhantomData<(W, G, F)>,
+}
+
+impl<W, G, F, B> ToGallicOp<W, G, F, B>
+where
+    W: Semiring,
+    G: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    ToGallicConverter: WeightConverter<W, G>,
+{
+    pub fn new(fst: B) -> Self {
+        // The ilabels are copied to the olabels : the result is an acceptor.
+        let properties = project_properties(fst.borrow().properties(), ProjectType::ProjectInput)
+            & FstProperties::weight_invariant_properties();
+        Self {
+            fst,
+            properties,
+            ghost: PhantomData,
+        }
+    }
+}
+
+impl<W, G, F, B> FstOp<G> for ToGallicOp<W, G, F, B>
+where
+    W: Semiring,
+    G: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    ToGallicConverter: WeightConverter<W, G>,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.borrow().start())
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<G>> {
+        let mut converter = ToGallicConverter {};
+        let trs = self
+            .fst
+            .borrow()
+            .get_trs(id)?
+            .trs()
+            .iter()
+            .map(|tr| converter.tr_map(tr))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<G>> {
+        let mut converter = ToGallicConverter {};
+        self.fst
+            .borrow()
+            .final_weight(id)?
+            .map(|weight| {
+                let final_tr = FinalTr {
+                    ilabel: EPS_LABEL,
+                    olabel: EPS_LABEL,
+                    weight,
+                };
+                converter.final_tr_map(&final_tr).map(|t| t.weight)
+            })
+            .transpose()
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
+
+type ToGallicFst<W, G, F, B> = LazyFst<G, ToGallicOp<W, G, F, B>, SimpleHashMapCache<G>>;
+type GallicDeterminizeFsa<W, G, F, B> = DeterminizeFsa<
+    G,
+    ToGallicFst<W, G, F, B>,
+    GallicCommonDivisor,
+    ToGallicFst<W, G, F, B>,
+    Vec<G>,
+>;
+type FactoredGallicFst<W, G, F, B, FI> =
+    FactorWeightFst<G, GallicDeterminizeFsa<W, G, F, B>, GallicDeterminizeFsa<W, G, F, B>, FI>;
+
+/// Lazily maps the Gallic weights of the determinized and factored acceptor back to
+/// the original weights. The output labels left on the final weights are moved to
+/// transitions towards a single super-final state, represented by `None` in the state table.
+#[derive(Debug)]
+pub struct FromGallicOp<W, G, F, B, FI>
+where
+    W: Semiring,
+    G: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    FI: FactorIterator<G> + 'static,
+    ToGallicConverter: WeightConverter<W, G>,
+    GallicCommonDivisor: CommonDivisor<G>,
+{
+    fst: FactoredGallicFst<W, G, F, B, FI>,
+    state_table: StateTable<Option<StateId>>,
+    limits: AlgorithmLimits,
+    ghost: PhantomData<W>,
+}
+
+impl<W, G, F, B, FI> FromGallicOp<W, G, F, B, FI>
+where
+    W: Semiring,
+    G: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    FI: FactorIterator<G> + 'static,
+    ToGallicConverter: WeightConverter<W, G>,
+    FromGallicConverter: WeightConverter<G, W>,
+    GallicCommonDivisor: CommonDivisor<G>,
+{
+    pub fn new(fst: B, delta: f32) -> Result<Self> {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let gallic_fst = LazyFst::from_op_and_cache(
+            ToGallicOp::new(fst),
+            SimpleHashMapCache::default(),
+            isymt,
+            osymt,
+        );
+        let determinized_fsa = DeterminizeFsa::new(gallic_fst, None, delta)?;
+        let factor_opts = FactorWeightOptions {
+            delta: KDELTA,
+            mode: FactorWeightType::FACTOR_FINAL_WEIGHTS,
+            final_ilabel: EPS_LABEL,
+            final_olabel: EPS_LABEL,
+            increment_final_ilabel: false,
+            increment_final_olabel: false,
+        };
+        Ok(Self {
+            fst: FactorWeightFst::new(determinized_fsa, factor_opts)?,
+            state_table: StateTable::new(),
+            limits: AlgorithmLimits::default(),
+            ghost: PhantomData,
+        })
+    }
+
+    fn final_tr(&self, state: StateId) -> Result<Option<FinalTr<W>>> {
+        let mut converter = FromGallicConverter {
+            superfinal_label: EPS_LABEL,
+        };
+        self.fst
+            .final_weight(state)?
+            .map(|weight| {
+                let final_tr = FinalTr {
+                    ilabel: EPS_LABEL,
+                    olabel: EPS_LABEL,
+                    weight,
+                };
+                converter.final_tr_map(&final_tr)
+            })
+            .transpose()
+    }
+}
+
+impl<W, G, F, B, FI> FstOp<W> for FromGallicOp<W, G, F, B, FI>
+where
+    W: Semiring,
+    G: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    FI: FactorIterator<G> + 'static,
+    ToGallicConverter: WeightConverter<W, G>,
+    FromGallicConverter: WeightConverter<G, W>,
+    GallicCommonDivisor: CommonDivisor<G>,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.start().map(|s| self.state_table.find_id(Some(s))))
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<W>> {
+        self.limits.check_interrupted()?;
+        let state = match self.state_table.find_tuple(id) {
+            Some(s) => s,
+            None => return Ok(TrsVec::default()),
+        };
+        let mut converter = FromGallicConverter {
+            superfinal_label: EPS_LABEL,
+        };
+        let mut trs = vec![];
+        for tr in self.fst.get_trs(state)?.trs() {
+            let mut new_tr = converter.tr_map(tr)?;
+            new_tr.nextstate = self.state_table.find_id(Some(tr.nextstate));
+            trs.push(new_tr);
+        }
+        if let Some(final_tr) = self.final_tr(state)? {
+            if final_tr.olabel != EPS_LABEL {
+                trs.push(Tr::new(
+                    final_tr.ilabel,
+                    final_tr.olabel,
+                    final_tr.weight,
+                    self.state_table.find_id(None),
+                ));
+            }
+        }
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<W>> {
+        match self.state_table.find_tuple(id) {
+            None => Ok(Some(W::one())),
+            Some(state) => Ok(self
+                .final_tr(state)?
+                .filter(|final_tr| final_tr.olabel == EPS_LABEL)
+                .map(|final_tr| final_tr.weight)),
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        // Set by DeterminizeFstOp.
+        FstProperties::empty()
+    }
+
+    fn set_limits(&mut self, limits: AlgorithmLimits) {
+        self.limits = limits;
+    }
+}
+
+/// Operation behind `DeterminizeFst`. Acceptors are directly determinized whereas
+/// transducers go through the Gallic semiring matching the determinization type.
+#[derive(Debug)]
+pub enum DeterminizeFstOp<W, F, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+{
+    Fsa(
+        DeterminizeFsaOp<W, F, DefaultCommonDivisor, B, Vec<W>>,
+        FstProperties,
+    ),
+    Functional(
+        FromGallicOp<W, GallicWeightRestrict<W>, F, B, GallicFactorRestrict<W>>,
+        FstProperties,
+    ),
+    NonFunctional(
+        FromGallicOp<W, GallicWeight<W>, F, B, GallicFactor<W>>,
+        FstProperties,
+    ),
+    Disambiguate(
+        FromGallicOp<W, GallicWeightMin<W>, F, B, GallicFactorMin<W>>,
+        FstProperties,
+    ),
+}
+
+impl<W, F, B> DeterminizeFstOp<W, F, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+{
+    pub fn new(
+        fst: B,
+        delta: f32,
+        det_type: DeterminizeType,
+        properties: FstProperties,
+    ) -> Result<Self> {
+        if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
+            bail!("DeterminizeFst : weight must be left distributive")
+        }
+        if fst.borrow().properties().contains(FstProperties::ACCEPTOR) {
+            return Ok(DeterminizeFstOp::Fsa(
+                DeterminizeFsaOp::new(fst, None, delta)?,
+                properties,
+            ));
+        }
+        let op = match det_type {
+            DeterminizeType::DeterminizeFunctional => {
+                DeterminizeFstOp::Functional(FromGallicOp::new(fst, delta)?, properties)
+            }
+            DeterminizeType::DeterminizeNonFunctional => {
+                DeterminizeFstOp::NonFunctional(FromGallicOp::new(fst, delta)?, properties)
+            }
+            DeterminizeType::DeterminizeDisambiguate => {
+                if !W::properties().contains(SemiringProperties::PATH) {
+                    bail!("DeterminizeFst : weight needs to have the path property to disambiguate output")
+                }
+                DeterminizeFstOp::Disambiguate(FromGallicOp::new(fst, delta)?, properties)
+            }
+        };
+        Ok(op)
+    }
+}
+
+impl<W, F, B> FstOp<W> for DeterminizeFstOp<W, F, B>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        match self {
+            DeterminizeFstOp::Fsa(op, _) => op.compute_start(),
+            DeterminizeFstOp::Functional(op, _) => op.compute_start(),
+            DeterminizeFstOp::NonFunctional(op, _) => op.compute_start(),
+            DeterminizeFstOp::Disambiguate(op, _) => op.compute_start(),
+        }
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<W>> {
+        match self {
+            DeterminizeFstOp::Fsa(op, _) => op.compute_trs(id),
+            DeterminizeFstOp::Functional(op, _) => op.compute_trs(id),
+            DeterminizeFstOp::NonFunctional(op, _) => op.compute_trs(id),
+            DeterminizeFstOp::Disambiguate(op, _) => op.compute_trs(id),
+        }
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<W>> {
+        match self {
+            DeterminizeFstOp::Fsa(op, _) => op.compute_final_weight(id),
+            DeterminizeFstOp::Functional(op, _) => op.compute_final_weight(id),
+            DeterminizeFstOp::NonFunctional(op, _) => op.compute_final_weight(id),
+            DeterminizeFstOp::Disambiguate(op, _) => op.compute_final_weight(id),
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        match self {
+            DeterminizeFstOp::Fsa(_, props)
+            | DeterminizeFstOp::Functional(_, props)
+            | DeterminizeFstOp::NonFunctional(_, props)
+            | DeterminizeFstOp::Disambiguate(_, props) => *props,
+        }
+    }
+
+    fn set_limits(&mut self, limits: AlgorithmLimits) {
+        match self {
+            DeterminizeFstOp::Fsa(op, _) => op.set_limits(limits),
+            DeterminizeFstOp::Functional(op, _) => op.set_limits(limits),
+            DeterminizeFstOp::NonFunctional(op, _) => op.set_limits(limits),
+            DeterminizeFstOp::Disambiguate(op, _) => op.set_limits(limits),
+        }
+    }
+}
@@ -9,8 +9,8 @@ use crate::algorithms::factor_weight::factor_iterators::{
     GallicFactor, GallicFactorMin, GallicFactorRestrict,
 };
 use crate::algorithms::factor_weight::{factor_weight, FactorWeightOptions, FactorWeightType};
-use crate::algorithms::weight_convert;
 use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
+use crate::algorithms::{weight_convert, AlgorithmLimits};
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::mutable_properties::determinize_properties;
 use crate::fst_properties::FstProperties;
@@ -38,7 +38,11 @@ where
     fst.compute_with_distance()
 }
 
-pub fn determinize_fsa<W, F1, F2, CD>(fst_in: &F1, delta: f32) -> Result<F2>
+pub fn determinize_fsa<W, F1, F2, CD>(
+    fst_in: &F1,
+    delta: f32,
+    limits: AlgorithmLimits,
+) -> Result<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize,
     F1: Fst<W>,
@@ -48,11 +52,17 @@ where
     if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
         bail!("determinize_fsa : weight must be left distributive")
     }
-    let det_fsa: DeterminizeFsa<W, F1, CD, _, Vec<W>> = DeterminizeFsa::new(fst_in, None, delta)?;
+    let det_fsa: DeterminizeFsa<W, F1, CD, _, Vec<W>> =
+        DeterminizeFsa::new(fst_in, None, delta)?.with_limits(limits);
     det_fsa.compute()
 }
 
-pub fn determinize_fst<W, F1, F2>(fst_in: &F1, det_type: DeterminizeType, delta: f32) -> Result<F2>
+pub fn determinize_fst<W, F1, F2>(
+    fst_in: &F1,
+    det_type: DeterminizeType,
+    delta: f32,
+    limits: AlgorithmLimits,
+) -> Result<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
     F1: ExpandedFst<W>,
@@ -80,7 +90,7 @@ where
             let fsa: VectorFst<GallicWeightMin<W>> =
                 weight_convert(fst_in.borrow(), &mut to_gallic)?;
             let determinized_fsa: VectorFst<GallicWeightMin<W>> =
-                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta)?;
+                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta, limits)?;
             let factored_determinized_fsa: VectorFst<GallicWeightMin<W>> =
                 factor_weight::<_, VectorFst<GallicWeightMin<W>>, _, _, GallicFactorMin<W>>(
                     &determinized_fsa,
@@ -92,7 +102,7 @@ where
             let fsa: VectorFst<GallicWeightRestrict<W>> =
                 weight_convert(fst_in.borrow(), &mut to_gallic)?;
             let determinized_fsa: VectorFst<GallicWeightRestrict<W>> =
-                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta)?;
+                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta, limits)?;
             let factored_determinized_fsa: VectorFst<GallicWeightRestrict<W>> =
                 factor_weight::<
                     _,
@@ -106,7 +116,7 @@ where
         DeterminizeType::DeterminizeNonFunctional => {
             let fsa: VectorFst<GallicWeight<W>> = weight_convert(fst_in.borrow(), &mut to_gallic)?;
             let determinized_fsa: VectorFst<GallicWeight<W>> =
-                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta)?;
+                determinize_fsa::<_, VectorFst<_>, _, GallicCommonDivisor>(&fsa, delta, limits)?;
             let factored_determinized_fsa: VectorFst<GallicWeight<W>> =
                 factor_weight::<_, VectorFst<GallicWeight<W>>, _, _, GallicFactor<W>>(
                     &determinized_fsa,
@@ -170,6 +180,47 @@ where
 /// ![determinize_out](https://raw.githubusercontent.com/Garvys/rustfst-images-doc/master/images/determinize_out.svg?sanitize=true)
 ///
 pub fn determinize_with_config<W, F1, F2>(fst_in: &F1, config: DeterminizeConfig) -> Result<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize,
+    F1: ExpandedFst<W>,
+    F2: MutableFst<W> + AllocableFst<W>,
+{
+    determinize_with_limits(fst_in, config, AlgorithmLimits::default())
+}
+
+/// Same as `determinize_with_config` but stops with a `LimitExceeded` error as soon as
+/// one of the `limits` is hit while determinizing.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::determinize::{determinize_with_limits, DeterminizeConfig};
+/// # use rustfst::algorithms::{AlgorithmLimits, LimitExceeded};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+/// fst.add_tr(0, Tr::new(1, 1, 2.0, 2))?;
+/// fst.set_final(1, 0.0)?;
+/// fst.set_final(2, 0.0)?;
+///
+/// let limits = AlgorithmLimits::new().with_max_states(1);
+/// let res: Result<VectorFst<_>> =
+///     determinize_with_limits(&fst, DeterminizeConfig::default(), limits);
+/// assert_eq!(
+///     res.unwrap_err().downcast_ref::<LimitExceeded>(),
+///     Some(&LimitExceeded::MaxStates(1))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn determinize_with_limits<W, F1, F2>(
+    fst_in: &F1,
+    config: DeterminizeConfig,
+    limits: AlgorithmLimits,
+) -> Result<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize,
     F1: ExpandedFst<W>,
@@ -179,9 +230,9 @@ where
     let det_type = config.det_type;
     let iprops = fst_in.borrow().properties();
     let mut fst_res: F2 = if iprops.contains(FstProperties::ACCEPTOR) {
-        determinize_fsa::<_, F1, _, DefaultCommonDivisor>(fst_in, delta)?
+        determinize_fsa::<_, F1, _, DefaultCommonDivisor>(fst_in, delta, limits)?
     } else {
-        determinize_fst(fst_in, det_type, delta)?
+        determinize_fst(fst_in, det_type, delta, limits)?
     };
 
     let distinct_psubsequential_labels = !(det_type == DeterminizeType::DeterminizeNonFunctional);
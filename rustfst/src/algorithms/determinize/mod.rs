@@ -1,7 +1,9 @@
 use determinize_fsa::DeterminizeFsa;
 use determinize_fsa_op::DeterminizeFsaOp;
+pub use determinize_fst::DeterminizeFst;
 pub use determinize_static::{
-    determinize, determinize_with_config, determinize_with_distance, DeterminizeConfig,
+    determinize, determinize_with_config, determinize_with_distance, determinize_with_limits,
+    DeterminizeConfig,
 };
 use divisors::{DefaultCommonDivisor, GallicCommonDivisor};
 use element::{DeterminizeElement, DeterminizeStateTuple, DeterminizeTr, WeightedSubset};
@@ -9,6 +11,8 @@ use state_table::DeterminizeStateTable;
 
 mod determinize_fsa;
 mod determinize_fsa_op;
+mod determinize_fst;
+mod determinize_fst_op;
 mod determinize_static;
 mod divisors;
 mod element;
@@ -16,6 +16,9 @@ enum DfsStateColor {
     Black,
 }
 
+/// Callbacks invoked by [`dfs_visit`] during a depth-first search of an FST.
+///
+/// The callbacks returning a `bool` can stop the search by returning `false`.
 pub trait Visitor<'a, W: Semiring, F: Fst<W>> {
     /// Invoked before DFS visit.
     fn init_visit(&mut self, fst: &'a F);
@@ -32,8 +35,7 @@ pub trait Visitor<'a, W: Semiring, F: Fst<W>> {
     /// Invoked when forward or cross transition to black/finished state examined.
     fn forward_or_cross_tr(&mut self, s: StateId, tr: &Tr<W>) -> bool;
 
-    /// Invoked when state finished ('s' is tree root, 'parent' is kNoStateId,
-    /// and 'tr' is nullptr).
+    /// Invoked when state finished (if `s` is a tree root, `parent` and `tr` are `None`).
     fn finish_state(&mut self, s: StateId, parent: Option<StateId>, tr: Option<&Tr<W>>);
 
     /// Invoked after DFS visit.
@@ -94,6 +96,12 @@ impl<W: Semiring, TRS: Trs<W>> OpenFstIterator<W, TRS> {
     }
 }
 
+/// Performs a depth-first search of `fst`, calling the callbacks of `visitor` as states and
+/// transitions are discovered. Only the transitions kept by `tr_filter` are followed.
+///
+/// The search starts from the start state. If `access_only` is false, it then restarts from
+/// every state that hasn't been visited yet, in increasing order, so that all the states are
+/// visited. Nothing is visited if the FST has no start state.
 pub fn dfs_visit<'a, W: Semiring, F: ExpandedFst<W>, V: Visitor<'a, W, F>, A: TrFilter<W>>(
     fst: &'a F,
     visitor: &mut V,
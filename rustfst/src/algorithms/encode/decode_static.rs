@@ -1,3 +1,6 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
 use anyhow::{Context, Result};
 
 use crate::algorithms::{encode::EncodeTable, rm_final_epsilon};
@@ -6,28 +9,36 @@ use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
 use crate::{Semiring, Tr};
 
-struct DecodeMapper<W: Semiring> {
-    encode_table: EncodeTable<W>,
+struct DecodeMapper<W: Semiring, B: Borrow<EncodeTable<W>>> {
+    encode_table: B,
+    ghost: PhantomData<W>,
 }
 
-impl<W: Semiring> DecodeMapper<W> {
-    pub fn new(encode_table: EncodeTable<W>) -> Self {
-        DecodeMapper { encode_table }
+impl<W: Semiring, B: Borrow<EncodeTable<W>>> DecodeMapper<W, B> {
+    pub fn new(encode_table: B) -> Self {
+        DecodeMapper {
+            encode_table,
+            ghost: PhantomData,
+        }
+    }
+
+    fn table(&self) -> &EncodeTable<W> {
+        self.encode_table.borrow()
     }
 
     pub fn encode_weights(&self) -> bool {
-        self.encode_table.0.borrow().encode_type.encode_weights()
+        self.table().0.borrow().encode_type.encode_weights()
     }
 
     pub fn encode_labels(&self) -> bool {
-        self.encode_table.0.borrow().encode_type.encode_labels()
+        self.table().0.borrow().encode_type.encode_labels()
     }
 }
 
-impl<W: Semiring> TrMapper<W> for DecodeMapper<W> {
+impl<W: Semiring, B: Borrow<EncodeTable<W>>> TrMapper<W> for DecodeMapper<W, B> {
     fn tr_map(&self, tr: &mut Tr<W>) -> Result<()> {
         let tuple = self
-            .encode_table
+            .table()
             .0
             .borrow_mut()
             .decode(tr.ilabel as usize)
@@ -68,11 +79,12 @@ impl<W: Semiring> TrMapper<W> for DecodeMapper<W> {
 }
 
 /// The `decode` operation takes as input an encoded FST and the corresponding `EncodeTable` object
-/// and reverts the encoding.
-pub fn decode<W, F>(fst: &mut F, encode_table: EncodeTable<W>) -> Result<()>
+/// and reverts the encoding. The table can be passed by reference to decode several FSTs.
+pub fn decode<W, F, B>(fst: &mut F, encode_table: B) -> Result<()>
 where
     W: Semiring,
     F: MutableFst<W>,
+    B: Borrow<EncodeTable<W>>,
 {
     let mut decode_mapper = DecodeMapper::new(encode_table);
     fst.tr_map(&mut decode_mapper)
@@ -0,0 +1,420 @@
+use std::borrow::Borrow;
+
+use anyhow::Result;
+
+use crate::algorithms::compose::compose;
+use crate::algorithms::stochastic::first_non_stochastic_state;
+use crate::algorithms::tr_compares::ILabelCompare;
+use crate::algorithms::weight_converters::SimpleWeightConverter;
+use crate::algorithms::{
+    shortest_distance_with_config, tr_sort, weight_convert, ShortestDistanceConfig,
+};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst};
+use crate::semirings::{
+    LogWeight, ProductWeight, RealWeight, ReverseBack, Semiring, SemiringProperties,
+};
+use crate::{Error, StateId, Tr, Trs, KDELTA};
+
+/// Expectation semiring over the log semiring. A weight `(p, v)` stores a probability `p`
+/// as a `LogWeight` and the sum `v` of the values of the paths weighted by their probability.
+///
+/// (p1, v1) ⊕ (p2, v2) = (p1 + p2, v1 + v2)
+/// (p1, v1) ⊗ (p2, v2) = (p1 * p2, p1 * v2 + p2 * v1)
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+struct ExpectationWeight {
+    value: (LogWeight, RealWeight),
+}
+
+impl ExpectationWeight {
+    // Weight of a transition with probability `exp(-w)` and value `v`.
+    fn from_log(w: LogWeight, v: f32) -> Self {
+        let p = prob(&w);
+        Self::new((w, RealWeight::new(p * v)))
+    }
+}
+
+fn prob(w: &LogWeight) -> f32 {
+    (-*w.value()).exp()
+}
+
+impl Semiring for ExpectationWeight {
+    type Type = (LogWeight, RealWeight);
+    type ReverseWeight = ExpectationWeight;
+
+    fn zero() -> Self {
+        Self::new((LogWeight::zero(), RealWeight::zero()))
+    }
+
+    fn one() -> Self {
+        Self::new((LogWeight::one(), RealWeight::zero()))
+    }
+
+    fn new(value: Self::Type) -> Self {
+        Self { value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        self.value.0.plus_assign(rhs.value.0)?;
+        self.value.1.plus_assign(rhs.value.1)
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        let v =
+            prob(&self.value.0) * rhs.value.1.value() + prob(&rhs.value.0) * self.value.1.value();
+        self.value.0.times_assign(rhs.value.0)?;
+        self.value.1 = RealWeight::new(v);
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        let rhs = rhs.borrow();
+        self.value.0.approx_equal(rhs.value.0, delta)
+            && self.value.1.approx_equal(rhs.value.1, delta)
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: Self::Type) {
+        self.value = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(self.clone())
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+    }
+}
+
+impl ReverseBack<ExpectationWeight> for ExpectationWeight {
+    fn reverse_back(&self) -> Result<ExpectationWeight> {
+        Ok(self.clone())
+    }
+}
+
+/// Compute the entropy, in nats, of the distribution over the paths defined by an FST:
+/// `H = - Σ p(π) log p(π)`.
+///
+/// The weights of the FST are interpreted as negated log probabilities (as for `LogWeight`)
+/// and the FST must be stochastic : at every state, the probabilities of the outgoing
+/// transitions and of the final weight must sum to one. Cyclic FSTs are supported, the sum
+/// over the infinite number of paths being computed by `shortest_distance`.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::entropy;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// // Uniform distribution over two paths.
+/// let mut fst = VectorFst::<LogWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_tr(s0, Tr::new(1, 1, 2f32.ln(), s1))?;
+/// fst.add_tr(s0, Tr::new(2, 2, 2f32.ln(), s1))?;
+/// fst.set_final(s1, LogWeight::one())?;
+///
+/// assert!((entropy(&fst)? - 2f32.ln()).abs() < 1e-5);
+/// # Ok(())
+/// # }
+/// ```
+pub fn entropy<W, F>(fst: &F) -> Result<f32>
+where
+    W: Semiring<Type = f32>,
+    F: ExpandedFst<W>,
+{
+    entropy_with_config(fst, ShortestDistanceConfig::default())
+}
+
+/// Compute the entropy of the distribution defined by an FST, with configurable delta for
+/// the shortest distance computation.
+pub fn entropy_with_config<W, F>(fst: &F, config: ShortestDistanceConfig) -> Result<f32>
+where
+    W: Semiring<Type = f32>,
+    F: ExpandedFst<W>,
+{
+    let log_fst: VectorFst<LogWeight> = weight_convert(fst, &mut SimpleWeightConverter {})?;
+    check_stochastic(&log_fst, "Entropy")?;
+
+    // The value of a transition is its weight : the expectation of the weight of a path,
+    // i.e - Σ p(π) log p(π).
+    let exp_fst: VectorFst<ExpectationWeight> =
+        map_weights(&log_fst, |w| ExpectationWeight::from_log(*w, *w.value()))?;
+    let total = total_weight(&exp_fst, config)?;
+    Ok(*total.value.1.value())
+}
+
+/// Compute the relative entropy (or Kullback-Leibler divergence), in nats, of the
+/// distribution defined by `fst1` with respect to the one defined by `fst2`:
+/// `D(p1 || p2) = Σ p1(x) log(p1(x) / p2(x))`.
+///
+/// Both FSTs must be stochastic deterministic acceptors, their weights being interpreted as
+/// negated log probabilities. The sum is computed over the paths of the composition of the
+/// two FSTs. If a string of `fst1` is not accepted by `fst2`, the divergence is infinite.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::relative_entropy;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let build = |p: f32| -> Result<VectorFst<LogWeight>> {
+///     let mut fst = VectorFst::new();
+///     let s0 = fst.add_state();
+///     let s1 = fst.add_state();
+///     fst.set_start(s0)?;
+///     fst.add_tr(s0, Tr::new(1, 1, -p.ln(), s1))?;
+///     fst.add_tr(s0, Tr::new(2, 2, -(1.0 - p).ln(), s1))?;
+///     fst.set_final(s1, LogWeight::one())?;
+///     Ok(fst)
+/// };
+/// let fst1 = build(0.5)?;
+/// let fst2 = build(0.25)?;
+///
+/// let expected = 0.5 * (0.5f32 / 0.25).ln() + 0.5 * (0.5f32 / 0.75).ln();
+/// assert!((relative_entropy(&fst1, &fst2)? - expected).abs() < 1e-5);
+/// assert!(relative_entropy(&fst1, &fst1)?.abs() < 1e-5);
+/// # Ok(())
+/// # }
+/// ```
+pub fn relative_entropy<W, F1, F2>(fst1: &F1, fst2: &F2) -> Result<f32>
+where
+    W: Semiring<Type = f32>,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+{
+    relative_entropy_with_config(fst1, fst2, ShortestDistanceConfig::default())
+}
+
+/// Compute the relative entropy of the distribution defined by `fst1` with respect to the
+/// one defined by `fst2`, with configurable delta for the shortest distance computation.
+pub fn relative_entropy_with_config<W, F1, F2>(
+    fst1: &F1,
+    fst2: &F2,
+    config: ShortestDistanceConfig,
+) -> Result<f32>
+where
+    W: Semiring<Type = f32>,
+    F1: ExpandedFst<W>,
+    F2: ExpandedFst<W>,
+{
+    let log_fst1: VectorFst<LogWeight> = weight_convert(fst1, &mut SimpleWeightConverter {})?;
+    let log_fst2: VectorFst<LogWeight> = weight_convert(fst2, &mut SimpleWeightConverter {})?;
+    for fst in [&log_fst1, &log_fst2] {
+        check_deterministic_acceptor(fst)?;
+        check_stochastic(fst, "RelativeEntropy")?;
+    }
+
+    // Keep both weights on the transitions of the composition.
+    type PairWeight = ProductWeight<LogWeight, LogWeight>;
+    let pair_fst1: VectorFst<PairWeight> =
+        map_weights(&log_fst1, |w| PairWeight::new((*w, LogWeight::one())))?;
+    let mut pair_fst2: VectorFst<PairWeight> =
+        map_weights(&log_fst2, |w| PairWeight::new((LogWeight::one(), *w)))?;
+    tr_sort(&mut pair_fst2, ILabelCompare {});
+    let composed: VectorFst<PairWeight> = compose(pair_fst1, pair_fst2)?;
+
+    // The value of a transition is log(p1 / p2) = w2 - w1.
+    let exp_fst: VectorFst<ExpectationWeight> = map_weights(&composed, |w| {
+        let (w1, w2) = (w.value1(), w.value2());
+        ExpectationWeight::from_log(*w1, w2.value() - w1.value())
+    })?;
+    let total = total_weight(&exp_fst, config)?;
+
+    // Probability mass of fst1 lost in the composition.
+    if !total.value.0.approx_equal(LogWeight::one(), KDELTA) {
+        return Ok(f32::INFINITY);
+    }
+    Ok(*total.value.1.value())
+}
+
+fn check_stochastic(fst: &VectorFst<LogWeight>, algo_name: &str) -> Result<()> {
+    if let Some((s, sum)) = first_non_stochastic_state(fst, KDELTA)? {
+        bail!(
+            "{}: the FST is not stochastic, the weights leaving the state {} sum to {}",
+            algo_name,
+            s,
+            sum
+        );
+    }
+    Ok(())
+}
+
+fn check_deterministic_acceptor(fst: &VectorFst<LogWeight>) -> Result<()> {
+    let mut known = FstProperties::empty();
+    let props = compute_fst_properties(
+        fst,
+        FstProperties::ACCEPTOR
+            | FstProperties::NOT_ACCEPTOR
+            | FstProperties::I_DETERMINISTIC
+            | FstProperties::NOT_I_DETERMINISTIC,
+        &mut known,
+        true,
+    )?;
+    if !props.contains(FstProperties::ACCEPTOR | FstProperties::I_DETERMINISTIC) {
+        return Err(Error::PropertyViolation(
+            "RelativeEntropy: the FSTs must be deterministic acceptors".to_string(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn map_weights<W1: Semiring, W2: Semiring, M: Fn(&W1) -> W2>(
+    fst: &VectorFst<W1>,
+    map: M,
+) -> Result<VectorFst<W2>> {
+    let mut res = VectorFst::new();
+    res.add_states(fst.num_states());
+    if let Some(start) = fst.start() {
+        res.set_start(start)?;
+    }
+    for s in fst.states_range() {
+        for tr in fst.get_trs(s)?.trs() {
+            res.add_tr(
+                s,
+                Tr::new(tr.ilabel, tr.olabel, map(&tr.weight), tr.nextstate),
+            )?;
+        }
+        if let Some(final_weight) = fst.final_weight(s)? {
+            res.set_final(s, map(&final_weight))?;
+        }
+    }
+    Ok(res)
+}
+
+// ⊕-sum of the weights of all the successful paths.
+fn total_weight(
+    fst: &VectorFst<ExpectationWeight>,
+    config: ShortestDistanceConfig,
+) -> Result<ExpectationWeight> {
+    let distance = shortest_distance_with_config(fst, false, config)?;
+    let mut total = ExpectationWeight::zero();
+    for (s, d) in distance.iter().enumerate() {
+        if let Some(final_weight) = fst.final_weight(s as StateId)? {
+            total.plus_assign(d.times(final_weight)?)?;
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semirings::TropicalWeight;
+
+    // Geometric distribution over the strings 1^n : p(1^n) = q^n (1 - q).
+    fn geometric(q: f32) -> Result<VectorFst<LogWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 1, -q.ln(), s0))?;
+        fst.set_final(s0, -(1.0 - q).ln())?;
+        Ok(fst)
+    }
+
+    fn geometric_entropy(q: f32) -> f32 {
+        -(q * q.ln() + (1.0 - q) * (1.0 - q).ln()) / (1.0 - q)
+    }
+
+    #[test]
+    fn test_entropy_acyclic() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, -(0.5f32).ln(), 1))?;
+        fst.add_tr(0, Tr::new(2, 2, -(0.5f32).ln(), 2))?;
+        fst.add_tr(1, Tr::new(3, 3, -(0.25f32).ln(), 2))?;
+        fst.set_final(1, -(0.75f32).ln())?;
+        fst.set_final(2, TropicalWeight::one())?;
+
+        let probs = [0.5f32 * 0.75, 0.5 * 0.25, 0.5];
+        let expected: f32 = probs.iter().map(|p| -p * p.ln()).sum();
+        assert!((entropy(&fst)? - expected).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_entropy_cyclic() -> Result<()> {
+        for q in [0.1, 0.5, 0.8] {
+            let fst = geometric(q)?;
+            let config = ShortestDistanceConfig::new(1e-6);
+            assert!((entropy_with_config(&fst, config)? - geometric_entropy(q)).abs() < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_entropy_not_stochastic() -> Result<()> {
+        let mut fst = geometric(0.5)?;
+        fst.set_final(0, LogWeight::one())?;
+        let err = entropy(&fst).unwrap_err().to_string();
+        assert!(
+            err.contains("the weights leaving the state 0 sum to"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_entropy_cyclic() -> Result<()> {
+        let (q1, q2) = (0.5f32, 0.25f32);
+        let config = ShortestDistanceConfig::new(1e-6);
+        let kl = relative_entropy_with_config(&geometric(q1)?, &geometric(q2)?, config)?;
+
+        // Σ_n p1(n) (n log(q1 / q2) + log((1 - q1) / (1 - q2))) with E[n] = q1 / (1 - q1).
+        let expected = q1 / (1.0 - q1) * (q1 / q2).ln() + ((1.0 - q1) / (1.0 - q2)).ln();
+        assert!((kl - expected).abs() < 1e-3);
+
+        let kl = relative_entropy_with_config(&geometric(q1)?, &geometric(q1)?, config)?;
+        assert!(kl.abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_entropy_missing_string() -> Result<()> {
+        let mut fst1 = VectorFst::<LogWeight>::new();
+        fst1.add_states(2);
+        fst1.set_start(0)?;
+        fst1.add_tr(0, Tr::new(1, 1, -(0.5f32).ln(), 1))?;
+        fst1.add_tr(0, Tr::new(2, 2, -(0.5f32).ln(), 1))?;
+        fst1.set_final(1, LogWeight::one())?;
+
+        let mut fst2 = VectorFst::<LogWeight>::new();
+        fst2.add_states(2);
+        fst2.set_start(0)?;
+        fst2.add_tr(0, Tr::new(1, 1, LogWeight::one(), 1))?;
+        fst2.set_final(1, LogWeight::one())?;
+
+        assert_eq!(relative_entropy(&fst1, &fst2)?, f32::INFINITY);
+        assert!(relative_entropy(&fst2, &fst1)?.is_finite());
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_entropy_errors() -> Result<()> {
+        let mut transducer = geometric(0.5)?;
+        transducer.add_tr(0, Tr::new(1, 2, LogWeight::zero(), 0))?;
+        assert!(relative_entropy(&transducer, &geometric(0.5)?).is_err());
+
+        let mut not_stochastic = geometric(0.5)?;
+        not_stochastic.set_final(0, LogWeight::one())?;
+        assert!(relative_entropy(&geometric(0.5)?, &not_stochastic).is_err());
+        Ok(())
+    }
+}
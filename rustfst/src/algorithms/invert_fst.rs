@@ -0,0 +1,217 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstCache, SimpleHashMapCache};
+use crate::algorithms::tr_mappers::InvertMapper;
+use crate::algorithms::TrMapFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, TrsVec};
+
+type InnerFst<W, F, B, Cache> = TrMapFst<W, F, B, InvertMapper, Cache>;
+
+/// Inverts a transduction by swapping the input and output labels of each transition.
+/// This version is a Delayed FST : the input FST is left untouched and the transitions
+/// of a state are only inverted when the state is visited.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::algorithms::InvertFst;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<TropicalWeight> = fst![2 => 3];
+/// let inverted_fst: InvertFst<_, VectorFst<_>, _> = InvertFst::new(&fst);
+///
+/// let expected_fst: VectorFst<TropicalWeight> = fst![3 => 2];
+/// assert_eq!(inverted_fst.compute::<VectorFst<_>>()?, expected_fst);
+/// # Ok(())
+/// # }
+/// ```
+pub struct InvertFst<W, F, B, Cache = SimpleHashMapCache<W>>(InnerFst<W, F, B, Cache>)
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug;
+
+impl<W, F, B, Cache> InvertFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    Cache: FstCache<W>,
+{
+    pub fn new(fst: B) -> Self
+    where
+        Cache: Default,
+    {
+        Self::new_with_cache(fst, Cache::default())
+    }
+
+    pub fn new_with_cache(fst: B, fst_cache: Cache) -> Self {
+        InvertFst(TrMapFst::new_with_cache(fst, InvertMapper {}, fst_cache))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+impl<W, F, B, Cache> CoreFst<W> for InvertFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, Cache> StateIterator<'a> for InvertFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for InvertFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, Cache> Fst<W> for InvertFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, Cache> Debug for InvertFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::invert;
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_properties::mutable_properties::invert_properties;
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    #[test]
+    fn test_invert_fst() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(3, 0, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(0, 4, 0.5, 2))?;
+        fst.set_final(2, 0.0)?;
+        let const_fst: ConstFst<_> = fst.clone().into();
+        let expected_props = invert_properties(const_fst.properties());
+
+        let mut static_fst = fst;
+        invert(&mut static_fst);
+
+        let lazy_fst: InvertFst<_, ConstFst<_>, _> = InvertFst::new(Arc::new(const_fst));
+        assert_eq!(lazy_fst.properties(), expected_props);
+        assert_eq!(lazy_fst.compute::<VectorFst<_>>()?, static_fst);
+        Ok(())
+    }
+}
@@ -1,17 +1,16 @@
 use std::sync::Arc;
 
 use nom::multi::count;
-use nom::IResult;
 
 use crate::algorithms::lazy::cache::cache_internal_types::{CacheTrs, FinalWeight, StartState};
 use crate::algorithms::lazy::CacheStatus;
-use crate::parsers::bin_fst::utils_parsing::{parse_bin_fst_tr, parse_start_state};
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::bin_fst::utils_parsing::{
+    parse_bin_fst_tr, parse_start_state, MIN_BIN_TR_SIZE,
+};
+use crate::parsers::nom_utils::{check_count, IResultCustomError};
 use crate::parsers::{parse_bin_i64, parse_bin_u64, parse_bin_u8};
 use crate::prelude::{SerializableSemiring, StateId, TrsVec};
 
-pub(crate) type IResultCustomError<A, B> = IResult<A, B, NomCustomError<A>>;
-
 pub(crate) fn parse_cache_start_state(
     i: &[u8],
 ) -> IResultCustomError<&[u8], CacheStatus<StartState>> {
@@ -33,7 +32,9 @@ pub(crate) fn parse_vec_cache_trs<W: SerializableSemiring>(
         Ok((i, CacheStatus::NotComputed))
     } else {
         let (i, num_trs) = parse_bin_i64(i)?;
-        let (i, trs) = count(parse_bin_fst_tr::<W>, num_trs as usize)(i)?;
+        let num_trs = check_count(i, num_trs, MIN_BIN_TR_SIZE)?;
+        // The number of states of a lazy FST is unknown, nextstates can't be bounded.
+        let (i, trs) = count(parse_bin_fst_tr::<W>(usize::MAX), num_trs)(i)?;
         let (i, niepsilons) = parse_bin_u64(i)?;
         let (i, noepsilons) = parse_bin_u64(i)?;
 
@@ -53,7 +54,8 @@ pub(crate) fn parse_hashmap_cache_trs<W: SerializableSemiring>(
 ) -> IResultCustomError<&[u8], (StateId, CacheTrs<W>)> {
     let (i, state) = parse_bin_i64(i)?;
     let (i, num_trs) = parse_bin_i64(i)?;
-    let (i, trs) = count(parse_bin_fst_tr::<W>, num_trs as usize)(i)?;
+    let num_trs = check_count(i, num_trs, MIN_BIN_TR_SIZE)?;
+    let (i, trs) = count(parse_bin_fst_tr::<W>(usize::MAX), num_trs)(i)?;
     let (i, niepsilons) = parse_bin_u64(i)?;
     let (i, noepsilons) = parse_bin_u64(i)?;
 
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::path::Path;
 
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::semirings::Semiring;
 use crate::{StateId, TrsVec};
@@ -15,6 +16,11 @@ pub trait FstOp<W: Semiring>: Debug {
 
     // Computed at construction time
     fn properties(&self) -> FstProperties;
+
+    /// Called by the lazy FST when `AlgorithmLimits` are set. Operations doing expensive work
+    /// should keep them and stop as soon as the algorithm is cancelled or the deadline has
+    /// passed. The sizes of the output are enforced by the lazy FST.
+    fn set_limits(&mut self, _limits: AlgorithmLimits) {}
 }
 
 impl<W: Semiring, F: FstOp<W>, FP: Deref<Target = F> + Debug> FstOp<W> for FP {
@@ -3,6 +3,7 @@ use std::ops::Deref;
 
 use anyhow::Result;
 
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::{Semiring, StateId, TrsVec};
 
@@ -12,6 +13,11 @@ pub trait FstOp2<W: Semiring>: Debug {
     fn compute_trs_and_final_weight(&self, id: StateId) -> Result<(TrsVec<W>, Option<W>)>;
 
     fn properties(&self) -> FstProperties;
+
+    /// Called by the lazy FST when `AlgorithmLimits` are set. Operations doing expensive work
+    /// should keep them and stop as soon as the algorithm is cancelled or the deadline has
+    /// passed. The sizes of the output are enforced by the lazy FST.
+    fn set_limits(&mut self, _limits: AlgorithmLimits) {}
 }
 
 impl<W: Semiring, F: FstOp2<W>, FP: Deref<Target = F> + Debug> FstOp2<W> for FP {
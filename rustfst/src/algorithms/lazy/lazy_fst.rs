@@ -12,6 +12,7 @@ use itertools::izip;
 use crate::algorithms::lazy::cache::CacheStatus;
 use crate::algorithms::lazy::fst_op::{AccessibleOpState, FstOp, SerializableOpState};
 use crate::algorithms::lazy::{FstCache, SerializableCache};
+use crate::algorithms::limits::{AlgorithmLimits, LimitsTracker};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{
     AllocableFst, CoreFst, Fst, FstIterData, FstIterator, MutableFst, StateIterator,
@@ -19,6 +20,12 @@ use crate::fst_traits::{
 use crate::semirings::{Semiring, SerializableSemiring};
 use crate::{StateId, SymbolTable, Trs, TrsVec};
 
+// The cache can't tell apart a state that doesn't exist from a state that hasn't been expanded,
+// so the error isn't reported as `Error::InvalidStateId`.
+pub(crate) fn not_expanded(state: StateId) -> anyhow::Error {
+    format_err!("State {} doesn't exist or hasn't been expanded yet", state)
+}
+
 #[derive(Debug, Clone)]
 pub struct LazyFst<W: Semiring, Op: FstOp<W>, Cache> {
     cache: Cache,
@@ -26,6 +33,7 @@ pub struct LazyFst<W: Semiring, Op: FstOp<W>, Cache> {
     w: PhantomData<W>,
     isymt: Option<Arc<SymbolTable>>,
     osymt: Option<Arc<SymbolTable>>,
+    limits: Option<LimitsTracker>,
 }
 
 impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op, Cache> {
@@ -56,13 +64,13 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
     }
 
     unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
-        self.final_weight(state_id).unwrap_unchecked()
+        // Computing the final weight can fail even for a valid state.
+        self.final_weight(state_id)
+            .unwrap_or_else(|e| panic!("Can't compute the final weight of {}: {:?}", state_id, e))
     }
 
     fn num_trs(&self, s: StateId) -> Result<usize> {
-        self.cache
-            .num_trs(s)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", s))
+        self.cache.num_trs(s).ok_or_else(|| not_expanded(s))
     }
 
     unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
@@ -75,13 +83,19 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
             CacheStatus::NotComputed => {
                 let trs = self.op.compute_trs(state_id)?;
                 self.cache.insert_trs(state_id, trs.shallow_clone());
+                if let Some(limits) = &self.limits {
+                    limits.state_expanded(state_id, trs.trs())?;
+                }
                 Ok(trs)
             }
         }
     }
 
     unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
-        self.get_trs(state_id).unwrap_unchecked()
+        // Expanding a state can fail even for a valid state, e.g when a limit is hit. Callers
+        // that need to handle it must use `get_trs`.
+        self.get_trs(state_id)
+            .unwrap_or_else(|e| panic!("Can't expand the state {}: {:?}", state_id, e))
     }
 
     fn properties(&self) -> FstProperties {
@@ -91,13 +105,13 @@ impl<W: Semiring, Op: FstOp<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst<W, Op
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
         self.cache
             .num_input_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+            .ok_or_else(|| not_expanded(state))
     }
 
     fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
         self.cache
             .num_output_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+            .ok_or_else(|| not_expanded(state))
     }
 }
 
@@ -219,9 +233,32 @@ where
             isymt,
             osymt,
             w: PhantomData,
+            limits: None,
         }
     }
 
+    /// Bounds the expansion of the states. Once a limit is hit, the methods expanding
+    /// new states return a `LimitExceeded` error. A state is counted once its transitions
+    /// are in the cache: the state that hit the limit stays available.
+    pub fn with_limits(mut self, limits: AlgorithmLimits) -> Self {
+        self.op.set_limits(limits.clone());
+        Self {
+            limits: LimitsTracker::new(limits),
+            ..self
+        }
+    }
+
+    /// Computes the transitions and the final weight of `state` if it hasn't been visited yet.
+    ///
+    /// `num_trs`, `num_input_epsilons` and `num_output_epsilons` only look up the cache and
+    /// fail for states that haven't been expanded. Calling this method first makes them
+    /// available. Fails with the error of the underlying operation, or with a `LimitExceeded`
+    /// error if limits are set.
+    pub fn expand_state(&self, state: StateId) -> Result<()> {
+        self.get_trs(state)?;
+        Ok(())
+    }
+
     /// Turns the Lazy FST into a static one.
     pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
         let start_state = self.start();
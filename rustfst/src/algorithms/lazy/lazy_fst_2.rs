@@ -6,7 +6,9 @@ use anyhow::Result;
 use itertools::izip;
 
 use crate::algorithms::lazy::fst_op_2::FstOp2;
+use crate::algorithms::lazy::lazy_fst::not_expanded;
 use crate::algorithms::lazy::{CacheStatus, FstCache};
+use crate::algorithms::limits::{AlgorithmLimits, LimitsTracker};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, Fst, FstIterData, FstIterator, MutableFst, StateIterator};
 use crate::semirings::Semiring;
@@ -20,6 +22,7 @@ pub struct LazyFst2<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> {
     w: PhantomData<W>,
     isymt: Option<Arc<SymbolTable>>,
     osymt: Option<Arc<SymbolTable>>,
+    limits: Option<LimitsTracker>,
 }
 
 impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst2<W, Op, Cache> {
@@ -42,22 +45,26 @@ impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst2<W,
             CacheStatus::Computed(final_weight) => Ok(final_weight),
             CacheStatus::NotComputed => {
                 let (trs, final_weight) = self.op.compute_trs_and_final_weight(state_id)?;
-                self.cache.insert_trs(state_id, trs);
+                self.cache.insert_trs(state_id, trs.shallow_clone());
                 self.cache
                     .insert_final_weight(state_id, final_weight.clone());
+                if let Some(limits) = &self.limits {
+                    limits.state_expanded(state_id, trs.trs())?;
+                }
                 Ok(final_weight)
             }
         }
     }
 
     unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
-        self.final_weight(state_id).unwrap_unchecked()
+        // Expanding a state can fail even for a valid state, e.g when a limit is hit. Callers
+        // that need to handle it must use `final_weight`.
+        self.final_weight(state_id)
+            .unwrap_or_else(|e| panic!("Can't expand the state {}: {:?}", state_id, e))
     }
 
     fn num_trs(&self, s: StateId) -> Result<usize> {
-        self.cache
-            .num_trs(s)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", s))
+        self.cache.num_trs(s).ok_or_else(|| not_expanded(s))
     }
 
     unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
@@ -71,13 +78,18 @@ impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst2<W,
                 let (trs, final_weight) = self.op.compute_trs_and_final_weight(state_id)?;
                 self.cache.insert_trs(state_id, trs.shallow_clone());
                 self.cache.insert_final_weight(state_id, final_weight);
+                if let Some(limits) = &self.limits {
+                    limits.state_expanded(state_id, trs.trs())?;
+                }
                 Ok(trs)
             }
         }
     }
 
     unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
-        self.get_trs(state_id).unwrap_unchecked()
+        // Same as `final_weight_unchecked`.
+        self.get_trs(state_id)
+            .unwrap_or_else(|e| panic!("Can't expand the state {}: {:?}", state_id, e))
     }
 
     fn properties(&self) -> FstProperties {
@@ -87,13 +99,13 @@ impl<W: Semiring, Op: FstOp2<W>, Cache: FstCache<W>> CoreFst<W> for LazyFst2<W,
     fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
         self.cache
             .num_input_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+            .ok_or_else(|| not_expanded(state))
     }
 
     fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
         self.cache
             .num_output_epsilons(state)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+            .ok_or_else(|| not_expanded(state))
     }
 }
 
@@ -215,6 +227,18 @@ where
             isymt,
             osymt,
             w: PhantomData,
+            limits: None,
+        }
+    }
+
+    /// Bounds the expansion of the states. Once a limit is hit, the methods expanding
+    /// new states return a `LimitExceeded` error. A state is counted once its transitions
+    /// are in the cache: the state that hit the limit stays available.
+    pub fn with_limits(mut self, limits: AlgorithmLimits) -> Self {
+        self.op.set_limits(limits.clone());
+        Self {
+            limits: LimitsTracker::new(limits),
+            ..self
         }
     }
 
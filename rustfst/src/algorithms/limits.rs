@@ -0,0 +1,534 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::{StateId, Tr};
+
+type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// Bounds on the resources an expensive algorithm (`determinize`, `compose`, `rm_epsilon`,
+/// `replace`, ...) is allowed to use, along with a way to cancel it from another thread.
+///
+/// The limits are checked every time a state of the output is expanded. When one of them is
+/// hit, the algorithm stops and returns an error wrapping a [`LimitExceeded`] that can be
+/// retrieved with `downcast_ref`, or with [`LimitExceeded::find`] once context has been
+/// attached. It is also reported as an `Error::LimitExceeded`. By default, no limit is set.
+///
+/// `rm_epsilon` and `replace` take the limits in their config. `determinize` and `compose`
+/// take them as a separate argument (`determinize_with_limits`, `compose_with_limits`) so
+/// that their configs stay `Copy` and comparable. The lazy FSTs take them through
+/// `with_limits`.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::{AlgorithmLimits, LimitExceeded};
+/// # use rustfst::algorithms::determinize::{determinize_with_limits, DeterminizeConfig};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+/// fst.add_tr(1, Tr::new(2, 2, 1.0, 2))?;
+/// fst.set_final(2, 0.0)?;
+///
+/// let limits = AlgorithmLimits::new().with_max_states(2);
+/// let err = determinize_with_limits::<_, _, VectorFst<_>>(&fst, DeterminizeConfig::default(), limits)
+///     .unwrap_err();
+/// assert_eq!(
+///     err.downcast_ref::<LimitExceeded>(),
+///     Some(&LimitExceeded::MaxStates(2))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct AlgorithmLimits {
+    max_states: Option<usize>,
+    max_trs: Option<usize>,
+    deadline: Option<Instant>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    progress_callback: Option<ProgressCallback>,
+}
+
+impl AlgorithmLimits {
+    /// Creates limits with no bound, cancel flag nor callback set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of states of the output.
+    pub fn with_max_states(self, max_states: usize) -> Self {
+        Self {
+            max_states: Some(max_states),
+            ..self
+        }
+    }
+
+    /// Maximum number of transitions of the output.
+    pub fn with_max_trs(self, max_trs: usize) -> Self {
+        Self {
+            max_trs: Some(max_trs),
+            ..self
+        }
+    }
+
+    /// Instant after which the algorithm is stopped.
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Same as `with_deadline`, the deadline being `timeout` from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Flag that can be set from another thread to cancel the algorithm.
+    pub fn with_cancel_flag(self, cancel_flag: Arc<AtomicBool>) -> Self {
+        Self {
+            cancel_flag: Some(cancel_flag),
+            ..self
+        }
+    }
+
+    /// Callback called every time a state of the output has been expanded.
+    pub fn with_progress_callback<C: Fn(&Progress) + Send + Sync + 'static>(
+        self,
+        callback: C,
+    ) -> Self {
+        Self {
+            progress_callback: Some(Arc::new(callback)),
+            ..self
+        }
+    }
+
+    /// Maximum number of states of the output, if set. A state counts as soon as a computed
+    /// transition reaches it, even if its own transitions haven't been computed yet.
+    pub fn max_states(&self) -> Option<usize> {
+        self.max_states
+    }
+
+    /// Maximum number of transitions of the output, if set, summed over the expanded states.
+    pub fn max_trs(&self) -> Option<usize> {
+        self.max_trs
+    }
+
+    /// Instant after which the algorithm is stopped, if set. It is only checked between state
+    /// expansions, so an expansion that has already started runs to completion.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns true if no limit, cancel flag nor callback has been set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_states.is_none()
+            && self.max_trs.is_none()
+            && self.deadline.is_none()
+            && self.cancel_flag.is_none()
+            && self.progress_callback.is_none()
+    }
+
+    /// Fails if the cancel flag is set or the deadline has passed. Cheap enough to be called
+    /// by the `FstOp`s before each expensive step.
+    pub(crate) fn check_interrupted(&self) -> Result<()> {
+        if let Some(cancel_flag) = &self.cancel_flag {
+            if cancel_flag.load(AtomicOrdering::Relaxed) {
+                return Err(LimitExceeded::Cancelled.into_error());
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(LimitExceeded::Deadline.into_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for AlgorithmLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlgorithmLimits")
+            .field("max_states", &self.max_states)
+            .field("max_trs", &self.max_trs)
+            .field("deadline", &self.deadline)
+            .field("cancel_flag", &self.cancel_flag)
+            .field("progress_callback", &self.progress_callback.is_some())
+            .finish()
+    }
+}
+
+// The cancel flags and callbacks are compared by address.
+impl PartialEq for AlgorithmLimits {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T: ?Sized>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+        self.max_states == other.max_states
+            && self.max_trs == other.max_trs
+            && self.deadline == other.deadline
+            && same(&self.cancel_flag, &other.cancel_flag)
+            && same(&self.progress_callback, &other.progress_callback)
+    }
+}
+
+impl Eq for AlgorithmLimits {}
+
+/// Statistics about the output of an algorithm, passed to the progress callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of states whose transitions have been computed.
+    pub num_expanded_states: usize,
+    /// Number of states discovered so far.
+    pub num_states: usize,
+    /// Number of transitions computed so far.
+    pub num_trs: usize,
+    /// Time elapsed since the beginning of the algorithm.
+    pub elapsed: Duration,
+}
+
+/// Error returned when one of the `AlgorithmLimits` is hit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The output has more states than the maximum allowed.
+    MaxStates(usize),
+    /// The output has more transitions than the maximum allowed.
+    MaxTrs(usize),
+    /// The deadline has passed.
+    Deadline,
+    /// The cancel flag has been set.
+    Cancelled,
+}
+
+impl LimitExceeded {
+    /// Look for a `LimitExceeded` in the chain of causes of an `anyhow::Error`.
+    pub fn find(err: &anyhow::Error) -> Option<LimitExceeded> {
+        err.chain()
+            .find_map(|e| e.downcast_ref::<LimitExceeded>())
+            .copied()
+    }
+
+    /// The returned error can be retrieved both with `downcast_ref::<LimitExceeded>()` and
+    /// with `Error::find`.
+    pub(crate) fn into_error(self) -> anyhow::Error {
+        anyhow::Error::new(self).context(crate::Error::LimitExceeded(self))
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::MaxStates(n) => write!(f, "Limit exceeded: more than {} states", n),
+            LimitExceeded::MaxTrs(n) => write!(f, "Limit exceeded: more than {} transitions", n),
+            LimitExceeded::Deadline => write!(f, "Limit exceeded: deadline reached"),
+            LimitExceeded::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+impl Error for LimitExceeded {}
+
+/// Keeps track of the size of the output of an algorithm to enforce `AlgorithmLimits`.
+pub(crate) struct LimitsTracker {
+    limits: AlgorithmLimits,
+    start_time: Instant,
+    num_expanded_states: AtomicUsize,
+    num_states: AtomicUsize,
+    num_trs: AtomicUsize,
+}
+
+impl LimitsTracker {
+    /// Returns `None` when there is nothing to track.
+    pub(crate) fn new(limits: AlgorithmLimits) -> Option<Self> {
+        if limits.is_unlimited() {
+            return None;
+        }
+        Some(Self {
+            limits,
+            start_time: Instant::now(),
+            num_expanded_states: AtomicUsize::new(0),
+            num_states: AtomicUsize::new(0),
+            num_trs: AtomicUsize::new(0),
+        })
+    }
+
+    /// To be called once the transitions leaving `state` have been computed and inserted in
+    /// the cache, so that a state is counted only once.
+    pub(crate) fn state_expanded<W>(&self, state: StateId, trs: &[Tr<W>]) -> Result<()> {
+        let num_states = trs
+            .iter()
+            .map(|tr| tr.nextstate)
+            .chain(std::iter::once(state))
+            .max()
+            .unwrap() as usize
+            + 1;
+        let num_states = self
+            .num_states
+            .fetch_max(num_states, AtomicOrdering::Relaxed)
+            .max(num_states);
+        let num_trs = self.num_trs.fetch_add(trs.len(), AtomicOrdering::Relaxed) + trs.len();
+        let num_expanded_states = self
+            .num_expanded_states
+            .fetch_add(1, AtomicOrdering::Relaxed)
+            + 1;
+
+        self.limits.check_interrupted()?;
+        if let Some(max_states) = self.limits.max_states {
+            if num_states > max_states {
+                return Err(LimitExceeded::MaxStates(max_states).into_error());
+            }
+        }
+        if let Some(max_trs) = self.limits.max_trs {
+            if num_trs > max_trs {
+                return Err(LimitExceeded::MaxTrs(max_trs).into_error());
+            }
+        }
+        if let Some(callback) = &self.limits.progress_callback {
+            callback(&Progress {
+                num_expanded_states,
+                num_states,
+                num_trs,
+                elapsed: self.start_time.elapsed(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Clone for LimitsTracker {
+    fn clone(&self) -> Self {
+        Self {
+            limits: self.limits.clone(),
+            start_time: self.start_time,
+            num_expanded_states: AtomicUsize::new(
+                self.num_expanded_states.load(AtomicOrdering::Relaxed),
+            ),
+            num_states: AtomicUsize::new(self.num_states.load(AtomicOrdering::Relaxed)),
+            num_trs: AtomicUsize::new(self.num_trs.load(AtomicOrdering::Relaxed)),
+        }
+    }
+}
+
+impl fmt::Debug for LimitsTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LimitsTracker")
+            .field("limits", &self.limits)
+            .field("num_expanded_states", &self.num_expanded_states)
+            .field("num_states", &self.num_states)
+            .field("num_trs", &self.num_trs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::algorithms::compose::{compose_with_limits, ComposeConfig};
+    use crate::algorithms::determinize::{
+        determinize_with_config, determinize_with_limits, DeterminizeConfig, DeterminizeFst,
+    };
+    use crate::algorithms::rm_epsilon::RmEpsilonFst;
+    use crate::algorithms::tr_compares::ILabelCompare;
+    use crate::algorithms::tr_sort;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst, StateIterator};
+    use crate::semirings::TropicalWeight;
+    use crate::EPS_LABEL;
+
+    // Non-deterministic acceptor whose determinization has an exponential number of states.
+    fn exponential_fst(n: StateId) -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(n as usize + 1);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 0.0, 0))?;
+        fst.add_tr(0, Tr::new(2, 2, 0.0, 0))?;
+        fst.add_tr(0, Tr::new(1, 1, 0.0, 1))?;
+        for s in 1..n {
+            fst.add_tr(s, Tr::new(1, 1, 0.0, s + 1))?;
+            fst.add_tr(s, Tr::new(2, 2, 0.0, s + 1))?;
+        }
+        fst.set_final(n, 0.0)?;
+        Ok(fst)
+    }
+
+    fn limit_exceeded(err: anyhow::Error) -> Option<LimitExceeded> {
+        let limit = err.downcast_ref::<LimitExceeded>().cloned();
+        assert_eq!(LimitExceeded::find(&err), limit);
+        assert!(matches!(
+            crate::Error::find(&err),
+            Some(crate::Error::LimitExceeded(l)) if Some(*l) == limit
+        ));
+        limit
+    }
+
+    #[test]
+    fn test_determinize_limits() -> Result<()> {
+        let fst = exponential_fst(8)?;
+        let config = DeterminizeConfig::default();
+        let limits = AlgorithmLimits::new().with_max_states(100);
+        let err = determinize_with_limits::<_, _, VectorFst<_>>(&fst, config, limits).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::MaxStates(100)));
+
+        let limits = AlgorithmLimits::new().with_max_trs(100);
+        let err = determinize_with_limits::<_, _, VectorFst<_>>(&fst, config, limits).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::MaxTrs(100)));
+
+        // Limits that are not hit don't change the result.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 1))?;
+        fst.add_tr(1, Tr::new(2, 2, 1.0, 2))?;
+        fst.set_final(2, 0.0)?;
+        let det: VectorFst<_> = determinize_with_config(&fst, DeterminizeConfig::default())?;
+        let limits = AlgorithmLimits::new()
+            .with_max_states(3)
+            .with_max_trs(2)
+            .with_timeout(Duration::from_secs(3600));
+        let det_limited: VectorFst<_> =
+            determinize_with_limits(&fst, DeterminizeConfig::default(), limits)?;
+        assert_eq!(det_limited, det);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancel_flag_and_deadline() -> Result<()> {
+        let fst = exponential_fst(8)?;
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cancel_flag);
+        let limits = AlgorithmLimits::new()
+            .with_cancel_flag(Arc::clone(&cancel_flag))
+            .with_progress_callback(move |progress| {
+                if progress.num_expanded_states == 10 {
+                    flag.store(true, AtomicOrdering::Relaxed);
+                }
+            });
+        let config = DeterminizeConfig::default();
+        let err = determinize_with_limits::<_, _, VectorFst<_>>(&fst, config, limits).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::Cancelled));
+
+        let limits = AlgorithmLimits::new().with_deadline(Instant::now());
+        let err = determinize_with_limits::<_, _, VectorFst<_>>(&fst, config, limits).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::Deadline));
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_callback() -> Result<()> {
+        let fst = exponential_fst(3)?;
+        let progresses = Arc::new(Mutex::new(vec![]));
+        let progresses_cloned = Arc::clone(&progresses);
+        let limits = AlgorithmLimits::new().with_progress_callback(move |progress| {
+            progresses_cloned.lock().unwrap().push(*progress)
+        });
+        let det: VectorFst<_> =
+            determinize_with_limits(&fst, DeterminizeConfig::default(), limits)?;
+
+        let progresses = progresses.lock().unwrap();
+        assert_eq!(progresses.len(), det.num_states());
+        let last = progresses.last().unwrap();
+        assert_eq!(last.num_expanded_states, det.num_states());
+        assert_eq!(last.num_states, det.num_states());
+        assert_eq!(
+            last.num_trs,
+            det.states_iter()
+                .map(|s| det.num_trs(s).unwrap())
+                .sum::<usize>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_limits() -> Result<()> {
+        let mut fst = exponential_fst(4)?;
+        tr_sort(&mut fst, ILabelCompare {});
+        let limits = AlgorithmLimits::new().with_max_trs(5);
+        let err =
+            compose_with_limits::<TropicalWeight, VectorFst<_>, VectorFst<_>, _, _, VectorFst<_>>(
+                &fst,
+                &fst,
+                ComposeConfig::default(),
+                limits,
+            )
+            .unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::MaxTrs(5)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_fst_limits() -> Result<()> {
+        let fst = exponential_fst(8)?;
+        let det_fst: DeterminizeFst<_, VectorFst<_>, _> = DeterminizeFst::new(fst.clone())?
+            .with_limits(AlgorithmLimits::new().with_max_states(3));
+        // The start state has 2 successors.
+        let start = det_fst.start().unwrap();
+        assert_eq!(det_fst.get_trs(start)?.len(), 2);
+        let err = det_fst.get_trs(1).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::MaxStates(3)));
+        // The state that hit the limit has been cached and is not counted twice.
+        assert_eq!(det_fst.get_trs(1)?.len(), 2);
+        assert_eq!(det_fst.num_trs(1)?, 2);
+
+        let mut eps_fst = fst;
+        eps_fst.add_tr(1, Tr::new(EPS_LABEL, EPS_LABEL, 0.0, 0))?;
+        let rmeps_fst = RmEpsilonFst::new(eps_fst)
+            .map(|f| f.with_limits(AlgorithmLimits::new().with_max_trs(2)))?;
+        let err = rmeps_fst.compute::<VectorFst<_>>().unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::MaxTrs(2)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fst_op_cancelled() -> Result<()> {
+        let fst = exponential_fst(8)?;
+        let cancel_flag = Arc::new(AtomicBool::new(true));
+        let det_fst: DeterminizeFst<_, VectorFst<_>, _> = DeterminizeFst::new(fst.clone())?
+            .with_limits(AlgorithmLimits::new().with_cancel_flag(Arc::clone(&cancel_flag)));
+        let start = det_fst.start().unwrap();
+        let err = det_fst.get_trs(start).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::Cancelled));
+
+        // Nothing has been computed nor cached, the expansion resumes once the flag is reset.
+        cancel_flag.store(false, AtomicOrdering::Relaxed);
+        assert_eq!(det_fst.get_trs(start)?.len(), 2);
+
+        cancel_flag.store(true, AtomicOrdering::Relaxed);
+        let rmeps_fst = RmEpsilonFst::new(fst)?
+            .with_limits(AlgorithmLimits::new().with_cancel_flag(cancel_flag));
+        let err = rmeps_fst.final_weight(0).unwrap_err();
+        assert_eq!(limit_exceeded(err), Some(LimitExceeded::Cancelled));
+        Ok(())
+    }
+
+    #[test]
+    fn test_limits_eq() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let limits = AlgorithmLimits::new()
+            .with_max_states(2)
+            .with_cancel_flag(Arc::clone(&flag));
+        assert_eq!(limits.clone(), limits);
+        assert_ne!(
+            limits,
+            AlgorithmLimits::new()
+                .with_max_states(2)
+                .with_cancel_flag(Arc::new(AtomicBool::new(false)))
+        );
+        assert!(AlgorithmLimits::default().is_unlimited());
+        assert!(!limits.is_unlimited());
+    }
+}
@@ -3,17 +3,23 @@ pub use self::{
     all_pairs_shortest_distance::all_pairs_shortest_distance,
     condense::condense,
     connect::connect,
+    entropy::{entropy, entropy_with_config, relative_entropy, relative_entropy_with_config},
     fst_convert::{fst_convert, fst_convert_from_ref},
     inversion::invert,
+    invert_fst::InvertFst,
     isomorphic::{isomorphic, isomorphic_with_config, IsomorphicConfig},
+    limits::{AlgorithmLimits, LimitExceeded, Progress},
     minimize::{acceptor_minimize, minimize, minimize_with_config, MinimizeConfig},
     optimize::optimize,
+    posteriors::{label_occupancy, posteriors, posteriors_with_config, Posteriors},
+    project_fst::ProjectFst,
     projection::{project, ProjectType},
     push::{
         push, push_weights, push_weights_with_config, push_with_config, PushConfig, PushType,
         PushWeightsConfig,
     },
     queue::{Queue, QueueType},
+    relabel_fst::RelabelFst,
     relabel_pairs::relabel_pairs,
     reverse::reverse,
     reweight::{reweight, ReweightType},
@@ -21,9 +27,12 @@ pub use self::{
     shortest_distance::{shortest_distance, shortest_distance_with_config, ShortestDistanceConfig},
     shortest_path::{shortest_path, shortest_path_with_config, ShortestPathConfig},
     state_sort::state_sort,
+    stochastic::{is_stochastic, normalize, normalize_with_config},
     top_sort::top_sort,
     tr_map::{tr_map, FinalTr, MapFinalAction, TrMapper},
+    tr_map_fst::TrMapFst,
     tr_sort::tr_sort,
+    tr_sort_fst::TrSortFst,
     tr_sum::tr_sum,
     tr_unique::tr_unique,
     weight_convert::{weight_convert, WeightConverter},
@@ -40,26 +49,34 @@ pub mod compose;
 /// Functions to concatenate FSTs.
 pub mod concat;
 mod condense;
+/// Functions to build word confusion networks from lattices and decode them.
+pub mod confusion_network;
 mod connect;
 /// Functions to determinize FSTs.
 pub mod determinize;
 pub(crate) mod dfs_visit;
 /// Functions to encode FSTs as FSAs and vice versa.
 pub mod encode;
+mod entropy;
 /// Functions to factor various weight types.
 pub mod factor_weight;
 mod fst_convert;
 mod inversion;
+mod invert_fst;
 mod isomorphic;
+pub(crate) mod limits;
 mod minimize;
 mod optimize;
 mod partition;
+mod posteriors;
+mod project_fst;
 mod projection;
 mod push;
 mod queue;
 
 /// Functions to randomly generate paths through an Fst. A static and a delayed version are available.
 pub mod randgen;
+mod relabel_fst;
 mod relabel_pairs;
 /// Functions for lazy replacing transitions in an FST.
 pub mod replace;
@@ -72,9 +89,12 @@ mod rm_final_epsilon;
 mod shortest_distance;
 mod shortest_path;
 mod state_sort;
+mod stochastic;
 mod top_sort;
 mod tr_map;
+mod tr_map_fst;
 mod tr_sort;
+mod tr_sort_fst;
 mod tr_sum;
 pub(crate) mod tr_unique;
 /// Functions to compute the union of FSTs.
@@ -90,7 +110,7 @@ pub mod tr_filters;
 /// Module that provides structures implementing the `TrMapper` trait.
 pub mod tr_mappers;
 
-pub(crate) mod visitors;
+pub mod visitors;
 
 /// Module providing structures implementing the `WeightConverter` trait.
 pub mod weight_converters;
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::algorithms::weight_converters::SimpleWeightConverter;
+use crate::algorithms::{shortest_distance_with_config, weight_convert, ShortestDistanceConfig};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::{compute_fst_properties, FstProperties};
+use crate::fst_traits::{CoreFst, ExpandedFst};
+use crate::semirings::{DivideType, LogWeight, Semiring, WeaklyDivisibleSemiring};
+use crate::{Error, Label, StateId, Trs, EPS_LABEL};
+
+/// Posterior weights computed by the forward-backward algorithm.
+///
+/// All the weights are expressed in the log semiring, i.e as the negated natural logarithm
+/// of a probability. A posterior of `LogWeight::one()` corresponds to a probability of 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Posteriors {
+    total_weight: LogWeight,
+    state_posteriors: Vec<LogWeight>,
+    tr_posteriors: Vec<Vec<LogWeight>>,
+}
+
+impl Posteriors {
+    /// ⊕-sum of the weights of all the successful paths of the FST.
+    pub fn total_weight(&self) -> &LogWeight {
+        &self.total_weight
+    }
+
+    /// Posterior of every state, indexed by `StateId`.
+    pub fn state_posteriors(&self) -> &[LogWeight] {
+        &self.state_posteriors
+    }
+
+    /// Posterior of a state i.e the probability that a successful path goes through it.
+    pub fn state_posterior(&self, state: StateId) -> Option<&LogWeight> {
+        self.state_posteriors.get(state as usize)
+    }
+
+    /// Posteriors of all the trs leaving a state, in the same order as `get_trs`.
+    pub fn tr_posteriors(&self, state: StateId) -> Option<&[LogWeight]> {
+        self.tr_posteriors.get(state as usize).map(|v| v.as_slice())
+    }
+
+    /// Posterior of the `tr_idx`-th tr leaving `state` i.e the probability
+    /// that a successful path takes this transition.
+    pub fn tr_posterior(&self, state: StateId, tr_idx: usize) -> Option<&LogWeight> {
+        self.tr_posteriors
+            .get(state as usize)
+            .and_then(|v| v.get(tr_idx))
+    }
+}
+
+/// Compute the state and transition posteriors of an FST using the forward-backward algorithm.
+///
+/// The weights of the FST are interpreted as negated log probabilities and the computation is
+/// performed in the `LogWeight` semiring, whatever the semiring of the input FST
+/// (e.g `TropicalWeight` for a lattice).
+///
+/// An error is returned if the FST has no successful path.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::posteriors;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_tr(s0, Tr::new(1, 1, -(0.75f32.ln()), s1))?;
+/// fst.add_tr(s0, Tr::new(2, 2, -(0.25f32.ln()), s1))?;
+/// fst.set_final(s1, TropicalWeight::one())?;
+///
+/// let posteriors = posteriors(&fst)?;
+/// let p = posteriors.tr_posterior(s0, 0).unwrap();
+/// assert!(((-p.value()).exp() - 0.75).abs() < 1e-5);
+/// # Ok(())
+/// # }
+/// ```
+pub fn posteriors<W, F>(fst: &F) -> Result<Posteriors>
+where
+    W: Semiring<Type = f32>,
+    F: ExpandedFst<W>,
+{
+    posteriors_with_config(fst, ShortestDistanceConfig::default())
+}
+
+/// Compute the state and transition posteriors of an FST using the forward-backward algorithm,
+/// with configurable delta for the shortest distance computations.
+pub fn posteriors_with_config<W, F>(fst: &F, config: ShortestDistanceConfig) -> Result<Posteriors>
+where
+    W: Semiring<Type = f32>,
+    F: ExpandedFst<W>,
+{
+    let start = match fst.start() {
+        Some(s) => s,
+        None => bail!("Posteriors: the FST has no start state"),
+    };
+    let log_fst: VectorFst<LogWeight> = weight_convert(fst, &mut SimpleWeightConverter {})?;
+
+    let alpha = shortest_distance_with_config(&log_fst, false, config)?;
+    let beta = shortest_distance_with_config(&log_fst, true, config)?;
+
+    let total_weight = distance(&beta, start);
+    if total_weight.is_zero() {
+        bail!("Posteriors: the FST has no successful path");
+    }
+
+    let num_states = log_fst.num_states();
+    let mut state_posteriors = Vec::with_capacity(num_states);
+    let mut tr_posteriors = Vec::with_capacity(num_states);
+    for s in log_fst.states_range() {
+        let a = distance(&alpha, s);
+        let mut state_post = a.times(distance(&beta, s))?;
+        state_post.divide_assign(&total_weight, DivideType::DivideAny)?;
+        state_posteriors.push(state_post);
+
+        let trs = log_fst.get_trs(s)?;
+        let mut posts = Vec::with_capacity(trs.len());
+        for tr in trs.trs() {
+            let mut post = a.times(tr.weight)?;
+            post.times_assign(distance(&beta, tr.nextstate))?;
+            post.divide_assign(&total_weight, DivideType::DivideAny)?;
+            posts.push(post);
+        }
+        tr_posteriors.push(posts);
+    }
+
+    Ok(Posteriors {
+        total_weight,
+        state_posteriors,
+        tr_posteriors,
+    })
+}
+
+// States unreachable from the source are not always present in the shortest distance vector.
+fn distance(d: &[LogWeight], s: StateId) -> LogWeight {
+    d.get(s as usize).cloned().unwrap_or_else(LogWeight::zero)
+}
+
+/// Compute the expected number of occurrences of each non-epsilon label in an acceptor,
+/// i.e the ⊕-sum of the posteriors of all the trs carrying that label.
+///
+/// An error is returned if the FST is not an acceptor.
+pub fn label_occupancy<W, F>(fst: &F) -> Result<HashMap<Label, LogWeight>>
+where
+    W: Semiring<Type = f32>,
+    F: ExpandedFst<W>,
+{
+    let mut known = FstProperties::empty();
+    let props = compute_fst_properties(
+        fst,
+        FstProperties::ACCEPTOR | FstProperties::NOT_ACCEPTOR,
+        &mut known,
+        true,
+    )?;
+    if !props.contains(FstProperties::ACCEPTOR) {
+        return Err(Error::PropertyViolation(
+            "LabelOccupancy: the FST must be an acceptor".to_string(),
+        )
+        .into());
+    }
+
+    let posteriors = posteriors(fst)?;
+    let mut occupancy: HashMap<Label, LogWeight> = HashMap::new();
+    for s in fst.states_range() {
+        let trs = fst.get_trs(s)?;
+        for (tr, post) in trs
+            .trs()
+            .iter()
+            .zip(posteriors.tr_posteriors[s as usize].iter())
+        {
+            if tr.ilabel == EPS_LABEL {
+                continue;
+            }
+            occupancy
+                .entry(tr.ilabel)
+                .or_insert_with(LogWeight::zero)
+                .plus_assign(post)?;
+        }
+    }
+    Ok(occupancy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    fn prob(w: &LogWeight) -> f32 {
+        (-*w.value()).exp()
+    }
+
+    // Two competing paths "1 2" (p=0.6) and "1 3" (p=0.4), sharing the first word.
+    fn lattice() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_tr(s0, Tr::new(1, 1, -(0.5f32.ln()), s1))?;
+        fst.add_tr(s0, Tr::new(1, 1, -(0.5f32.ln()), s2))?;
+        fst.add_tr(s1, Tr::new(2, 2, -(0.8f32.ln()), s3))?;
+        fst.add_tr(s1, Tr::new(3, 3, -(0.2f32.ln()), s3))?;
+        fst.add_tr(s2, Tr::new(2, 2, -(0.4f32.ln()), s3))?;
+        fst.add_tr(s2, Tr::new(3, 3, -(0.6f32.ln()), s3))?;
+        fst.set_final(s3, TropicalWeight::one())?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_posteriors() -> Result<()> {
+        let fst = lattice()?;
+        let posteriors = posteriors(&fst)?;
+
+        assert!(posteriors
+            .total_weight()
+            .approx_equal(LogWeight::one(), 1e-5));
+        for s in 0..4 {
+            let expected = if s == 1 || s == 2 { 0.5 } else { 1.0 };
+            assert!((prob(posteriors.state_posterior(s).unwrap()) - expected).abs() < 1e-5);
+        }
+        assert!((prob(posteriors.tr_posterior(1, 0).unwrap()) - 0.4).abs() < 1e-5);
+        assert!((prob(posteriors.tr_posterior(2, 1).unwrap()) - 0.3).abs() < 1e-5);
+        assert!(posteriors.tr_posterior(3, 0).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_posteriors_are_normalized() -> Result<()> {
+        let mut fst = lattice()?;
+        fst.set_final(3, 5.0)?;
+        let posteriors = posteriors(&fst)?;
+
+        assert!(posteriors
+            .total_weight()
+            .approx_equal(LogWeight::new(5.0), 1e-5));
+        assert!((prob(posteriors.tr_posterior(0, 0).unwrap()) - 0.5).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_posteriors_no_successful_path() -> Result<()> {
+        let mut fst = lattice()?;
+        fst.delete_final_weight(3)?;
+        assert!(posteriors(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_label_occupancy() -> Result<()> {
+        let fst = lattice()?;
+        let occupancy = label_occupancy(&fst)?;
+
+        assert_eq!(occupancy.len(), 3);
+        assert!((prob(&occupancy[&1]) - 1.0).abs() < 1e-5);
+        assert!((prob(&occupancy[&2]) - 0.6).abs() < 1e-5);
+        assert!((prob(&occupancy[&3]) - 0.4).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_label_occupancy_transducer() -> Result<()> {
+        let mut fst = lattice()?;
+        fst.add_tr(0, Tr::new(4, 5, 1.0, 3))?;
+        assert!(label_occupancy(&fst).is_err());
+        Ok(())
+    }
+}
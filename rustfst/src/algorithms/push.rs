@@ -34,8 +34,8 @@ bitflags! {
 /// Configuration for [`push_weights_with_config`].
 #[derive(Clone, Debug, Copy, PartialOrd, PartialEq)]
 pub struct PushWeightsConfig {
-    delta: f32,
-    remove_total_weight: bool,
+    pub delta: f32,
+    pub remove_total_weight: bool,
 }
 
 impl Default for PushWeightsConfig {
@@ -235,7 +235,7 @@ macro_rules! m_labels_pushing {
 /// Configuration for [`push_with_config`].
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq)]
 pub struct PushConfig {
-    delta: f32,
+    pub delta: f32,
 }
 
 impl Default for PushConfig {
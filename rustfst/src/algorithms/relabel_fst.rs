@@ -0,0 +1,237 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstCache, SimpleHashMapCache};
+use crate::algorithms::tr_mappers::RelabelMapper;
+use crate::algorithms::TrMapFst;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{Label, StateId, SymbolTable, TrsVec};
+
+type InnerFst<W, F, B, Cache> = TrMapFst<W, F, B, RelabelMapper, Cache>;
+
+/// Replaces input and/or output labels using pairs of labels of the form (old_ID, new_ID),
+/// omitted labels being identity-mapped. This version is a Delayed FST : the input FST is
+/// left untouched and the transitions of a state are only relabeled when the state is visited.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::algorithms::RelabelFst;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<IntegerWeight> = fst![2 => 3];
+/// let relabeled_fst: RelabelFst<_, VectorFst<_>, _> =
+///     RelabelFst::new(&fst, vec![(2, 5)], vec![(3, 4)])?;
+///
+/// let expected_fst: VectorFst<IntegerWeight> = fst![5 => 4];
+/// assert_eq!(relabeled_fst.compute::<VectorFst<_>>()?, expected_fst);
+/// # Ok(())
+/// # }
+/// ```
+pub struct RelabelFst<W, F, B, Cache = SimpleHashMapCache<W>>(InnerFst<W, F, B, Cache>)
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug;
+
+impl<W, F, B, Cache> RelabelFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    Cache: FstCache<W>,
+{
+    pub fn new<I, J>(fst: B, ipairs: I, opairs: J) -> Result<Self>
+    where
+        I: IntoIterator<Item = (Label, Label)>,
+        J: IntoIterator<Item = (Label, Label)>,
+        Cache: Default,
+    {
+        Self::new_with_cache(fst, ipairs, opairs, Cache::default())
+    }
+
+    pub fn new_with_cache<I, J>(fst: B, ipairs: I, opairs: J, fst_cache: Cache) -> Result<Self>
+    where
+        I: IntoIterator<Item = (Label, Label)>,
+        J: IntoIterator<Item = (Label, Label)>,
+    {
+        let mapper = RelabelMapper::new(ipairs, opairs)?;
+        Ok(RelabelFst(TrMapFst::new_with_cache(fst, mapper, fst_cache)))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+impl<W, F, B, Cache> CoreFst<W> for RelabelFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, Cache> StateIterator<'a> for RelabelFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerFst<W, F, B, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, Cache> FstIterator<'a, W> for RelabelFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerFst<W, F, B, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, Cache> Fst<W> for RelabelFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, Cache> Debug for RelabelFst<W, F, B, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::relabel_pairs;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::IntegerWeight;
+    use crate::Tr;
+
+    #[test]
+    fn test_relabel_fst() -> Result<()> {
+        let mut fst = VectorFst::<IntegerWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(3, 18, 10, 1))?;
+        fst.add_tr(0, Tr::new(2, 5, 10, 1))?;
+        fst.add_tr(0, Tr::new(5, 7, 18, 2))?;
+        fst.set_final(1, 31)?;
+        fst.set_final(2, 45)?;
+
+        let ipairs = vec![(3, 45), (5, 75)];
+        let opairs = vec![(18, 51), (5, 75), (7, 85)];
+
+        let mut static_fst = fst.clone();
+        relabel_pairs(&mut static_fst, ipairs.clone(), opairs.clone())?;
+
+        let lazy_fst: RelabelFst<_, VectorFst<_>, _> = RelabelFst::new(&fst, ipairs, opairs)?;
+        assert_eq!(lazy_fst.compute::<VectorFst<_>>()?, static_fst);
+        assert!(!lazy_fst
+            .properties()
+            .contains(FstProperties::I_LABEL_SORTED));
+        Ok(())
+    }
+
+    #[test]
+    fn test_relabel_fst_duplicated_pairs() -> Result<()> {
+        let fst: VectorFst<IntegerWeight> = VectorFst::new();
+        let res: Result<RelabelFst<_, VectorFst<_>, _>> =
+            RelabelFst::new(&fst, vec![(1, 2), (1, 3)], vec![]);
+        assert!(res.is_err());
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::algorithms::AlgorithmLimits;
 use crate::Label;
 
 /// This specifies what labels to output on the call or return transition.
@@ -44,3 +45,30 @@ impl ReplaceFstOptions {
         }
     }
 }
+
+/// Configuration for `replace`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ReplaceConfig {
+    /// Label of the root FST.
+    pub root: Label,
+    /// Whether to use epsilon labels on the call and return transitions.
+    pub epsilon_on_replace: bool,
+    /// Bounds on the size of the expanded FST.
+    pub limits: AlgorithmLimits,
+}
+
+impl ReplaceConfig {
+    /// Creates a config without limits.
+    pub fn new(root: Label, epsilon_on_replace: bool) -> Self {
+        Self {
+            root,
+            epsilon_on_replace,
+            limits: AlgorithmLimits::default(),
+        }
+    }
+
+    /// Sets the limits checked for each state of the expanded FST.
+    pub fn with_limits(self, limits: AlgorithmLimits) -> Self {
+        Self { limits, ..self }
+    }
+}
@@ -5,5 +5,6 @@ mod replace_static;
 pub(crate) mod state_table;
 pub(crate) mod utils;
 
+pub use config::ReplaceConfig;
 pub use replace_fst::ReplaceFst;
-pub use replace_static::replace;
+pub use replace_static::{replace, replace_with_config};
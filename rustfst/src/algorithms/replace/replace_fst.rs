@@ -5,7 +5,7 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::algorithms::lazy::{LazyFst, SimpleHashMapCache};
-use crate::algorithms::replace::config::ReplaceFstOptions;
+use crate::algorithms::replace::config::{ReplaceConfig, ReplaceFstOptions};
 use crate::algorithms::replace::replace_fst_op::ReplaceFstOp;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
@@ -26,18 +26,22 @@ where
     B: Borrow<F>,
 {
     pub fn new(fst_list: Vec<(Label, B)>, root: Label, epsilon_on_replace: bool) -> Result<Self> {
+        Self::new_with_config(fst_list, ReplaceConfig::new(root, epsilon_on_replace))
+    }
+
+    pub fn new_with_config(fst_list: Vec<(Label, B)>, config: ReplaceConfig) -> Result<Self> {
         let mut isymt = None;
         let mut osymt = None;
         if let Some(first_elt) = fst_list.first() {
             isymt = first_elt.1.borrow().input_symbols().cloned();
             osymt = first_elt.1.borrow().output_symbols().cloned();
         }
-        let opts = ReplaceFstOptions::new(root, epsilon_on_replace);
+        let opts = ReplaceFstOptions::new(config.root, config.epsilon_on_replace);
         let fst_op = ReplaceFstOp::new(fst_list, opts)?;
         let fst_cache = SimpleHashMapCache::default();
-        Ok(ReplaceFst(LazyFst::from_op_and_cache(
-            fst_op, fst_cache, isymt, osymt,
-        )))
+        Ok(ReplaceFst(
+            LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt).with_limits(config.limits),
+        ))
     }
 
     /// Turns the Lazy FST into a static one.
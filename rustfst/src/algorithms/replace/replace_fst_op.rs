@@ -13,6 +13,7 @@ use crate::algorithms::replace::state_table::{
     ReplaceStackPrefix, ReplaceStateTable, ReplaceStateTuple,
 };
 use crate::algorithms::replace::utils::{epsilon_on_input, epsilon_on_output};
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::Fst;
 use crate::semirings::Semiring;
@@ -31,6 +32,7 @@ pub struct ReplaceFstOp<W: Semiring, F: Fst<W>, B: Borrow<F>> {
     properties: FstProperties,
     fst_type: PhantomData<F>,
     w: PhantomData<W>,
+    limits: AlgorithmLimits,
 }
 
 impl<W: Semiring, F: Fst<W>, B: Borrow<F>> std::fmt::Debug for ReplaceFstOp<W, F, B> {
@@ -74,6 +76,7 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> FstOp<W> for ReplaceFstOp<W, F, B> {
     }
 
     fn compute_trs(&self, state: StateId) -> Result<TrsVec<W>> {
+        self.limits.check_interrupted()?;
         let tuple = self.state_table.tuple_table.find_tuple(state);
         let mut trs = vec![];
         if let Some(fst_state) = tuple.fst_state {
@@ -115,6 +118,10 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> FstOp<W> for ReplaceFstOp<W, F, B> {
     fn properties(&self) -> FstProperties {
         self.properties
     }
+
+    fn set_limits(&mut self, limits: AlgorithmLimits) {
+        self.limits = limits;
+    }
 }
 
 fn replace_properties<W, F, B>(
@@ -222,6 +229,7 @@ impl<W: Semiring, F: Fst<W>, B: Borrow<F>> ReplaceFstOp<W, F, B> {
             properties,
             fst_type: PhantomData,
             w: PhantomData,
+            limits: AlgorithmLimits::default(),
         };
 
         if let Some(v) = replace_fst_impl.call_output_label_ {
@@ -2,7 +2,7 @@ use std::borrow::Borrow;
 
 use anyhow::Result;
 
-use crate::algorithms::replace::ReplaceFst;
+use crate::algorithms::replace::{ReplaceConfig, ReplaceFst};
 use crate::fst_traits::{AllocableFst, Fst, MutableFst};
 use crate::semirings::Semiring;
 use crate::Label;
@@ -53,6 +53,45 @@ where
     F2: MutableFst<W> + AllocableFst<W>,
     B: Borrow<F1>,
 {
-    let fst = ReplaceFst::new(fst_list, root, epsilon_on_replace)?;
+    replace_with_config(fst_list, ReplaceConfig::new(root, epsilon_on_replace))
+}
+
+/// Same as `replace` but the expansion can be bounded with `AlgorithmLimits`, which is useful
+/// as recursive replacements can lead to an infinite number of states.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::replace::{replace_with_config, ReplaceConfig};
+/// # use rustfst::algorithms::{AlgorithmLimits, LimitExceeded};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// // Non-terminal 10 calls itself: the expansion never ends.
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(2);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 10, 1.0, 1))?;
+/// fst.set_final(1, 0.0)?;
+///
+/// let config = ReplaceConfig::new(10, false).with_limits(AlgorithmLimits::new().with_max_states(100));
+/// let res = replace_with_config::<_, _, VectorFst<_>, _>(vec![(10, fst)], config);
+/// assert_eq!(
+///     res.unwrap_err().downcast_ref::<LimitExceeded>(),
+///     Some(&LimitExceeded::MaxStates(100))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn replace_with_config<W, F1, F2, B>(
+    fst_list: Vec<(Label, B)>,
+    config: ReplaceConfig,
+) -> Result<F2>
+where
+    F1: Fst<W>,
+    W: Semiring,
+    F2: MutableFst<W> + AllocableFst<W>,
+    B: Borrow<F1>,
+{
+    let fst = ReplaceFst::new_with_config(fst_list, config)?;
     fst.compute()
 }
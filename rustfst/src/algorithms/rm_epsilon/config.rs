@@ -1,9 +1,52 @@
 use crate::algorithms::shortest_distance::ShortestDistanceInternalConfig;
 use crate::algorithms::tr_filters::EpsilonTrFilter;
-use crate::algorithms::Queue;
+use crate::algorithms::{AlgorithmLimits, Queue};
 use crate::semirings::Semiring;
 use crate::{StateId, KSHORTESTDELTA};
 
+/// Configuration for epsilon removal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RmEpsilonConfig {
+    /// Whether to trim the output FST.
+    pub connect: bool,
+    /// Delta used for the shortest distance computation.
+    pub delta: f32,
+    /// Bounds on the number of transitions added while removing epsilons.
+    pub limits: AlgorithmLimits,
+}
+
+impl RmEpsilonConfig {
+    /// Creates a config without limits.
+    pub fn new(connect: bool, delta: f32) -> Self {
+        Self {
+            connect,
+            delta,
+            limits: AlgorithmLimits::default(),
+        }
+    }
+
+    /// Sets whether to trim the output FST.
+    pub fn with_connect(self, connect: bool) -> Self {
+        Self { connect, ..self }
+    }
+
+    /// Sets the delta used for the shortest distance computation.
+    pub fn with_delta(self, delta: f32) -> Self {
+        Self { delta, ..self }
+    }
+
+    /// Sets the limits checked for each state whose epsilons are removed.
+    pub fn with_limits(self, limits: AlgorithmLimits) -> Self {
+        Self { limits, ..self }
+    }
+}
+
+impl Default for RmEpsilonConfig {
+    fn default() -> Self {
+        Self::new(true, KSHORTESTDELTA)
+    }
+}
+
 pub(crate) struct RmEpsilonInternalConfig<W: Semiring, Q: Queue> {
     pub(crate) sd_opts: ShortestDistanceInternalConfig<W, Q, EpsilonTrFilter>,
     pub connect: bool,
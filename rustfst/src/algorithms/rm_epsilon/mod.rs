@@ -5,8 +5,9 @@ mod rm_epsilon_op;
 mod rm_epsilon_state;
 mod rm_epsilon_static;
 
+pub use config::RmEpsilonConfig;
 pub(crate) use config::RmEpsilonInternalConfig;
 use element::Element;
 pub use rm_epsilon_fst::RmEpsilonFst;
 use rm_epsilon_state::RmEpsilonState;
-pub use rm_epsilon_static::rm_epsilon;
+pub use rm_epsilon_static::{rm_epsilon, rm_epsilon_with_config};
@@ -6,6 +6,7 @@ use anyhow::Result;
 
 use crate::algorithms::lazy::{LazyFst2, SimpleHashMapCache};
 use crate::algorithms::rm_epsilon::rm_epsilon_op::RmEpsilonOp;
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, Fst, FstIterator, MutableFst, StateIterator};
 use crate::{Semiring, StateId, SymbolTable, TrsVec};
@@ -151,6 +152,11 @@ where
         Ok(RmEpsilonFst(lazy_fst))
     }
 
+    /// Bounds the number of states and transitions computed, see `AlgorithmLimits`.
+    pub fn with_limits(self, limits: AlgorithmLimits) -> Self {
+        RmEpsilonFst(self.0.with_limits(limits))
+    }
+
     /// Turns the Lazy FST into a static one.
     pub fn compute<F2: MutableFst<W>>(&self) -> Result<F2> {
         self.0.compute()
@@ -5,6 +5,7 @@ use anyhow::Result;
 use crate::algorithms::lazy::FstOp2;
 use crate::algorithms::queues::FifoQueue;
 use crate::algorithms::rm_epsilon::{RmEpsilonInternalConfig, RmEpsilonState};
+use crate::algorithms::AlgorithmLimits;
 use crate::fst_properties::mutable_properties::rmepsilon_properties;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
@@ -21,6 +22,7 @@ pub struct RmEpsilonOp<W: Semiring, F: MutableFst<W>, B: Borrow<F>> {
     properties: FstProperties,
     ghost: PhantomData<F>,
     fst: B,
+    limits: AlgorithmLimits,
 }
 
 impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> std::fmt::Debug for RmEpsilonOp<W, F, B> {
@@ -50,6 +52,7 @@ impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> RmEpsilonOp<W, F, B> {
             )),
             fst,
             ghost: PhantomData,
+            limits: AlgorithmLimits::default(),
         }
     }
 }
@@ -60,6 +63,7 @@ impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> FstOp2<W> for RmEpsilonOp<W, F
     }
 
     fn compute_trs_and_final_weight(&self, state: StateId) -> Result<(TrsVec<W>, Option<W>)> {
+        self.limits.check_interrupted()?;
         let (trs, final_weight) = self
             .rmeps_state
             .borrow_mut()
@@ -80,4 +84,8 @@ impl<W: Semiring, F: MutableFst<W>, B: Borrow<F>> FstOp2<W> for RmEpsilonOp<W, F
     fn properties(&self) -> FstProperties {
         self.properties
     }
+
+    fn set_limits(&mut self, limits: AlgorithmLimits) {
+        self.limits = limits;
+    }
 }
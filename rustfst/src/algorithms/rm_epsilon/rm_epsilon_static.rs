@@ -1,12 +1,13 @@
 use anyhow::Result;
 
 use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::limits::LimitsTracker;
 use crate::algorithms::queues::AutoQueue;
-use crate::algorithms::rm_epsilon::{RmEpsilonInternalConfig, RmEpsilonState};
+use crate::algorithms::rm_epsilon::{RmEpsilonConfig, RmEpsilonInternalConfig, RmEpsilonState};
 use crate::algorithms::top_sort::TopOrderVisitor;
 use crate::algorithms::tr_filters::EpsilonTrFilter;
 use crate::algorithms::visitors::SccVisitor;
-use crate::algorithms::Queue;
+use crate::algorithms::{AlgorithmLimits, Queue};
 use crate::fst_properties::mutable_properties::rmepsilon_properties;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
@@ -63,15 +64,53 @@ use crate::{StateId, Trs, EPS_LABEL};
 /// ![rmepsilon_out](https://raw.githubusercontent.com/Garvys/rustfst-images-doc/master/images/rmepsilon_out.svg?sanitize=true)
 ///
 pub fn rm_epsilon<W: Semiring, F: MutableFst<W>>(fst: &mut F) -> Result<()> {
+    rm_epsilon_with_config(fst, RmEpsilonConfig::default())
+}
+
+/// Removes epsilon-transitions from a transducer, with configurable trimming, delta and
+/// limits. When a limit is hit, an error is returned and the FST is left partially
+/// processed.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::rm_epsilon::{rm_epsilon_with_config, RmEpsilonConfig};
+/// # use rustfst::algorithms::{AlgorithmLimits, LimitExceeded};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(EPS_LABEL, EPS_LABEL, 1.0, 1))?;
+/// fst.add_tr(0, Tr::new(1, 1, 1.0, 2))?;
+/// fst.add_tr(1, Tr::new(2, 2, 1.0, 2))?;
+/// fst.set_final(2, 0.0)?;
+///
+/// let config = RmEpsilonConfig::default().with_limits(AlgorithmLimits::new().with_max_trs(1));
+/// let err = rm_epsilon_with_config(&mut fst.clone(), config).unwrap_err();
+/// assert_eq!(err.downcast_ref::<LimitExceeded>(), Some(&LimitExceeded::MaxTrs(1)));
+///
+/// rm_epsilon_with_config(&mut fst, RmEpsilonConfig::default())?;
+/// assert_eq!(fst.num_trs(0)?, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn rm_epsilon_with_config<W: Semiring, F: MutableFst<W>>(
+    fst: &mut F,
+    config: RmEpsilonConfig,
+) -> Result<()> {
     let tr_filter = EpsilonTrFilter {};
     let queue = AutoQueue::new(fst, None, &tr_filter)?;
-    let opts = RmEpsilonInternalConfig::new_with_default(queue);
-    rm_epsilon_with_internal_config(fst, opts)
+    let opts = RmEpsilonInternalConfig::new(queue, config.connect, W::zero(), None, config.delta);
+    rm_epsilon_with_internal_config(fst, opts, config.limits)
 }
+
 pub(crate) fn rm_epsilon_with_internal_config<W: Semiring, F: MutableFst<W>, Q: Queue>(
     fst: &mut F,
     opts: RmEpsilonInternalConfig<W, Q>,
+    limits: AlgorithmLimits,
 ) -> Result<()> {
+    let limits = LimitsTracker::new(limits);
     let connect = opts.connect;
     let weight_threshold = opts.weight_threshold.clone();
     let state_threshold = opts.state_threshold;
@@ -144,6 +183,9 @@ pub(crate) fn rm_epsilon_with_internal_config<W: Semiring, F: MutableFst<W>, Q:
             continue;
         }
         let (trs, final_weight) = rmeps_state.expand::<F, _>(state, &*fst)?;
+        if let Some(limits) = &limits {
+            limits.state_expanded(state, &trs)?;
+        }
 
         unsafe {
             fst.pop_trs_unchecked(state);
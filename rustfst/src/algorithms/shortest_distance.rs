@@ -254,7 +254,7 @@ pub(crate) fn shortest_distance_with_internal_config<
 /// Configuration for shortest distance computation
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
 pub struct ShortestDistanceConfig {
-    delta: f32,
+    pub delta: f32,
 }
 
 impl Default for ShortestDistanceConfig {
@@ -371,3 +371,76 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{MinMaxWeight, RealWeight};
+    use crate::Tr;
+
+    #[test]
+    fn test_shortest_distance_min_max() -> Result<()> {
+        // The distance is the smallest capacity needed to reach each state.
+        let mut fst = VectorFst::<MinMaxWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 5.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 3.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 2.0, 3))?;
+        fst.add_tr(2, Tr::new(4, 4, 4.0, 3))?;
+        fst.add_tr(3, Tr::new(5, 5, 1.0, 0))?;
+        fst.set_final(3, MinMaxWeight::one())?;
+
+        let distance = shortest_distance(&fst, false)?;
+        assert_eq!(
+            distance,
+            vec![
+                MinMaxWeight::one(),
+                MinMaxWeight::new(5.0),
+                MinMaxWeight::new(3.0),
+                MinMaxWeight::new(4.0)
+            ]
+        );
+
+        let distance = shortest_distance(&fst, true)?;
+        assert_eq!(
+            distance,
+            vec![
+                MinMaxWeight::new(4.0),
+                MinMaxWeight::new(2.0),
+                MinMaxWeight::new(4.0),
+                MinMaxWeight::one()
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_distance_real() -> Result<()> {
+        let mut fst = VectorFst::<RealWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 2.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, -1.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 0.5, 1))?;
+        fst.add_tr(1, Tr::new(4, 4, 3.0, 3))?;
+        fst.add_tr(2, Tr::new(5, 5, 0.5, 3))?;
+        fst.set_final(3, 1.0)?;
+
+        // The self-loop on the state 1 multiplies its weight by 1 / (1 - 0.5).
+        let distance = shortest_distance(&fst, false)?;
+        let expected = [1.0, 4.0, -1.0, 11.5];
+        assert_eq!(distance.len(), expected.len());
+        for (d, e) in distance.iter().zip(expected.iter()) {
+            assert!(d.approx_equal(RealWeight::new(*e), 1e-3));
+        }
+
+        let distance = shortest_distance(&fst, true)?;
+        let expected = [11.5, 6.0, 0.5, 1.0];
+        for (d, e) in distance.iter().zip(expected.iter()) {
+            assert!(d.approx_equal(RealWeight::new(*e), 1e-3));
+        }
+        Ok(())
+    }
+}
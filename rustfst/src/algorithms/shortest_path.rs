@@ -516,3 +516,55 @@ where
     );
     Ok(ofst)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fst_traits::Fst;
+    use crate::semirings::MinMaxWeight;
+
+    fn bottleneck_fst() -> Result<VectorFst<MinMaxWeight>> {
+        // Two routes from 0 to 3 : the bottleneck of 0 -> 1 -> 3 is 5, the one
+        // of 0 -> 2 -> 3 is 4.
+        let mut fst = VectorFst::<MinMaxWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 5.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 3.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 2.0, 3))?;
+        fst.add_tr(2, Tr::new(4, 4, 4.0, 3))?;
+        fst.set_final(3, MinMaxWeight::one())?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_shortest_path_min_max() -> Result<()> {
+        let fst = bottleneck_fst()?;
+        let path: VectorFst<MinMaxWeight> = shortest_path(&fst)?;
+
+        let mut paths: Vec<_> = path.paths_iter().collect();
+        assert_eq!(paths.len(), 1);
+        let path = paths.pop().unwrap();
+        assert_eq!(path.ilabels, vec![2, 4]);
+        assert_eq!(path.weight, MinMaxWeight::new(4.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_path_min_max_nshortest() -> Result<()> {
+        let fst = bottleneck_fst()?;
+        let paths: VectorFst<MinMaxWeight> =
+            shortest_path_with_config(&fst, ShortestPathConfig::default().with_nshortest(2))?;
+
+        let mut paths: Vec<_> = paths.paths_iter().map(|p| (p.ilabels, p.weight)).collect();
+        paths.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            paths,
+            vec![
+                (vec![1, 3], MinMaxWeight::new(5.0)),
+                (vec![2, 4], MinMaxWeight::new(4.0))
+            ]
+        );
+        Ok(())
+    }
+}
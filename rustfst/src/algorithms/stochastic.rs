@@ -0,0 +1,238 @@
+use anyhow::Result;
+
+use crate::algorithms::{
+    reweight, shortest_distance_with_config, ReweightType, ShortestDistanceConfig,
+};
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::{DivideType, Semiring, WeaklyDivisibleSemiring};
+use crate::{StateId, Trs};
+
+/// Check whether an FST is stochastic, i.e whether at every state the ⊕-sum of the weights
+/// of the outgoing transitions and of the final weight is equal to `One()`, up to `delta`.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::is_stochastic;
+/// # use rustfst::KDELTA;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<ProbabilityWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_tr(s0, Tr::new(1, 1, 0.25, s1))?;
+/// fst.set_final(s0, 0.75)?;
+/// fst.set_final(s1, 1.0)?;
+/// assert!(is_stochastic(&fst, KDELTA)?);
+///
+/// fst.set_final(s0, 0.5)?;
+/// assert!(!is_stochastic(&fst, KDELTA)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn is_stochastic<W, F>(fst: &F, delta: f32) -> Result<bool>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+{
+    Ok(first_non_stochastic_state(fst, delta)?.is_none())
+}
+
+/// Returns the first state whose weights don't sum to `One()`, along with that sum.
+pub(crate) fn first_non_stochastic_state<W, F>(fst: &F, delta: f32) -> Result<Option<(StateId, W)>>
+where
+    W: Semiring,
+    F: ExpandedFst<W>,
+{
+    for s in fst.states_range() {
+        let sum = state_sum(fst, s)?;
+        if !sum.approx_equal(W::one(), delta) {
+            return Ok(Some((s, sum)));
+        }
+    }
+    Ok(None)
+}
+
+/// Normalize the weights of an FST so that it becomes stochastic and defines a probability
+/// distribution over its paths, e.g to turn counts into probabilities. Returns the total
+/// weight of the FST before normalization, i.e the mass that has been removed.
+///
+/// The weights are first pushed toward the initial state, which makes every state but the
+/// initial one stochastic, then the weights leaving each state are divided by their
+/// ⊕-sum. The semiring must be weakly divisible, e.g `LogWeight` or `ProbabilityWeight`.
+/// States without successful paths should be removed beforehand (e.g with `connect`):
+/// their weights are left untouched.
+///
+/// An error is returned if the FST has no successful path.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::{is_stochastic, normalize};
+/// # use rustfst::KDELTA;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// // Counts of the bigrams "a b" and "a c".
+/// let mut fst = VectorFst::<ProbabilityWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_tr(s0, Tr::new(1, 1, 1.0, s1))?;
+/// fst.add_tr(s1, Tr::new(2, 2, 3.0, s2))?;
+/// fst.add_tr(s1, Tr::new(3, 3, 1.0, s2))?;
+/// fst.set_final(s2, 1.0)?;
+///
+/// let total = normalize(&mut fst)?;
+/// assert_eq!(total, ProbabilityWeight::new(4.0));
+/// assert!(is_stochastic(&fst, KDELTA)?);
+/// assert_eq!(fst.get_trs(s1)?[0].weight, ProbabilityWeight::new(0.75));
+/// # Ok(())
+/// # }
+/// ```
+pub fn normalize<W, F>(fst: &mut F) -> Result<W>
+where
+    W: WeaklyDivisibleSemiring,
+    F: MutableFst<W>,
+{
+    normalize_with_config(fst, ShortestDistanceConfig::default())
+}
+
+/// Normalize the weights of an FST so that it becomes stochastic, with configurable delta
+/// for the shortest distance computation.
+pub fn normalize_with_config<W, F>(fst: &mut F, config: ShortestDistanceConfig) -> Result<W>
+where
+    W: WeaklyDivisibleSemiring,
+    F: MutableFst<W>,
+{
+    let start = match fst.start() {
+        Some(s) => s,
+        None => bail!("Normalize: the FST has no start state"),
+    };
+
+    // Pushing toward the initial state moves the total weight of the FST onto the
+    // transitions and final weight of the initial state.
+    let dist = shortest_distance_with_config(fst, true, config)?;
+    let total_weight = dist.get(start as usize).cloned().unwrap_or_else(W::zero);
+    if total_weight.is_zero() {
+        bail!("Normalize: the FST has no successful path");
+    }
+    reweight(fst, &dist, ReweightType::ReweightToInitial)?;
+
+    for s in fst.states_range() {
+        let sum = state_sum(fst, s)?;
+        if sum.is_zero() || sum.is_one() {
+            continue;
+        }
+
+        let mut trs = fst.tr_iter_mut(s)?;
+        for idx in 0..trs.len() {
+            let weight = trs
+                .get(idx)
+                .unwrap()
+                .weight
+                .divide(&sum, DivideType::DivideLeft)?;
+            trs.set_weight(idx, weight)?;
+        }
+        if let Some(final_weight) = fst.final_weight(s)? {
+            fst.set_final(s, final_weight.divide(&sum, DivideType::DivideLeft)?)?;
+        }
+    }
+
+    Ok(total_weight)
+}
+
+fn state_sum<W: Semiring, F: ExpandedFst<W>>(fst: &F, s: StateId) -> Result<W> {
+    let mut sum = fst.final_weight(s)?.unwrap_or_else(W::zero);
+    for tr in fst.get_trs(s)?.trs() {
+        sum.plus_assign(&tr.weight)?;
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{entropy, shortest_distance};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::{LogWeight, ProbabilityWeight, TropicalWeight};
+    use crate::{Tr, KDELTA};
+
+    // Cyclic FST with weights c / 8 for the counts c.
+    fn counts_fst<W: Semiring<Type = f32>>(to_weight: fn(f32) -> f32) -> Result<VectorFst<W>> {
+        let mut fst = VectorFst::<W>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, W::new(to_weight(2.0 / 8.0)), 1))?;
+        fst.add_tr(0, Tr::new(2, 2, W::new(to_weight(6.0 / 8.0)), 2))?;
+        fst.add_tr(1, Tr::new(3, 3, W::new(to_weight(1.0 / 8.0)), 1))?;
+        fst.add_tr(1, Tr::new(4, 4, W::new(to_weight(2.0 / 8.0)), 2))?;
+        fst.set_final(2, W::new(to_weight(1.0 / 8.0)))?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_normalize_probability() -> Result<()> {
+        let mut fst = counts_fst::<ProbabilityWeight>(|p| p)?;
+        assert!(!is_stochastic(&fst, KDELTA)?);
+        let ref_distance = shortest_distance(&fst, true)?;
+
+        let total = normalize(&mut fst)?;
+        assert!(is_stochastic(&fst, KDELTA)?);
+        assert!(total.approx_equal(ref_distance[0], KDELTA));
+
+        // The self-loop keeps its probability, the exit transition takes the remaining mass.
+        let trs = fst.get_trs(1)?;
+        assert!(trs[0]
+            .weight
+            .approx_equal(ProbabilityWeight::new(1.0 / 8.0), KDELTA));
+        assert!(trs[1]
+            .weight
+            .approx_equal(ProbabilityWeight::new(7.0 / 8.0), KDELTA));
+        assert_eq!(fst.final_weight(2)?, Some(ProbabilityWeight::one()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_log() -> Result<()> {
+        let mut fst = counts_fst::<LogWeight>(|p| -p.ln())?;
+        let ref_distance = shortest_distance(&fst, true)?;
+
+        let total = normalize(&mut fst)?;
+        assert!(is_stochastic(&fst, KDELTA)?);
+        assert!(total.approx_equal(ref_distance[0], KDELTA));
+        assert!(entropy(&fst).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_stochastic_tropical() -> Result<()> {
+        // In the tropical semiring, the ⊕-sum is the min.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 0.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 3.0, 1))?;
+        fst.set_final(1, 0.0)?;
+        assert!(is_stochastic(&fst, KDELTA)?);
+
+        fst.set_final(1, 1.0)?;
+        assert!(!is_stochastic(&fst, KDELTA)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_errors() -> Result<()> {
+        let mut fst = VectorFst::<LogWeight>::new();
+        assert!(normalize(&mut fst).is_err());
+
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        assert!(normalize(&mut fst).is_err());
+        assert_eq!(fst.final_weight(1)?, None);
+        Ok(())
+    }
+}
@@ -24,7 +24,7 @@ pub struct FinalTr<W: Semiring> {
 }
 
 /// Determines how final weights are mapped.
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MapFinalAction {
     /// A final weight is mapped into a final weight. An error is raised if this
     /// is not possible.
@@ -0,0 +1,525 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstCache, FstOp, LazyFst, SimpleHashMapCache, StateTable};
+use crate::algorithms::{FinalTr, MapFinalAction, TrMapper};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, Tr, Trs, TrsVec, EPS_LABEL};
+
+/// Operation behind `TrMapFst`. When the mapper may require a super-final state, the
+/// states of the input FST are mapped through a state table in which `None` is the
+/// super-final state. Otherwise the state ids are left untouched.
+pub struct TrMapFstOp<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    fst: B,
+    mapper: M,
+    final_action: MapFinalAction,
+    state_table: Option<StateTable<Option<StateId>>>,
+    properties: FstProperties,
+    ghost: PhantomData<(W, F)>,
+}
+
+impl<W, F, B, M> Debug for TrMapFstOp<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: TrMapper<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrMapFstOp")
+            .field("fst", &self.fst)
+            .field("state_table", &self.state_table)
+            .field("properties", &self.properties)
+            .finish()
+    }
+}
+
+impl<W, F, B, M> TrMapFstOp<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    M: TrMapper<W>,
+{
+    pub fn new(fst: B, mapper: M) -> Self {
+        let final_action = mapper.final_action();
+        let state_table = match final_action {
+            MapFinalAction::MapNoSuperfinal => None,
+            _ => Some(StateTable::new()),
+        };
+        let properties = mapper.properties(fst.borrow().properties());
+        Self {
+            fst,
+            mapper,
+            final_action,
+            state_table,
+            properties,
+            ghost: PhantomData,
+        }
+    }
+
+    fn map_state(&self, state: StateId) -> StateId {
+        match &self.state_table {
+            Some(state_table) => state_table.find_id(Some(state)),
+            None => state,
+        }
+    }
+
+    fn unmap_state(&self, id: StateId) -> Option<StateId> {
+        match &self.state_table {
+            Some(state_table) => state_table.find_tuple(id),
+            None => Some(id),
+        }
+    }
+
+    fn superfinal(&self) -> StateId {
+        // Only called when the final action allows a super-final state.
+        self.state_table.as_ref().unwrap().find_id(None)
+    }
+
+    fn final_tr(&self, state: StateId) -> Result<Option<FinalTr<W>>> {
+        match self.fst.borrow().final_weight(state)? {
+            Some(weight) => {
+                let mut final_tr = FinalTr {
+                    ilabel: EPS_LABEL,
+                    olabel: EPS_LABEL,
+                    weight,
+                };
+                self.mapper.final_tr_map(&mut final_tr)?;
+                Ok(Some(final_tr))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<W, F, B, M> FstOp<W> for TrMapFstOp<W, F, B, M>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: TrMapper<W>,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.borrow().start().map(|s| self.map_state(s)))
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<W>> {
+        let state = match self.unmap_state(id) {
+            Some(s) => s,
+            None => return Ok(TrsVec::default()),
+        };
+        let itrs = self.fst.borrow().get_trs(state)?;
+        let mut trs = Vec::with_capacity(itrs.len());
+        for tr in itrs.trs() {
+            let mut new_tr = tr.clone();
+            self.mapper.tr_map(&mut new_tr)?;
+            new_tr.nextstate = self.map_state(tr.nextstate);
+            trs.push(new_tr);
+        }
+        if let Some(final_tr) = self.final_tr(state)? {
+            let has_labels = final_tr.ilabel != EPS_LABEL || final_tr.olabel != EPS_LABEL;
+            let needs_superfinal = match self.final_action {
+                MapFinalAction::MapNoSuperfinal => false,
+                MapFinalAction::MapAllowSuperfinal => has_labels,
+                MapFinalAction::MapRequireSuperfinal => has_labels || !final_tr.weight.is_zero(),
+            };
+            if needs_superfinal {
+                trs.push(Tr::new(
+                    final_tr.ilabel,
+                    final_tr.olabel,
+                    final_tr.weight,
+                    self.superfinal(),
+                ));
+            }
+        }
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<W>> {
+        let state = match self.unmap_state(id) {
+            Some(s) => s,
+            None => return Ok(Some(W::one())),
+        };
+        let final_tr = match self.final_tr(state)? {
+            Some(final_tr) => final_tr,
+            None => return Ok(None),
+        };
+        let has_labels = final_tr.ilabel != EPS_LABEL || final_tr.olabel != EPS_LABEL;
+        match self.final_action {
+            MapFinalAction::MapNoSuperfinal => {
+                if has_labels {
+                    bail!("TrMapFst: Non-zero tr labels for superfinal tr")
+                }
+                Ok(Some(final_tr.weight))
+            }
+            MapFinalAction::MapAllowSuperfinal => {
+                if has_labels {
+                    Ok(None)
+                } else {
+                    Ok(Some(final_tr.weight))
+                }
+            }
+            MapFinalAction::MapRequireSuperfinal => Ok(None),
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
+
+type InnerLazyFst<W, F, B, M, Cache> = LazyFst<W, TrMapFstOp<W, F, B, M>, Cache>;
+
+/// Maps every transition of an FST using a `TrMapper` object. This version is a
+/// Delayed FST : the input FST is left untouched and the transitions of a state are only
+/// mapped when the state is visited.
+///
+/// When the mapper requires a super-final state, it is only created once it is reached.
+/// `num_trs`, `num_input_epsilons` and `num_output_epsilons` expand the state if needed, so
+/// that the FST can be used as an input of other delayed algorithms like `ComposeFst`.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::tr_mappers::TimesMapper;
+/// # use rustfst::algorithms::TrMapFst;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(2);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+/// fst.set_final(1, 0.5)?;
+///
+/// let mapped_fst: TrMapFst<_, VectorFst<_>, _, _> =
+///     TrMapFst::new(&fst, TimesMapper::new(2.0));
+///
+/// assert_eq!(mapped_fst.get_trs(0)?.trs()[0].weight, TropicalWeight::new(3.0));
+/// assert_eq!(mapped_fst.final_weight(1)?, Some(TropicalWeight::new(2.5)));
+/// # Ok(())
+/// # }
+/// ```
+pub struct TrMapFst<W, F, B, M, Cache = SimpleHashMapCache<W>>(InnerLazyFst<W, F, B, M, Cache>)
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: TrMapper<W>;
+
+impl<W, F, B, M, Cache> TrMapFst<W, F, B, M, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: TrMapper<W>,
+    Cache: FstCache<W>,
+{
+    pub fn new(fst: B, mapper: M) -> Self
+    where
+        Cache: Default,
+    {
+        Self::new_with_cache(fst, mapper, Cache::default())
+    }
+
+    pub fn new_with_cache(fst: B, mapper: M, fst_cache: Cache) -> Self {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let fst_op = TrMapFstOp::new(fst, mapper);
+        TrMapFst(LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+impl<W, F, B, M, Cache> CoreFst<W> for TrMapFst<W, F, B, M, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: TrMapper<W>,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.expand_state(s)?;
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        // The cache only knows the number of transitions of expanded states.
+        self.0.get_trs_unchecked(s).len()
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.expand_state(state)?;
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.expand_state(state)?;
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, M, Cache> StateIterator<'a> for TrMapFst<W, F, B, M, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    M: TrMapper<W> + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerLazyFst<W, F, B, M, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, M, Cache> FstIterator<'a, W> for TrMapFst<W, F, B, M, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    M: TrMapper<W> + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerLazyFst<W, F, B, M, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, M, Cache> Fst<W> for TrMapFst<W, F, B, M, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    M: TrMapper<W> + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, M, Cache> Debug for TrMapFst<W, F, B, M, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    M: TrMapper<W>,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::isomorphic;
+    use crate::algorithms::tr_map;
+    use crate::algorithms::tr_mappers::{InputEpsilonMapper, TimesMapper};
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_traits::ExpandedFst;
+    use crate::semirings::TropicalWeight;
+    use crate::Label;
+
+    // Moves the final weights to transitions labeled with `label`.
+    struct FinalLabelMapper {
+        label: Label,
+        final_action: MapFinalAction,
+    }
+
+    impl TrMapper<TropicalWeight> for FinalLabelMapper {
+        fn tr_map(&self, _tr: &mut Tr<TropicalWeight>) -> Result<()> {
+            Ok(())
+        }
+
+        fn final_tr_map(&self, final_tr: &mut FinalTr<TropicalWeight>) -> Result<()> {
+            final_tr.olabel = self.label;
+            Ok(())
+        }
+
+        fn final_action(&self) -> MapFinalAction {
+            self.final_action
+        }
+
+        fn properties(&self, inprops: FstProperties) -> FstProperties {
+            inprops & FstProperties::weight_invariant_properties()
+        }
+    }
+
+    fn fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(3, 4, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(5, 6, 0.5, 2))?;
+        fst.set_final(1, 1.0)?;
+        fst.set_final(2, 0.0)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_tr_map_fst_no_superfinal() -> Result<()> {
+        let fst = fst()?;
+        let mut static_fst = fst.clone();
+        tr_map(&mut static_fst, &InputEpsilonMapper {})?;
+
+        let lazy_fst: TrMapFst<_, VectorFst<_>, _, _> = TrMapFst::new(&fst, InputEpsilonMapper {});
+        let computed_fst: VectorFst<_> = lazy_fst.compute()?;
+        assert_eq!(computed_fst, static_fst);
+        assert_eq!(lazy_fst.properties(), static_fst.properties());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_map_fst_allow_superfinal() -> Result<()> {
+        let fst = fst()?;
+        let mapper = FinalLabelMapper {
+            label: 7,
+            final_action: MapFinalAction::MapAllowSuperfinal,
+        };
+        let mut static_fst = fst.clone();
+        tr_map(&mut static_fst, &mapper)?;
+
+        let lazy_fst: TrMapFst<_, VectorFst<_>, _, _> = TrMapFst::new(fst, mapper);
+        let computed_fst: VectorFst<_> = lazy_fst.compute()?;
+        assert_eq!(computed_fst.num_states(), 4);
+        assert!(isomorphic(&computed_fst, &static_fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_map_fst_require_superfinal() -> Result<()> {
+        let fst = fst()?;
+        let mapper = FinalLabelMapper {
+            label: EPS_LABEL,
+            final_action: MapFinalAction::MapRequireSuperfinal,
+        };
+        let mut static_fst = fst.clone();
+        tr_map(&mut static_fst, &mapper)?;
+
+        let lazy_fst: TrMapFst<_, VectorFst<_>, _, _> = TrMapFst::new(fst, mapper);
+        let computed_fst: VectorFst<_> = lazy_fst.compute()?;
+        let final_states: Vec<_> = computed_fst
+            .states_iter()
+            .filter(|s| computed_fst.is_final(*s).unwrap())
+            .collect();
+        assert_eq!(final_states.len(), 1);
+        assert!(isomorphic(&computed_fst, &static_fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_map_fst_no_superfinal_with_labels() -> Result<()> {
+        let mapper = FinalLabelMapper {
+            label: 7,
+            final_action: MapFinalAction::MapNoSuperfinal,
+        };
+        let lazy_fst: TrMapFst<_, VectorFst<_>, _, _> = TrMapFst::new(fst()?, mapper);
+        assert!(lazy_fst.final_weight(1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_map_fst_num_trs_unchecked() -> Result<()> {
+        let lazy_fst: TrMapFst<_, VectorFst<_>, _, _> =
+            TrMapFst::new(fst()?, TimesMapper::new(1.0));
+        assert_eq!(unsafe { lazy_fst.num_trs_unchecked(0) }, 2);
+        assert_eq!(unsafe { lazy_fst.num_trs_unchecked(1) }, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_map_fst_is_lazy() -> Result<()> {
+        let fst: Arc<ConstFst<TropicalWeight>> = Arc::new(fst()?.into());
+        let cache = Arc::new(SimpleHashMapCache::default());
+        let lazy_fst: TrMapFst<_, Arc<ConstFst<_>>, _, _, _> =
+            TrMapFst::new_with_cache(Arc::clone(&fst), TimesMapper::new(1.0), Arc::clone(&cache));
+        assert_eq!(
+            lazy_fst.get_trs(1)?.trs()[0].weight,
+            TropicalWeight::new(1.5)
+        );
+        assert_eq!(cache.len_trs(), 1);
+
+        // Expands the state.
+        assert_eq!(lazy_fst.num_trs(0)?, 2);
+        assert_eq!(lazy_fst.num_input_epsilons(0)?, 0);
+        assert_eq!(cache.len_trs(), 2);
+
+        let computed_fst: VectorFst<_> = lazy_fst.compute()?;
+        assert_eq!(cache.len_trs(), 3);
+        assert_eq!(
+            computed_fst.final_weight(1)?,
+            Some(TropicalWeight::new(2.0))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,343 @@
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::lazy::{FstCache, FstOp, LazyFst, SimpleHashMapCache};
+use crate::algorithms::tr_compares::TrCompare;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{AllocableFst, CoreFst, Fst, FstIterator, MutableFst, StateIterator};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, Trs, TrsVec};
+
+/// Operation behind `TrSortFst`.
+pub struct TrSortFstOp<W, F, B, C> {
+    fst: B,
+    properties: FstProperties,
+    ghost: PhantomData<(W, F, C)>,
+}
+
+impl<W, F, B: Debug, C> Debug for TrSortFstOp<W, F, B, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrSortFstOp")
+            .field("fst", &self.fst)
+            .field("properties", &self.properties)
+            .finish()
+    }
+}
+
+impl<W, F, B, C> TrSortFstOp<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F>,
+    C: TrCompare,
+{
+    pub fn new(fst: B) -> Self {
+        let properties = C::properties(fst.borrow().properties());
+        Self {
+            fst,
+            properties,
+            ghost: PhantomData,
+        }
+    }
+}
+
+impl<W, F, B, C> FstOp<W> for TrSortFstOp<W, F, B, C>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    C: TrCompare,
+{
+    fn compute_start(&self) -> Result<Option<StateId>> {
+        Ok(self.fst.borrow().start())
+    }
+
+    fn compute_trs(&self, id: StateId) -> Result<TrsVec<W>> {
+        let mut trs = self.fst.borrow().get_trs(id)?.trs().to_vec();
+        trs.sort_by(C::compare);
+        Ok(TrsVec(Arc::new(trs)))
+    }
+
+    fn compute_final_weight(&self, id: StateId) -> Result<Option<W>> {
+        self.fst.borrow().final_weight(id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+}
+
+type InnerFst<W, F, B, C, Cache> = LazyFst<W, TrSortFstOp<W, F, B, C>, Cache>;
+
+/// Sorts the transitions leaving each state of an FST using a compare function.
+/// This version is a Delayed FST : the input FST is left untouched and the transitions
+/// of a state are only sorted when the state is visited.
+///
+/// `num_trs`, `num_input_epsilons` and `num_output_epsilons` expand the state if needed, so
+/// that the FST can be used as an input of other delayed algorithms like `ComposeFst`.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::algorithms::tr_compares::OLabelCompare;
+/// # use rustfst::algorithms::TrSortFst;
+/// # use rustfst::fst_properties::FstProperties;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(2);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 3, 1.0, 1))?;
+/// fst.add_tr(0, Tr::new(2, 2, 1.0, 1))?;
+/// fst.set_final(1, 0.0)?;
+///
+/// let sorted_fst: TrSortFst<_, VectorFst<_>, _, _> = TrSortFst::new(&fst, OLabelCompare {});
+///
+/// let olabels: Vec<_> = sorted_fst.get_trs(0)?.trs().iter().map(|tr| tr.olabel).collect();
+/// assert_eq!(olabels, vec![2, 3]);
+/// assert!(sorted_fst.properties().contains(FstProperties::O_LABEL_SORTED));
+/// # Ok(())
+/// # }
+/// ```
+pub struct TrSortFst<W, F, B, C, Cache = SimpleHashMapCache<W>>(InnerFst<W, F, B, C, Cache>)
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    C: TrCompare;
+
+impl<W, F, B, C, Cache> TrSortFst<W, F, B, C, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    C: TrCompare,
+    Cache: FstCache<W>,
+{
+    // The compare function could be passed only with the generic parameters but it seems less intuitive.
+    pub fn new(fst: B, _comp: C) -> Self
+    where
+        Cache: Default,
+    {
+        Self::new_with_cache(fst, Cache::default())
+    }
+
+    pub fn new_with_cache(fst: B, fst_cache: Cache) -> Self {
+        let isymt = fst.borrow().input_symbols().cloned();
+        let osymt = fst.borrow().output_symbols().cloned();
+        let fst_op = TrSortFstOp::new(fst);
+        TrSortFst(LazyFst::from_op_and_cache(fst_op, fst_cache, isymt, osymt))
+    }
+
+    /// Turns the Lazy FST into a static one.
+    pub fn compute<F2: MutableFst<W> + AllocableFst<W>>(&self) -> Result<F2> {
+        self.0.compute()
+    }
+}
+
+impl<W, F, B, C, Cache> CoreFst<W> for TrSortFst<W, F, B, C, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    C: TrCompare,
+    Cache: FstCache<W>,
+{
+    type TRS = TrsVec<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.0.final_weight(state_id)
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.0.expand_state(s)?;
+        self.0.num_trs(s)
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        // The cache only knows the number of transitions of expanded states.
+        self.0.get_trs_unchecked(s).len()
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.0.get_trs(state_id)
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.expand_state(state)?;
+        self.0.num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.expand_state(state)?;
+        self.0.num_output_epsilons(state)
+    }
+}
+
+impl<'a, W, F, B, C, Cache> StateIterator<'a> for TrSortFst<W, F, B, C, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    C: TrCompare + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type Iter = <InnerFst<W, F, B, C, Cache> as StateIterator<'a>>::Iter;
+
+    fn states_iter(&'a self) -> Self::Iter {
+        self.0.states_iter()
+    }
+}
+
+impl<'a, W, F, B, C, Cache> FstIterator<'a, W> for TrSortFst<W, F, B, C, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'a,
+    B: Borrow<F> + Debug + 'a,
+    C: TrCompare + 'a,
+    Cache: FstCache<W> + 'a,
+{
+    type FstIter = <InnerFst<W, F, B, C, Cache> as FstIterator<'a, W>>::FstIter;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        self.0.fst_iter()
+    }
+}
+
+impl<W, F, B, C, Cache> Fst<W> for TrSortFst<W, F, B, C, Cache>
+where
+    W: Semiring,
+    F: Fst<W> + 'static,
+    B: Borrow<F> + Debug + 'static,
+    C: TrCompare + 'static,
+    Cache: FstCache<W> + 'static,
+{
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.take_output_symbols()
+    }
+}
+
+impl<W, F, B, C, Cache> Debug for TrSortFst<W, F, B, C, Cache>
+where
+    W: Semiring,
+    F: Fst<W>,
+    B: Borrow<F> + Debug,
+    C: TrCompare,
+    Cache: FstCache<W>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::compose::{compose, ComposeFst};
+    use crate::algorithms::tr_compares::{ILabelCompare, OLabelCompare};
+    use crate::algorithms::{invert, tr_sort, InvertFst};
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    fn fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(3, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 2, 2.0, 2))?;
+        fst.add_tr(0, Tr::new(2, 1, 3.0, 2))?;
+        fst.add_tr(1, Tr::new(2, 3, 0.5, 2))?;
+        fst.set_final(2, 0.0)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_tr_sort_fst() -> Result<()> {
+        let fst = fst()?;
+        let mut static_fst = fst.clone();
+        tr_sort(&mut static_fst, ILabelCompare {});
+
+        let lazy_fst: TrSortFst<_, VectorFst<_>, _, _> = TrSortFst::new(&fst, ILabelCompare {});
+        assert_eq!(lazy_fst.properties(), static_fst.properties());
+        assert_eq!(lazy_fst.compute::<VectorFst<_>>()?, static_fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_sort_fst_num_trs_unchecked() -> Result<()> {
+        let fst = fst()?;
+        let lazy_fst: TrSortFst<_, VectorFst<_>, _, _> = TrSortFst::new(&fst, ILabelCompare {});
+        assert_eq!(unsafe { lazy_fst.num_trs_unchecked(0) }, 3);
+        assert_eq!(unsafe { lazy_fst.num_trs_unchecked(2) }, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tr_sort_fst_feeds_compose() -> Result<()> {
+        let fst1: ConstFst<TropicalWeight> = fst()?.into();
+        let mut fst2 = fst()?;
+        tr_sort(&mut fst2, ILabelCompare {});
+
+        // Static pipeline
+        let mut inverted = fst()?;
+        invert(&mut inverted);
+        tr_sort(&mut inverted, OLabelCompare {});
+        let static_fst: VectorFst<_> = compose(inverted, fst2.clone())?;
+
+        // Delayed pipeline : the ConstFst is never copied.
+        type Inverted =
+            InvertFst<TropicalWeight, ConstFst<TropicalWeight>, Arc<ConstFst<TropicalWeight>>>;
+        type Sorted = TrSortFst<TropicalWeight, Inverted, Inverted, OLabelCompare>;
+        let inverted: Inverted = InvertFst::new(Arc::new(fst1));
+        let sorted: Sorted = TrSortFst::new(inverted, OLabelCompare {});
+        let lazy_fst = ComposeFst::<_, Sorted, VectorFst<_>, _, _, _, _, _>::new_auto(
+            Arc::new(sorted),
+            Arc::new(fst2),
+        )?;
+        let computed_fst: VectorFst<_> = lazy_fst.compute()?;
+
+        assert_eq!(computed_fst, static_fst);
+        Ok(())
+    }
+}
@@ -1,2 +1,67 @@
+//! Depth-first traversal of FSTs and analyses built on top of it.
+//!
+//! A [`Visitor`] receives callbacks from [`dfs_visit`] when states are discovered and finished and
+//! when tree, back and forward/cross transitions are examined. Transitions can be restricted with a
+//! [`TrFilter`](crate::algorithms::tr_filters::TrFilter). [`SccVisitor`] and
+//! [`scc_decomposition`] compute the strongly connected components of an FST and which states
+//! are accessible and coaccessible, [`StateReachable`] and [`LabelReachable`] answer reachability
+//! queries for final states and labels.
+//!
+//! # Example
+//!
+//! Detecting whether an FST has a cycle made of epsilon transitions :
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use rustfst::prelude::*;
+//! # use rustfst::algorithms::tr_filters::{AnyTrFilter, EpsilonTrFilter};
+//! # use rustfst::algorithms::visitors::{dfs_visit, Visitor};
+//! struct CycleDetector {
+//!     cyclic: bool,
+//! }
+//!
+//! impl<'a, W: Semiring, F: Fst<W>> Visitor<'a, W, F> for CycleDetector {
+//!     fn init_visit(&mut self, _fst: &'a F) {}
+//!     fn init_state(&mut self, _s: StateId, _root: StateId) -> bool {
+//!         true
+//!     }
+//!     fn tree_tr(&mut self, _s: StateId, _tr: &Tr<W>) -> bool {
+//!         true
+//!     }
+//!     fn back_tr(&mut self, _s: StateId, _tr: &Tr<W>) -> bool {
+//!         // A back transition closes a cycle, the search can be stopped.
+//!         self.cyclic = true;
+//!         false
+//!     }
+//!     fn forward_or_cross_tr(&mut self, _s: StateId, _tr: &Tr<W>) -> bool {
+//!         true
+//!     }
+//!     fn finish_state(&mut self, _s: StateId, _parent: Option<StateId>, _tr: Option<&Tr<W>>) {}
+//!     fn finish_visit(&mut self) {}
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut fst = VectorFst::<TropicalWeight>::new();
+//! fst.add_states(2);
+//! fst.set_start(0)?;
+//! fst.emplace_tr(0, 0, 0, 1.0, 1)?;
+//! fst.emplace_tr(1, 1, 1, 1.0, 0)?;
+//!
+//! let mut visitor = CycleDetector { cyclic: false };
+//! dfs_visit(&fst, &mut visitor, &AnyTrFilter {}, false);
+//! assert!(visitor.cyclic);
+//!
+//! let mut visitor = CycleDetector { cyclic: false };
+//! dfs_visit(&fst, &mut visitor, &EpsilonTrFilter {}, false);
+//! assert!(!visitor.cyclic);
+//! # Ok(())
+//! # }
+//! ```
+
+mod scc_decomposition;
 mod scc_visitors;
-pub(crate) use scc_visitors::SccVisitor;
+
+pub use crate::algorithms::compose::{LabelReachable, StateReachable};
+pub use crate::algorithms::dfs_visit::{dfs_visit, Visitor};
+pub use scc_decomposition::{scc_decomposition, SccDecomposition};
+pub use scc_visitors::SccVisitor;
@@ -0,0 +1,231 @@
+use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::tr_filters::AnyTrFilter;
+use crate::algorithms::visitors::SccVisitor;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{StateId, Trs};
+
+/// Strongly connected components (SCCs) of an FST along with the accessibility and
+/// coaccessibility of its states. Computed by [`scc_decomposition`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SccDecomposition {
+    scc: Vec<Option<usize>>,
+    access: Vec<bool>,
+    coaccess: Vec<bool>,
+    properties: FstProperties,
+    scc_properties: Vec<FstProperties>,
+}
+
+/// Computes the strongly connected components of `fst`.
+///
+/// Component ids are in topological order : transitions between two components always go from
+/// a lower id to a higher id. If the FST has no start state, no state is visited and there is no
+/// component.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::prelude::*;
+/// # use rustfst::fst_properties::FstProperties;
+/// # use rustfst::algorithms::visitors::scc_decomposition;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(4);
+/// fst.set_start(0)?;
+/// fst.emplace_tr(0, 1, 1, 1.0, 1)?;
+/// fst.emplace_tr(1, 2, 2, 1.0, 0)?;
+/// fst.emplace_tr(1, 3, 3, 1.0, 2)?;
+/// fst.emplace_tr(3, 4, 4, 1.0, 2)?;
+/// fst.set_final(2, 1.0)?;
+///
+/// let sccs = scc_decomposition(&fst);
+/// assert_eq!(sccs.num_sccs(), 3);
+/// assert_eq!(sccs.scc(0), sccs.scc(1));
+/// assert!(sccs.scc(0) < sccs.scc(2));
+///
+/// // The component containing states 0 and 1 is a cycle.
+/// let scc_props = sccs.scc_properties(sccs.scc(0).unwrap()).unwrap();
+/// assert!(scc_props.contains(FstProperties::CYCLIC));
+///
+/// // State 3 can't be reached from the start state.
+/// assert!(!sccs.is_accessible(3));
+/// assert_eq!(sccs.dead_states(), vec![3]);
+/// assert!(sccs.properties().contains(FstProperties::NOT_ACCESSIBLE));
+/// # Ok(())
+/// # }
+/// ```
+pub fn scc_decomposition<W: Semiring, F: ExpandedFst<W>>(fst: &F) -> SccDecomposition {
+    let mut visitor = SccVisitor::new(fst, true, true);
+    dfs_visit(fst, &mut visitor, &AnyTrFilter {}, false);
+
+    let nscc = visitor.nscc as usize;
+    let scc: Vec<_> = visitor
+        .scc
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| if c >= 0 { Some(c as usize) } else { None })
+        .collect();
+    let access = visitor.access.unwrap_or_default();
+    let coaccess = visitor.coaccess;
+
+    let props = FstProperties::ACYCLIC | FstProperties::ACCESSIBLE | FstProperties::COACCESSIBLE;
+    let mut scc_properties = vec![props; nscc];
+    for (s, c) in scc.iter().enumerate() {
+        let c = match c {
+            Some(c) => *c,
+            None => continue,
+        };
+        let props = &mut scc_properties[c];
+        if !access[s] {
+            *props |= FstProperties::NOT_ACCESSIBLE;
+            *props &= !FstProperties::ACCESSIBLE;
+        }
+        if !coaccess[s] {
+            *props |= FstProperties::NOT_COACCESSIBLE;
+            *props &= !FstProperties::COACCESSIBLE;
+        }
+        let trs = unsafe { fst.get_trs_unchecked(s as StateId) };
+        if trs
+            .trs()
+            .iter()
+            .any(|tr| scc[tr.nextstate as usize] == Some(c))
+        {
+            *props |= FstProperties::CYCLIC;
+            *props &= !FstProperties::ACYCLIC;
+        }
+    }
+
+    SccDecomposition {
+        scc,
+        access,
+        coaccess,
+        properties: visitor.props,
+        scc_properties,
+    }
+}
+
+impl SccDecomposition {
+    /// Number of strongly connected components.
+    pub fn num_sccs(&self) -> usize {
+        self.scc_properties.len()
+    }
+
+    /// Id of the component containing `state`, `None` if it hasn't been visited.
+    pub fn scc(&self, state: StateId) -> Option<usize> {
+        self.scc.get(state as usize).copied().flatten()
+    }
+
+    /// States of each component, indexed by component id.
+    pub fn sccs(&self) -> Vec<Vec<StateId>> {
+        let mut sccs = vec![vec![]; self.num_sccs()];
+        for (s, c) in self.scc.iter().enumerate() {
+            if let Some(c) = c {
+                sccs[*c].push(s as StateId);
+            }
+        }
+        sccs
+    }
+
+    /// Properties of a component : `CYCLIC` if it contains a cycle, `ACCESSIBLE` if its states
+    /// can be reached from the start state and `COACCESSIBLE` if a final state can be reached
+    /// from its states, or their negations. `None` if there is no such component.
+    pub fn scc_properties(&self, scc: usize) -> Option<FstProperties> {
+        self.scc_properties.get(scc).copied()
+    }
+
+    /// Cyclicity, accessibility and coaccessibility properties of the whole FST.
+    pub fn properties(&self) -> FstProperties {
+        self.properties
+    }
+
+    /// Whether `state` can be reached from the start state.
+    pub fn is_accessible(&self, state: StateId) -> bool {
+        self.access.get(state as usize).copied().unwrap_or(false)
+    }
+
+    /// Whether a final state can be reached from `state`.
+    pub fn is_coaccessible(&self, state: StateId) -> bool {
+        self.coaccess.get(state as usize).copied().unwrap_or(false)
+    }
+
+    /// States that are not on any successful path, i.e. not accessible or not coaccessible.
+    /// These are the states removed by [`connect`](crate::algorithms::connect).
+    pub fn dead_states(&self) -> Vec<StateId> {
+        (0..self.access.len() as StateId)
+            .filter(|s| !self.is_accessible(*s) || !self.is_coaccessible(*s))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+    use anyhow::Result;
+
+    use super::*;
+    use crate::algorithms::{condense, connect};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, MutableFst, StateIterator};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_scc_decomposition_no_start_state() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.emplace_tr(0, 1, 1, 1.0, 1)?;
+        let sccs = scc_decomposition(&fst);
+        assert_eq!(sccs.num_sccs(), 0);
+        assert_eq!(sccs.scc(0), None);
+        assert_eq!(sccs.dead_states(), vec![0, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scc_decomposition_self_loop() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.emplace_tr(0, 1, 1, 1.0, 1)?;
+        fst.emplace_tr(1, 1, 1, 1.0, 1)?;
+        fst.set_final(1, 1.0)?;
+        let sccs = scc_decomposition(&fst);
+        assert_eq!(sccs.sccs(), vec![vec![0], vec![1]]);
+        assert!(sccs
+            .scc_properties(0)
+            .unwrap()
+            .contains(FstProperties::ACYCLIC | FstProperties::ACCESSIBLE));
+        assert!(sccs
+            .scc_properties(1)
+            .unwrap()
+            .contains(FstProperties::CYCLIC | FstProperties::COACCESSIBLE));
+        assert_eq!(sccs.scc_properties(2), None);
+        assert!(sccs.properties().contains(FstProperties::CYCLIC));
+        assert!(sccs.dead_states().is_empty());
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_scc_decomposition_proptest(fst in any::<VectorFst::<TropicalWeight>>()) {
+            let sccs = scc_decomposition(&fst);
+            let (scc, _): (_, VectorFst<TropicalWeight>) = condense(&fst).unwrap();
+            for (s, c) in scc.iter().enumerate() {
+                prop_assert_eq!(sccs.scc(s as StateId), Some(*c as usize));
+            }
+
+            // Component ids are in topological order.
+            for s in fst.states_iter() {
+                for tr in fst.get_trs(s).unwrap().trs() {
+                    prop_assert!(sccs.scc(s) <= sccs.scc(tr.nextstate));
+                }
+            }
+
+            let mut connected = fst.clone();
+            connect(&mut connected).unwrap();
+            let num_dead_states = sccs.dead_states().len();
+            prop_assert_eq!(connected.num_states() + num_dead_states, fst.num_states());
+        }
+    }
+}
@@ -7,10 +7,16 @@ use crate::semirings::Semiring;
 use crate::Tr;
 use crate::{StateId, NO_STATE_ID};
 
+/// Visitor computing the strongly connected components (SCCs) of an FST with Tarjan's
+/// algorithm, along with the accessibility and coaccessibility of its states.
+///
+/// It must be run with [`dfs_visit`](super::dfs_visit) without `access_only` for the results to
+/// cover all the states. [`scc_decomposition`](super::scc_decomposition) wraps it for the
+/// common case where all the transitions are followed.
 pub struct SccVisitor<'a, W: Semiring, F: Fst<W>> {
-    pub scc: Option<Vec<i32>>,
-    pub access: Option<Vec<bool>>,
-    pub coaccess: Vec<bool>,
+    pub(crate) scc: Option<Vec<i32>>,
+    pub(crate) access: Option<Vec<bool>>,
+    pub(crate) coaccess: Vec<bool>,
     start: StateId,
     fst: &'a F,
     nstates: usize,
@@ -18,12 +24,14 @@ pub struct SccVisitor<'a, W: Semiring, F: Fst<W>> {
     lowlink: Vec<i32>,
     onstack: Vec<bool>,
     scc_stack: Vec<StateId>,
-    pub nscc: i32,
-    pub props: FstProperties,
+    pub(crate) nscc: i32,
+    pub(crate) props: FstProperties,
     w: PhantomData<W>,
 }
 
 impl<'a, W: Semiring, F: 'a + ExpandedFst<W>> SccVisitor<'a, W, F> {
+    /// Creates a visitor for `fst`. The component ids and the accessibility of the states are
+    /// only stored if `compute_scc` and `compute_acess` are set.
     pub fn new(fst: &'a F, compute_scc: bool, compute_acess: bool) -> Self {
         let n = fst.num_states();
         let mut props = FstProperties::empty();
@@ -55,6 +63,33 @@ impl<'a, W: Semiring, F: 'a + ExpandedFst<W>> SccVisitor<'a, W, F> {
             w: PhantomData,
         }
     }
+
+    /// Component id of each state, if requested. Ids are in topological order : transitions
+    /// between components always go from a lower id to a higher id. States that haven't been
+    /// visited have an id of -1.
+    pub fn scc(&self) -> Option<&[i32]> {
+        self.scc.as_deref()
+    }
+
+    /// Whether each state is accessible from the start state, if requested.
+    pub fn access(&self) -> Option<&[bool]> {
+        self.access.as_deref()
+    }
+
+    /// Whether a final state can be reached from each state.
+    pub fn coaccess(&self) -> &[bool] {
+        &self.coaccess
+    }
+
+    /// Number of components found.
+    pub fn nscc(&self) -> usize {
+        self.nscc as usize
+    }
+
+    /// Cyclicity, accessibility and coaccessibility properties of the whole FST.
+    pub fn properties(&self) -> FstProperties {
+        self.props
+    }
 }
 
 impl<'a, W: Semiring, F: 'a + ExpandedFst<W>> Visitor<'a, W, F> for SccVisitor<'a, W, F> {
@@ -72,7 +107,7 @@ impl<'a, W: Semiring, F: 'a + ExpandedFst<W>> Visitor<'a, W, F> for SccVisitor<'
             }
         } else {
             if let Some(ref mut access) = self.access {
-                access[s] = true;
+                access[s] = false;
             }
             self.props |= FstProperties::NOT_ACCESSIBLE;
             self.props &= !FstProperties::ACCESSIBLE;
@@ -172,7 +207,7 @@ impl<'a, W: Semiring, F: 'a + ExpandedFst<W>> Visitor<'a, W, F> for SccVisitor<'
     #[inline]
     fn finish_visit(&mut self) {
         if let Some(ref mut scc) = self.scc {
-            for scc_curr in scc.iter_mut() {
+            for scc_curr in scc.iter_mut().filter(|c| **c >= 0) {
                 *scc_curr = self.nscc - 1 - *scc_curr;
             }
         }
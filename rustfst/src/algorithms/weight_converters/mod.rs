@@ -3,5 +3,5 @@ mod simple_weight_converter;
 mod to_gallic_converter;
 
 pub use self::from_gallic_mapper::FromGallicConverter;
-pub use self::simple_weight_converter::SimpleWeightConverter;
+pub use self::simple_weight_converter::{FromSemiringValue, SimpleWeightConverter};
 pub use self::to_gallic_converter::ToGallicConverter;
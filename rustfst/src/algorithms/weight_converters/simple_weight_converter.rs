@@ -5,6 +5,31 @@ use crate::fst_properties::FstProperties;
 use crate::semirings::Semiring;
 use crate::Tr;
 
+/// Conversion of the underlying value of a semiring to the one of another semiring.
+/// Allows converting between single and double precision float semirings.
+pub trait FromSemiringValue<T> {
+    /// Converts `value`, possibly losing precision (e.g from `f64` to `f32`).
+    fn from_semiring_value(value: T) -> Self;
+}
+
+impl<T> FromSemiringValue<T> for T {
+    fn from_semiring_value(value: T) -> Self {
+        value
+    }
+}
+
+impl FromSemiringValue<f32> for f64 {
+    fn from_semiring_value(value: f32) -> Self {
+        value as f64
+    }
+}
+
+impl FromSemiringValue<f64> for f32 {
+    fn from_semiring_value(value: f64) -> Self {
+        value as f32
+    }
+}
+
 /// Mapper that leaves labels and nextstate unchanged and constructs a new weight
 /// from the underlying value of the transition weight.
 pub struct SimpleWeightConverter {}
@@ -12,13 +37,14 @@ pub struct SimpleWeightConverter {}
 impl<SI, SO> WeightConverter<SI, SO> for SimpleWeightConverter
 where
     SI: Semiring,
-    SO: Semiring<Type = SI::Type>,
+    SO: Semiring,
+    SO::Type: FromSemiringValue<SI::Type>,
 {
     fn tr_map(&mut self, tr: &Tr<SI>) -> Result<Tr<SO>> {
         Ok(Tr::new(
             tr.ilabel,
             tr.olabel,
-            SO::new(tr.weight.value().clone()),
+            SO::new(SO::Type::from_semiring_value(tr.weight.value().clone())),
             tr.nextstate,
         ))
     }
@@ -27,7 +53,9 @@ where
         Ok(FinalTr {
             ilabel: final_tr.ilabel,
             olabel: final_tr.olabel,
-            weight: SO::new(final_tr.weight.value().clone()),
+            weight: SO::new(SO::Type::from_semiring_value(
+                final_tr.weight.value().clone(),
+            )),
         })
     }
 
@@ -39,3 +67,36 @@ where
         inprops
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::weight_convert;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, MutableFst};
+    use crate::semirings::{LogWeight, LogWeight64, TropicalWeight, TropicalWeight64};
+    use crate::Trs;
+
+    #[test]
+    fn test_simple_weight_converter_64() -> Result<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.5, 1))?;
+        fst.set_final(1, 0.25)?;
+
+        let fst64: VectorFst<TropicalWeight64> =
+            weight_convert(&fst, &mut SimpleWeightConverter {})?;
+        assert_eq!(
+            fst64.get_trs(0)?.trs(),
+            &[Tr::new(1, 2, TropicalWeight64::new(1.5), 1)]
+        );
+        assert_eq!(fst64.final_weight(1)?, Some(TropicalWeight64::new(0.25)));
+
+        let log64: VectorFst<LogWeight64> = weight_convert(&fst64, &mut SimpleWeightConverter {})?;
+        let log: VectorFst<LogWeight> = weight_convert(&log64, &mut SimpleWeightConverter {})?;
+        let back: VectorFst<TropicalWeight> = weight_convert(&log, &mut SimpleWeightConverter {})?;
+        assert_eq!(back, fst);
+        Ok(())
+    }
+}
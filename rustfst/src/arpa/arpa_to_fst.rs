@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::tr_compares::ILabelCompare;
+use crate::algorithms::tr_sort;
+use crate::arpa::parsed_arpa::ParsedArpa;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{Fst, MutableFst};
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{Label, StateId, SymbolTable, Tr, EPS_LABEL, NO_LABEL};
+
+/// Label of the transitions going from a history state to its backoff state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArpaBackoff {
+    /// Backoff transitions are epsilon transitions.
+    Epsilon,
+    /// Backoff transitions carry the label of the given symbol (added to the symbol table if
+    /// needed). Meant to be used with a `PhiMatcher` to get an exact interpretation of the model.
+    Phi(String),
+}
+
+/// Behaviour when a word of the model is missing from the symbol table provided in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OovPolicy {
+    /// Add the word to the symbol table.
+    Add,
+    /// Drop all the n-grams containing the word.
+    Skip,
+    /// Return an error.
+    Error,
+}
+
+/// Configuration for the conversion of an ARPA model to an FST.
+#[derive(Debug, Clone)]
+pub struct ArpaToFstConfig {
+    /// Begin-of-sentence symbol. The history made of this symbol is the start state.
+    pub bos_symbol: String,
+    /// End-of-sentence symbol. Its probabilities are stored as final weights.
+    pub eos_symbol: String,
+    /// Label of the backoff transitions. Defaults to epsilon.
+    pub backoff: ArpaBackoff,
+    /// Behaviour for the words missing from `symbol_table`. Defaults to adding them.
+    pub oov_policy: OovPolicy,
+    /// Word symbol table to use. If `None`, a new one is created.
+    pub symbol_table: Option<SymbolTable>,
+}
+
+impl Default for ArpaToFstConfig {
+    fn default() -> Self {
+        Self {
+            bos_symbol: "<s>".to_string(),
+            eos_symbol: "</s>".to_string(),
+            backoff: ArpaBackoff::Epsilon,
+            oov_policy: OovPolicy::Add,
+            symbol_table: None,
+        }
+    }
+}
+
+impl ArpaToFstConfig {
+    /// Sets the begin-of-sentence symbol, `<s>` by default.
+    pub fn with_bos_symbol(self, bos_symbol: impl Into<String>) -> Self {
+        Self {
+            bos_symbol: bos_symbol.into(),
+            ..self
+        }
+    }
+
+    /// Sets the end-of-sentence symbol, `</s>` by default.
+    pub fn with_eos_symbol(self, eos_symbol: impl Into<String>) -> Self {
+        Self {
+            eos_symbol: eos_symbol.into(),
+            ..self
+        }
+    }
+
+    /// Sets the label of the backoff transitions.
+    pub fn with_backoff(self, backoff: ArpaBackoff) -> Self {
+        Self { backoff, ..self }
+    }
+
+    /// Sets the behaviour for the words missing from the symbol table.
+    pub fn with_oov_policy(self, oov_policy: OovPolicy) -> Self {
+        Self { oov_policy, ..self }
+    }
+
+    /// Sets the word symbol table to use.
+    pub fn with_symbol_table(self, symbol_table: SymbolTable) -> Self {
+        Self {
+            symbol_table: Some(symbol_table),
+            ..self
+        }
+    }
+}
+
+// Labels used in the histories for the sentence boundaries. They never appear on a transition.
+const BOS_KEY: Label = NO_LABEL;
+const EOS_KEY: Label = NO_LABEL - 1;
+
+fn arpa_to_cost(log10: f32) -> TropicalWeight {
+    TropicalWeight::new(-log10 * std::f32::consts::LN_10)
+}
+
+/// Converts an ARPA language model to a backoff grammar acceptor (usually called G).
+///
+/// A state is created for each history i.e for each n-gram of order lower than the order of
+/// the model, plus the empty history (the unigram state). A transition labelled by `w` goes
+/// from the state of history `h` to the state of the longest suffix of `h w` being a history.
+/// Each history state has a backoff transition to the state of its longest proper suffix,
+/// weighted by the backoff weight of the history.
+///
+/// The start state is the history containing only the begin-of-sentence symbol (the unigram
+/// state if it is missing) and the end-of-sentence probabilities are encoded as final weights,
+/// thus none of these symbols appear on the transitions. Weights are the negated natural
+/// logarithm of the probabilities.
+///
+/// The returned word symbol table is also attached to the FST as input and output symbols.
+pub fn arpa_to_fst(
+    arpa: &ParsedArpa,
+    config: &ArpaToFstConfig,
+) -> Result<(VectorFst<TropicalWeight>, Arc<SymbolTable>)> {
+    let mut symt = config.symbol_table.clone().unwrap_or_default();
+    let max_order = arpa.order();
+
+    // Maps words to labels. Returns None if the n-gram must be skipped.
+    let to_keys = |words: &[String], symt: &mut SymbolTable| -> Result<Option<Vec<Label>>> {
+        let mut keys = Vec::with_capacity(words.len());
+        for word in words {
+            let key = if *word == config.bos_symbol {
+                BOS_KEY
+            } else if *word == config.eos_symbol {
+                EOS_KEY
+            } else if let Some(label) = symt.get_label(word) {
+                label
+            } else {
+                match config.oov_policy {
+                    OovPolicy::Add => symt.add_symbol(word.as_str()),
+                    OovPolicy::Skip => return Ok(None),
+                    OovPolicy::Error => bail!("ArpaToFst: OOV word {:?}", word),
+                }
+            };
+            keys.push(key);
+        }
+        Ok(Some(keys))
+    };
+
+    let mut ngrams = Vec::with_capacity(max_order);
+    for entries in arpa.ngrams.iter() {
+        let mut keys = Vec::with_capacity(entries.len());
+        for ngram in entries.iter() {
+            if let Some(k) = to_keys(&ngram.words, &mut symt)? {
+                keys.push((k, ngram));
+            }
+        }
+        ngrams.push(keys);
+    }
+
+    let backoff_label = match &config.backoff {
+        ArpaBackoff::Epsilon => EPS_LABEL,
+        ArpaBackoff::Phi(phi_symbol) => match symt.get_label(phi_symbol) {
+            Some(label) => label,
+            None => symt.add_symbol(phi_symbol.as_str()),
+        },
+    };
+
+    let mut fst = VectorFst::<TropicalWeight>::new();
+    let mut states: HashMap<Vec<Label>, StateId> = HashMap::new();
+    let backoff_state = fst.add_state();
+    states.insert(vec![], backoff_state);
+    for (keys, _) in ngrams.iter().take(max_order.saturating_sub(1)).flatten() {
+        if keys.last() != Some(&EOS_KEY) && !states.contains_key(keys) {
+            states.insert(keys.clone(), fst.add_state());
+        }
+    }
+    fst.set_start(*states.get([BOS_KEY].as_ref()).unwrap_or(&backoff_state))?;
+
+    let longest_suffix_state = |keys: &[Label]| -> StateId {
+        (0..=keys.len())
+            .find_map(|start| states.get(&keys[start..]).cloned())
+            .unwrap()
+    };
+
+    for (keys, ngram) in ngrams.iter().flatten() {
+        let (word, history) = keys.split_last().unwrap();
+        let src = match states.get(history) {
+            Some(s) => *s,
+            None => bail!("ArpaToFst: missing history for n-gram {:?}", ngram.words),
+        };
+        let weight = arpa_to_cost(ngram.logprob);
+        if *word == EOS_KEY {
+            fst.set_final(src, weight)?;
+        } else if *word != BOS_KEY {
+            let dest = longest_suffix_state(keys);
+            fst.add_tr(src, Tr::new(*word, *word, weight, dest))?;
+        }
+
+        if let Some(&state) = states.get(keys) {
+            if state != backoff_state {
+                let weight = ngram
+                    .backoff
+                    .map(arpa_to_cost)
+                    .unwrap_or_else(TropicalWeight::one);
+                let dest = longest_suffix_state(&keys[1..]);
+                fst.add_tr(state, Tr::new(backoff_label, backoff_label, weight, dest))?;
+            }
+        }
+    }
+
+    tr_sort(&mut fst, ILabelCompare {});
+
+    let symt = Arc::new(symt);
+    fst.set_input_symbols(Arc::clone(&symt));
+    fst.set_output_symbols(Arc::clone(&symt));
+    Ok((fst, symt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::compose::compose;
+    use crate::algorithms::shortest_distance;
+    use crate::fst_traits::{CoreFst, ExpandedFst, SerializableFst, StateIterator};
+    use crate::utils::acceptor;
+    use crate::Trs;
+
+    const ARPA: &str = "\\data\\
+ngram 1=4
+ngram 2=3
+
+\\1-grams:
+-99\t<s>\t-0.5
+-0.5\ta\t-0.3
+-0.7\tb
+-0.6\t</s>
+
+\\2-grams:
+-0.2\t<s> a
+-0.1\ta b
+-0.4\ta </s>
+
+\\end\\
+";
+
+    fn sentence_cost(
+        g: &VectorFst<TropicalWeight>,
+        symt: &SymbolTable,
+        words: &[&str],
+    ) -> Result<f32> {
+        let labels: Vec<_> = words.iter().map(|w| symt.get_label(w).unwrap()).collect();
+        let sentence: VectorFst<TropicalWeight> = acceptor(&labels, TropicalWeight::one());
+        let composed: VectorFst<TropicalWeight> = compose(sentence, g.clone())?;
+        let distances = shortest_distance(&composed, true)?;
+        Ok(*distances[composed.start().unwrap() as usize].value())
+    }
+
+    #[test]
+    fn test_arpa_to_fst() -> Result<()> {
+        let arpa = ParsedArpa::from_string(ARPA)?;
+        let (g, symt) = arpa_to_fst(&arpa, &ArpaToFstConfig::default())?;
+
+        // <eps>, a, b
+        assert_eq!(symt.len(), 3);
+        // Histories : [], [<s>], [a], [b]
+        assert_eq!(g.num_states(), 4);
+        assert!(g.input_symbols().is_some());
+
+        let ln10 = std::f32::consts::LN_10;
+        let cost = sentence_cost(&g, &symt, &["a", "b"])?;
+        assert!((cost - (0.2 + 0.1 + 0.6) * ln10).abs() < 1e-4);
+
+        let cost = sentence_cost(&g, &symt, &["a"])?;
+        assert!((cost - (0.2 + 0.4) * ln10).abs() < 1e-4);
+
+        // Backoff from <s> : -0.5 then unigram b : -0.7 then backoff from b to </s>.
+        let cost = sentence_cost(&g, &symt, &["b"])?;
+        assert!((cost - (0.5 + 0.7 + 0.6) * ln10).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arpa_to_fst_phi() -> Result<()> {
+        let arpa = ParsedArpa::from_string(ARPA)?;
+        let config = ArpaToFstConfig::default().with_backoff(ArpaBackoff::Phi("#phi".into()));
+        let (g, symt) = arpa_to_fst(&arpa, &config)?;
+
+        let phi = symt.get_label("#phi").unwrap();
+        let mut num_phi = 0;
+        for s in g.states_iter() {
+            num_phi += g
+                .get_trs(s)?
+                .trs()
+                .iter()
+                .filter(|tr| tr.ilabel == phi)
+                .count();
+        }
+        assert_eq!(num_phi, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arpa_to_fst_oov() -> Result<()> {
+        let arpa = ParsedArpa::from_string(ARPA)?;
+        let mut symt = SymbolTable::new();
+        symt.add_symbol("a");
+
+        let config = ArpaToFstConfig::default()
+            .with_symbol_table(symt.clone())
+            .with_oov_policy(OovPolicy::Error);
+        assert!(arpa_to_fst(&arpa, &config).is_err());
+
+        let config = config.with_oov_policy(OovPolicy::Skip);
+        let (g, out_symt) = arpa_to_fst(&arpa, &config)?;
+        assert_eq!(*out_symt, symt);
+        // Histories : [], [<s>], [a]
+        assert_eq!(g.num_states(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arpa_to_fst_binary_round_trip() -> Result<()> {
+        let arpa = ParsedArpa::from_string(ARPA)?;
+        let (g, _) = arpa_to_fst(&arpa, &ArpaToFstConfig::default())?;
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("G.fst");
+        g.write(&path)?;
+        let g_read = VectorFst::<TropicalWeight>::read(&path)?;
+        assert_eq!(g, g_read);
+        assert_eq!(g.input_symbols(), g_read.input_symbols());
+        Ok(())
+    }
+}
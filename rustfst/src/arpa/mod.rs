@@ -0,0 +1,6 @@
+mod arpa_to_fst;
+mod nom_parser;
+mod parsed_arpa;
+
+pub use self::arpa_to_fst::{arpa_to_fst, ArpaBackoff, ArpaToFstConfig, OovPolicy};
+pub use self::parsed_arpa::{ArpaNgram, ParsedArpa};
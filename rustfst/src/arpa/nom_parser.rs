@@ -0,0 +1,38 @@
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{space0, space1};
+use nom::combinator::all_consuming;
+use nom::multi::separated_list1;
+use nom::number::complete::float;
+use nom::sequence::{delimited, terminated};
+use nom::IResult;
+
+use crate::parsers::nom_utils::num;
+
+fn token(i: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| !c.is_whitespace())(i)
+}
+
+/// Parses a line of the `\data\` section e.g `ngram 2=145`.
+pub(crate) fn ngram_count(i: &str) -> IResult<&str, (usize, usize)> {
+    let (i, _) = terminated(tag("ngram"), space1)(i)?;
+    let (i, order) = num(i)?;
+    let (i, _) = delimited(space0, tag("="), space0)(i)?;
+    let (i, count) = all_consuming(terminated(num, space0))(i)?;
+    Ok((i, (order, count)))
+}
+
+/// Parses a section header e.g `\2-grams:`.
+pub(crate) fn ngram_section(i: &str) -> IResult<&str, usize> {
+    let (i, _) = tag("\\")(i)?;
+    let (i, order) = num(i)?;
+    let (i, _) = all_consuming(terminated(tag("-grams:"), space0))(i)?;
+    Ok((i, order))
+}
+
+/// Parses an n-gram entry : a log10 probability followed by whitespace separated tokens.
+/// The words and the optional backoff weight are distinguished by the caller, based on the order.
+pub(crate) fn ngram_entry(i: &str) -> IResult<&str, (f32, Vec<&str>)> {
+    let (i, logprob) = terminated(float, space1)(i)?;
+    let (i, tokens) = all_consuming(terminated(separated_list1(space1, token), space0))(i)?;
+    Ok((i, (logprob, tokens)))
+}
@@ -0,0 +1,234 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::arpa::nom_parser::{ngram_count, ngram_entry, ngram_section};
+
+/// An n-gram entry of an ARPA language model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArpaNgram {
+    /// Words of the n-gram, the last one being the predicted word.
+    pub words: Vec<String>,
+    /// Log10 conditional probability of the last word given the previous ones.
+    pub logprob: f32,
+    /// Log10 backoff weight of the n-gram used as history. Missing backoff weights are
+    /// equivalent to a weight of `0.0`.
+    pub backoff: Option<f32>,
+}
+
+/// Struct representing a parsed ARPA language model. The n-grams are grouped by order :
+/// `ngrams[0]` contains the unigrams, `ngrams[1]` the bigrams...
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedArpa {
+    /// N-grams of each order, in the order of the file.
+    pub ngrams: Vec<Vec<ArpaNgram>>,
+}
+
+// Bounds the memory reserved from the counts of the `\data\` section, which can't be trusted.
+const MAX_NGRAMS_RESERVE: usize = 1 << 16;
+
+#[derive(PartialEq)]
+enum Section {
+    Preamble,
+    Data,
+    Ngrams(usize),
+    End,
+}
+
+impl ParsedArpa {
+    /// Highest order of the n-grams in the model.
+    pub fn order(&self) -> usize {
+        self.ngrams.len()
+    }
+
+    /// Loads an ARPA language model from a file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = read_to_string(path.as_ref())
+            .with_context(|| format!("Can't open file : {:?}", path.as_ref()))?;
+        Self::from_string(&content)
+    }
+
+    /// Loads an ARPA language model from a string.
+    ///
+    /// # Format:
+    ///
+    /// ```text
+    /// \data\
+    /// ngram 1=3
+    /// ngram 2=1
+    ///
+    /// \1-grams:
+    /// -99     <s>     -0.3
+    /// -0.5    hello   -0.2
+    /// -0.4    </s>
+    ///
+    /// \2-grams:
+    /// -0.1    <s> hello
+    ///
+    /// \end\
+    /// ```
+    ///
+    /// Probabilities and backoff weights are log10 values. Anything before `\data\` is ignored.
+    /// The number of n-grams of each order must match the counts declared in the `\data\` section.
+    pub fn from_string(arpa_string: &str) -> Result<Self> {
+        let mut counts: Vec<usize> = vec![];
+        let mut ngrams: Vec<Vec<ArpaNgram>> = vec![];
+        let mut section = Section::Preamble;
+
+        for (idx, line) in arpa_string.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "\\data\\" {
+                if section != Section::Preamble {
+                    bail!("Line {}: unexpected \\data\\ section", line_number);
+                }
+                section = Section::Data;
+                continue;
+            }
+            if line == "\\end\\" {
+                if section == Section::Preamble {
+                    bail!("Line {}: \\end\\ found before \\data\\", line_number);
+                }
+                section = Section::End;
+                break;
+            }
+            if section != Section::Preamble && line.starts_with('\\') {
+                let (_, order) = ngram_section(line).map_err(|_| {
+                    format_err!("Line {}: invalid section header {:?}", line_number, line)
+                })?;
+                if order != ngrams.len() + 1 || order > counts.len() {
+                    bail!("Line {}: unexpected {}-grams section", line_number, order);
+                }
+                ngrams.push(Vec::with_capacity(
+                    counts[order - 1].min(MAX_NGRAMS_RESERVE),
+                ));
+                section = Section::Ngrams(order);
+                continue;
+            }
+            match section {
+                Section::Preamble | Section::End => {}
+                Section::Data => {
+                    let (_, (order, count)) = ngram_count(line).map_err(|_| {
+                        format_err!("Line {}: invalid n-gram count {:?}", line_number, line)
+                    })?;
+                    if order != counts.len() + 1 {
+                        bail!(
+                            "Line {}: expected count of {}-grams, found {}-grams",
+                            line_number,
+                            counts.len() + 1,
+                            order
+                        );
+                    }
+                    counts.push(count);
+                }
+                Section::Ngrams(order) => {
+                    let (_, (logprob, tokens)) = ngram_entry(line).map_err(|_| {
+                        format_err!("Line {}: invalid n-gram entry {:?}", line_number, line)
+                    })?;
+                    let backoff = if tokens.len() == order + 1 {
+                        Some(tokens[order].parse::<f32>().map_err(|_| {
+                            format_err!("Line {}: invalid backoff weight {:?}", line_number, line)
+                        })?)
+                    } else if tokens.len() == order {
+                        None
+                    } else {
+                        bail!(
+                            "Line {}: expected {} words in {:?}",
+                            line_number,
+                            order,
+                            line
+                        );
+                    };
+                    ngrams[order - 1].push(ArpaNgram {
+                        words: tokens[..order].iter().map(|s| s.to_string()).collect(),
+                        logprob,
+                        backoff,
+                    });
+                }
+            }
+        }
+
+        if section != Section::End {
+            bail!("Missing \\end\\ marker");
+        }
+        if ngrams.len() != counts.len() {
+            bail!(
+                "Expected {} n-gram sections, found {}",
+                counts.len(),
+                ngrams.len()
+            );
+        }
+        for (order, (entries, count)) in ngrams.iter().zip(counts.iter()).enumerate() {
+            if entries.len() != *count {
+                bail!(
+                    "Expected {} {}-grams, found {}",
+                    count,
+                    order + 1,
+                    entries.len()
+                );
+            }
+        }
+
+        Ok(Self { ngrams })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arpa() -> Result<()> {
+        let arpa =
+            "some preamble\n\n\\data\\\nngram 1=3\nngram 2=1\n\n\\1-grams:\n-99\t<s>\t-0.3\n\
+            -0.5\thello -0.2\n-0.4\t</s>\n\n\\2-grams:\n-0.1\t<s> hello\n\n\\end\\\n";
+        let parsed = ParsedArpa::from_string(arpa)?;
+
+        assert_eq!(parsed.order(), 2);
+        assert_eq!(parsed.ngrams[0].len(), 3);
+        assert_eq!(
+            parsed.ngrams[0][1],
+            ArpaNgram {
+                words: vec!["hello".to_string()],
+                logprob: -0.5,
+                backoff: Some(-0.2)
+            }
+        );
+        assert_eq!(parsed.ngrams[0][2].backoff, None);
+        assert_eq!(
+            parsed.ngrams[1][0].words,
+            vec!["<s>".to_string(), "hello".to_string()]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_arpa_wrong_count() {
+        let arpa = "\\data\\\nngram 1=2\n\n\\1-grams:\n-0.5\thello\n\n\\end\\\n";
+        assert!(ParsedArpa::from_string(arpa).is_err());
+    }
+
+    #[test]
+    fn test_parse_arpa_oversized_count() {
+        let arpa = "\\data\\\nngram 1=999999999999\n\n\\1-grams:\n-0.5\thello\n\n\\end\\\n";
+        let err = ParsedArpa::from_string(arpa).unwrap_err();
+        assert_eq!(format!("{}", err), "Expected 999999999999 1-grams, found 1");
+    }
+
+    #[test]
+    fn test_parse_arpa_missing_end() {
+        let arpa = "\\data\\\nngram 1=1\n\n\\1-grams:\n-0.5\thello\n";
+        assert!(ParsedArpa::from_string(arpa).is_err());
+    }
+
+    #[test]
+    fn test_parse_arpa_invalid_entry() {
+        let arpa = "\\data\\\nngram 1=1\n\n\\1-grams:\n-0.5\thello world foo\n\n\\end\\\n";
+        let err = ParsedArpa::from_string(arpa).unwrap_err();
+        assert!(format!("{}", err).starts_with("Line 5"));
+    }
+}
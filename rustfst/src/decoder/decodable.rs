@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use crate::{Label, EPS_LABEL};
+
+/// Interface between the decoder and the acoustic model : provides a score for each input
+/// label of the decoding graph at each frame.
+pub trait Decodable {
+    /// Number of frames to decode.
+    fn num_frames(&self) -> usize;
+
+    /// Log-likelihood of the input label `label` (never `EPS_LABEL`) at frame `frame`.
+    fn log_likelihood(&self, frame: usize, label: Label) -> Result<f32>;
+}
+
+/// `Decodable` backed by a dense matrix of log-likelihoods of shape `num_frames x num_labels`.
+/// The score of label `l` at frame `t` is the element at row `t` and column `l - 1`, label `0`
+/// being reserved for epsilon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseDecodable {
+    num_labels: usize,
+    log_likelihoods: Vec<f32>,
+}
+
+impl DenseDecodable {
+    /// Creates a `DenseDecodable` from a row-major matrix.
+    pub fn new(num_labels: usize, log_likelihoods: Vec<f32>) -> Result<Self> {
+        if num_labels == 0 {
+            bail!("DenseDecodable: num_labels must be positive");
+        }
+        if !log_likelihoods.len().is_multiple_of(num_labels) {
+            bail!(
+                "DenseDecodable: {} scores can't be split in rows of {} labels",
+                log_likelihoods.len(),
+                num_labels
+            );
+        }
+        Ok(Self {
+            num_labels,
+            log_likelihoods,
+        })
+    }
+
+    /// Creates a `DenseDecodable` from a list of frames. All the frames must have the same length.
+    pub fn from_rows(rows: Vec<Vec<f32>>) -> Result<Self> {
+        let num_labels = rows.first().map(|r| r.len()).unwrap_or(1);
+        if rows.iter().any(|r| r.len() != num_labels) {
+            bail!("DenseDecodable: all the rows must have the same length");
+        }
+        Self::new(num_labels, rows.into_iter().flatten().collect())
+    }
+
+    /// Number of labels scored at each frame.
+    pub fn num_labels(&self) -> usize {
+        self.num_labels
+    }
+}
+
+impl Decodable for DenseDecodable {
+    fn num_frames(&self) -> usize {
+        self.log_likelihoods.len() / self.num_labels
+    }
+
+    fn log_likelihood(&self, frame: usize, label: Label) -> Result<f32> {
+        if frame >= self.num_frames() {
+            bail!("DenseDecodable: frame {} out of range", frame);
+        }
+        if label == EPS_LABEL || label as usize > self.num_labels {
+            bail!("DenseDecodable: label {} out of range", label);
+        }
+        Ok(self.log_likelihoods[frame * self.num_labels + label as usize - 1])
+    }
+}
@@ -0,0 +1,5 @@
+mod decodable;
+mod viterbi_decoder;
+
+pub use self::decodable::{Decodable, DenseDecodable};
+pub use self::viterbi_decoder::{decode, DecoderConfig, DecoderOutput};
@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::algorithms::connect;
+use crate::decoder::Decodable;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{Fst, MutableFst};
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{FstPath, Label, StateId, Tr, Trs, EPS_LABEL};
+
+/// Configuration of the Viterbi decoder.
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+pub struct DecoderConfig {
+    /// Tokens whose cost is higher than the best cost of the frame plus `beam` are pruned.
+    pub beam: f32,
+    /// Maximum number of tokens kept active at each frame.
+    pub max_active: usize,
+    /// Factor applied to the log-likelihoods of the `Decodable` before adding them to the
+    /// graph costs.
+    pub acoustic_scale: f32,
+    /// Whether to build the raw state-level lattice of all the surviving tokens.
+    pub generate_lattice: bool,
+    /// If no final state is reached at the end of the decoding, return the best partial
+    /// hypothesis instead of an error.
+    pub allow_partial: bool,
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        Self {
+            beam: 16.0,
+            max_active: usize::MAX,
+            acoustic_scale: 1.0,
+            generate_lattice: false,
+            allow_partial: false,
+        }
+    }
+}
+
+impl DecoderConfig {
+    pub fn with_beam(self, beam: f32) -> Self {
+        Self { beam, ..self }
+    }
+
+    pub fn with_max_active(self, max_active: usize) -> Self {
+        Self { max_active, ..self }
+    }
+
+    pub fn with_acoustic_scale(self, acoustic_scale: f32) -> Self {
+        Self {
+            acoustic_scale,
+            ..self
+        }
+    }
+
+    pub fn with_generate_lattice(self, generate_lattice: bool) -> Self {
+        Self {
+            generate_lattice,
+            ..self
+        }
+    }
+
+    pub fn with_allow_partial(self, allow_partial: bool) -> Self {
+        Self {
+            allow_partial,
+            ..self
+        }
+    }
+}
+
+/// Result of the decoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecoderOutput {
+    /// Best path. Its weight is the total cost i.e graph cost plus scaled acoustic cost.
+    pub best_path: FstPath<TropicalWeight>,
+    /// Whether the best path ends in a final state of the decoding graph.
+    pub reached_final: bool,
+    /// Raw state-level lattice, only generated if `generate_lattice` is set. Its transitions
+    /// carry the labels of the graph and the sum of the graph and acoustic costs.
+    pub lattice: Option<VectorFst<TropicalWeight>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Link {
+    prev_token: usize,
+    ilabel: Label,
+    olabel: Label,
+    weight: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    cost: f32,
+    best_link: Option<Link>,
+    links: Vec<Link>,
+}
+
+struct ViterbiDecoder<'a, F: Fst<TropicalWeight>> {
+    fst: &'a F,
+    config: DecoderConfig,
+    tokens: Vec<Token>,
+    // Maps a state of the graph to its token for the current frame.
+    active: HashMap<StateId, usize>,
+}
+
+impl<'a, F: Fst<TropicalWeight>> ViterbiDecoder<'a, F> {
+    fn new(fst: &'a F, config: DecoderConfig) -> Self {
+        Self {
+            fst,
+            config,
+            tokens: vec![],
+            active: HashMap::new(),
+        }
+    }
+
+    // Returns true if the cost of the token of `state` has been improved.
+    fn relax(
+        tokens: &mut Vec<Token>,
+        active: &mut HashMap<StateId, usize>,
+        state: StateId,
+        cost: f32,
+        link: Link,
+        generate_lattice: bool,
+    ) -> bool {
+        match active.get(&state) {
+            Some(&idx) => {
+                let token = &mut tokens[idx];
+                if generate_lattice {
+                    match token.links.iter_mut().find(|l| {
+                        l.prev_token == link.prev_token
+                            && l.ilabel == link.ilabel
+                            && l.olabel == link.olabel
+                    }) {
+                        Some(l) => l.weight = l.weight.min(link.weight),
+                        None => token.links.push(link),
+                    }
+                }
+                if cost < token.cost {
+                    token.cost = cost;
+                    token.best_link = Some(link);
+                    true
+                } else {
+                    false
+                }
+            }
+            None => {
+                active.insert(state, tokens.len());
+                tokens.push(Token {
+                    cost,
+                    best_link: Some(link),
+                    links: if generate_lattice { vec![link] } else { vec![] },
+                });
+                true
+            }
+        }
+    }
+
+    fn sorted_active(&self) -> Vec<(StateId, usize)> {
+        let mut active: Vec<_> = self.active.iter().map(|(s, t)| (*s, *t)).collect();
+        active.sort_unstable();
+        active
+    }
+
+    fn cutoff(&self) -> f32 {
+        let mut costs: Vec<f32> = self.active.values().map(|t| self.tokens[*t].cost).collect();
+        let best = costs.iter().cloned().fold(f32::INFINITY, f32::min);
+        let mut cutoff = best + self.config.beam;
+        if costs.len() > self.config.max_active && self.config.max_active > 0 {
+            let (_, nth, _) =
+                costs.select_nth_unstable_by(self.config.max_active - 1, |a, b| a.total_cmp(b));
+            cutoff = cutoff.min(*nth);
+        }
+        cutoff
+    }
+
+    fn process_emitting<D: Decodable>(&mut self, decodable: &D, frame: usize) -> Result<()> {
+        let cutoff = self.cutoff();
+        let mut next_active = HashMap::new();
+        let mut next_cutoff = f32::INFINITY;
+        for (state, token_idx) in self.sorted_active() {
+            let cost = self.tokens[token_idx].cost;
+            if cost > cutoff {
+                continue;
+            }
+            for tr in self.fst.get_trs(state)?.trs() {
+                if tr.ilabel == EPS_LABEL {
+                    continue;
+                }
+                let log_likelihood = decodable.log_likelihood(frame, tr.ilabel)?;
+                if log_likelihood.is_nan() {
+                    bail!(
+                        "Decoder: NaN log-likelihood for label {} at frame {}",
+                        tr.ilabel,
+                        frame
+                    );
+                }
+                let acoustic_cost = -log_likelihood * self.config.acoustic_scale;
+                let weight = *tr.weight.value() + acoustic_cost;
+                let new_cost = cost + weight;
+                if new_cost > next_cutoff {
+                    continue;
+                }
+                next_cutoff = next_cutoff.min(new_cost + self.config.beam);
+                let link = Link {
+                    prev_token: token_idx,
+                    ilabel: tr.ilabel,
+                    olabel: tr.olabel,
+                    weight,
+                };
+                Self::relax(
+                    &mut self.tokens,
+                    &mut next_active,
+                    tr.nextstate,
+                    new_cost,
+                    link,
+                    self.config.generate_lattice,
+                );
+            }
+        }
+        if next_active.is_empty() {
+            bail!("Decoder: no active token left at frame {}", frame);
+        }
+        self.active = next_active;
+        Ok(())
+    }
+
+    fn process_nonemitting(&mut self) -> Result<()> {
+        let cutoff = self.cutoff();
+        let mut queue: Vec<StateId> = self.sorted_active().iter().map(|(s, _)| *s).collect();
+        // Number of epsilon transitions on the best path to each state since the last emitting
+        // step. A path longer than the number of active states goes through a cycle, which can
+        // only improve the cost if the cycle has a negative cost.
+        let mut depth: HashMap<StateId, usize> = HashMap::new();
+        while let Some(state) = queue.pop() {
+            let token_idx = self.active[&state];
+            let cost = self.tokens[token_idx].cost;
+            if cost > cutoff {
+                continue;
+            }
+            for tr in self.fst.get_trs(state)?.trs() {
+                if tr.ilabel != EPS_LABEL {
+                    continue;
+                }
+                let weight = *tr.weight.value();
+                let new_cost = cost + weight;
+                if new_cost > cutoff {
+                    continue;
+                }
+                let link = Link {
+                    prev_token: token_idx,
+                    ilabel: tr.ilabel,
+                    olabel: tr.olabel,
+                    weight,
+                };
+                if Self::relax(
+                    &mut self.tokens,
+                    &mut self.active,
+                    tr.nextstate,
+                    new_cost,
+                    link,
+                    self.config.generate_lattice,
+                ) {
+                    let d = depth.get(&state).copied().unwrap_or(0) + 1;
+                    if d >= self.active.len() {
+                        bail!("Decoder: the decoding graph has a negative cost epsilon cycle");
+                    }
+                    depth.insert(tr.nextstate, d);
+                    queue.push(tr.nextstate);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn decode<D: Decodable>(mut self, decodable: &D) -> Result<DecoderOutput> {
+        let start = match self.fst.start() {
+            Some(s) => s,
+            None => bail!("Decoder: the decoding graph has no start state"),
+        };
+        self.tokens.push(Token {
+            cost: 0.0,
+            best_link: None,
+            links: vec![],
+        });
+        self.active.insert(start, 0);
+        self.process_nonemitting()?;
+        for frame in 0..decodable.num_frames() {
+            self.process_emitting(decodable, frame)?;
+            self.process_nonemitting()?;
+        }
+
+        let mut final_tokens = vec![];
+        for (state, token_idx) in self.sorted_active() {
+            if let Some(w) = self.fst.final_weight(state)? {
+                final_tokens.push((token_idx, *w.value()));
+            }
+        }
+        let reached_final = !final_tokens.is_empty();
+        if !reached_final {
+            if !self.config.allow_partial {
+                bail!("Decoder: no final state reached at the end of the utterance");
+            }
+            final_tokens = self
+                .sorted_active()
+                .iter()
+                .map(|(_, t)| (*t, 0.0))
+                .collect();
+        }
+
+        let (best_token, best_final) = final_tokens
+            .iter()
+            .cloned()
+            .min_by(|(t1, w1), (t2, w2)| {
+                (self.tokens[*t1].cost + w1).total_cmp(&(self.tokens[*t2].cost + w2))
+            })
+            .unwrap();
+        let best_path = self.traceback(best_token, best_final)?;
+
+        let lattice = if self.config.generate_lattice {
+            Some(self.build_lattice(&final_tokens)?)
+        } else {
+            None
+        };
+
+        Ok(DecoderOutput {
+            best_path,
+            reached_final,
+            lattice,
+        })
+    }
+
+    fn traceback(&self, token_idx: usize, final_weight: f32) -> Result<FstPath<TropicalWeight>> {
+        let mut links = vec![];
+        let mut current = token_idx;
+        while let Some(link) = self.tokens[current].best_link {
+            links.push(link);
+            current = link.prev_token;
+        }
+        let mut path = FstPath::default();
+        for link in links.iter().rev() {
+            path.add_to_path(link.ilabel, link.olabel, &TropicalWeight::new(link.weight))?;
+        }
+        path.add_weight(&TropicalWeight::new(final_weight))?;
+        Ok(path)
+    }
+
+    fn build_lattice(&self, final_tokens: &[(usize, f32)]) -> Result<VectorFst<TropicalWeight>> {
+        let mut lattice = VectorFst::new();
+        lattice.add_states(self.tokens.len());
+        lattice.set_start(0)?;
+        for (token_idx, token) in self.tokens.iter().enumerate() {
+            for link in token.links.iter() {
+                lattice.add_tr(
+                    link.prev_token as StateId,
+                    Tr::new(
+                        link.ilabel,
+                        link.olabel,
+                        TropicalWeight::new(link.weight),
+                        token_idx as StateId,
+                    ),
+                )?;
+            }
+        }
+        for (token_idx, final_weight) in final_tokens.iter() {
+            lattice.set_final(*token_idx as StateId, TropicalWeight::new(*final_weight))?;
+        }
+        connect(&mut lattice)?;
+        if let Some(symt) = self.fst.input_symbols() {
+            lattice.set_input_symbols(symt.clone());
+        }
+        if let Some(symt) = self.fst.output_symbols() {
+            lattice.set_output_symbols(symt.clone());
+        }
+        Ok(lattice)
+    }
+}
+
+/// Viterbi beam search over a decoding graph using token passing.
+///
+/// At each frame, the tokens are propagated through the transitions with a non-epsilon input
+/// label, scored by the `Decodable`, then through the epsilon transitions of the graph. The
+/// cost of a transition is its weight minus the scaled log-likelihood of its input label.
+/// Tokens are pruned using both the beam and the maximum number of active tokens.
+///
+/// The graph can be any `Fst<TropicalWeight>`, including lazy FSTs : only the visited states
+/// are ever computed.
+///
+/// Fails if the `Decodable` returns a NaN log-likelihood or if the graph has an epsilon cycle
+/// with a negative cost.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::decoder::{decode, DecoderConfig, DenseDecodable};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// // Two words : 1 (spelled with label 1) and 2 (spelled with label 2).
+/// let mut graph = VectorFst::<TropicalWeight>::new();
+/// let s0 = graph.add_state();
+/// let s1 = graph.add_state();
+/// graph.set_start(s0)?;
+/// graph.add_tr(s0, Tr::new(1, 1, 0.0, s1))?;
+/// graph.add_tr(s0, Tr::new(2, 2, 0.0, s1))?;
+/// graph.set_final(s1, 0.0)?;
+///
+/// let decodable = DenseDecodable::from_rows(vec![vec![-3.0, -1.0]])?;
+/// let output = decode(&graph, &decodable, &DecoderConfig::default())?;
+/// assert_eq!(output.best_path.olabels, vec![2]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode<F, D>(fst: &F, decodable: &D, config: &DecoderConfig) -> Result<DecoderOutput>
+where
+    F: Fst<TropicalWeight>,
+    D: Decodable,
+{
+    ViterbiDecoder::new(fst, *config).decode(decodable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::shortest_path;
+    use crate::decoder::DenseDecodable;
+    use crate::fst_traits::{CoreFst, ExpandedFst};
+
+    // Word 10 is spelled "1 2", word 20 is spelled "3 2". Each label has a self-loop and
+    // words can be repeated through an epsilon transition.
+    fn graph() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 10, 0.5, 1))?;
+        fst.add_tr(0, Tr::new(3, 20, 0.5, 2))?;
+        fst.add_tr(1, Tr::new(1, 0, 0.0, 1))?;
+        fst.add_tr(1, Tr::new(2, 0, 0.0, 3))?;
+        fst.add_tr(2, Tr::new(3, 0, 0.0, 2))?;
+        fst.add_tr(2, Tr::new(2, 0, 0.0, 3))?;
+        fst.add_tr(3, Tr::new(2, 0, 0.0, 3))?;
+        fst.add_tr(3, Tr::new(0, 0, 1.0, 0))?;
+        fst.set_final(3, 0.0)?;
+        Ok(fst)
+    }
+
+    fn scores(best_labels: &[Label]) -> Result<DenseDecodable> {
+        DenseDecodable::from_rows(
+            best_labels
+                .iter()
+                .map(|l| {
+                    (1..=3)
+                        .map(|c| if c == *l { -0.1 } else { -5.0 })
+                        .collect::<Vec<f32>>()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_decode() -> Result<()> {
+        let decodable = scores(&[1, 1, 2, 3, 3, 3, 2])?;
+        let output = decode(&graph()?, &decodable, &DecoderConfig::default())?;
+
+        assert!(output.reached_final);
+        assert!(output.lattice.is_none());
+        assert_eq!(output.best_path.ilabels, vec![1, 1, 2, 3, 3, 3, 2]);
+        assert_eq!(output.best_path.olabels, vec![10, 20]);
+        let expected_cost = 0.5 + 0.5 + 1.0 + 7.0 * 0.1;
+        assert!((output.best_path.weight.value() - expected_cost).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_acoustic_scale() -> Result<()> {
+        let decodable = scores(&[1, 2])?;
+        let config = DecoderConfig::default().with_acoustic_scale(0.5);
+        let output = decode(&graph()?, &decodable, &config)?;
+
+        assert_eq!(output.best_path.olabels, vec![10]);
+        assert!((output.best_path.weight.value() - (0.5 + 0.1)).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_not_final() -> Result<()> {
+        let decodable = scores(&[1])?;
+        assert!(decode(&graph()?, &decodable, &DecoderConfig::default()).is_err());
+
+        let config = DecoderConfig::default().with_allow_partial(true);
+        let output = decode(&graph()?, &decodable, &config)?;
+        assert!(!output.reached_final);
+        assert_eq!(output.best_path.olabels, vec![10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_max_active() -> Result<()> {
+        let decodable = scores(&[1, 1, 2, 3, 3, 3, 2])?;
+        let config = DecoderConfig::default().with_max_active(2);
+        let output = decode(&graph()?, &decodable, &config)?;
+        assert_eq!(output.best_path.olabels, vec![10, 20]);
+
+        // With a single active token, the hypothesis going back to the start state is pruned
+        // as soon as the word is finished.
+        let config = DecoderConfig::default().with_max_active(1);
+        let output = decode(&graph()?, &decodable, &config)?;
+        assert_eq!(output.best_path.olabels, vec![10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_lattice() -> Result<()> {
+        let decodable = scores(&[1, 2, 3, 2])?;
+        let config = DecoderConfig::default().with_generate_lattice(true);
+        let output = decode(&graph()?, &decodable, &config)?;
+        let lattice = output.lattice.unwrap();
+
+        assert!(lattice.num_states() > 0);
+        assert!(lattice.start().is_some());
+        let best: VectorFst<TropicalWeight> = shortest_path(&lattice)?;
+        let path = best.paths_iter().next().unwrap();
+        assert_eq!(path.olabels, output.best_path.olabels);
+        assert!(path.weight.approx_equal(output.best_path.weight, 1e-5));
+        // Other hypotheses survive in the lattice.
+        assert!(lattice.paths_iter().count() > 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_nan_log_likelihood() -> Result<()> {
+        let decodable = DenseDecodable::from_rows(vec![vec![f32::NAN, -1.0, -1.0]])?;
+        let err = decode(&graph()?, &decodable, &DecoderConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("NaN"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_negative_epsilon_cycle() -> Result<()> {
+        let mut fst = graph()?;
+        fst.add_tr(0, Tr::new(0, 0, -2.0, 3))?;
+        let decodable = scores(&[1, 2])?;
+        let err = decode(&fst, &decodable, &DecoderConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("epsilon cycle"), "{}", err);
+
+        // Together with the transition from 3 to 0, the cycle has a zero cost.
+        let mut fst = graph()?;
+        fst.add_tr(0, Tr::new(0, 0, -1.0, 3))?;
+        let output = decode(&fst, &decodable, &DecoderConfig::default())?;
+        assert!(output.reached_final);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dense_decodable() -> Result<()> {
+        let decodable = DenseDecodable::new(2, vec![1.0, 2.0, 3.0, 4.0])?;
+        assert_eq!(decodable.num_frames(), 2);
+        assert_eq!(decodable.log_likelihood(1, 1)?, 3.0);
+        assert!(decodable.log_likelihood(0, 0).is_err());
+        assert!(decodable.log_likelihood(0, 3).is_err());
+        assert!(DenseDecodable::new(2, vec![1.0, 2.0, 3.0]).is_err());
+        Ok(())
+    }
+}
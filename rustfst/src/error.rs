@@ -0,0 +1,198 @@
+use std::fmt;
+use std::io;
+
+use crate::algorithms::LimitExceeded;
+use crate::StateId;
+
+/// Typed errors returned by rustfst.
+///
+/// The public APIs return `anyhow::Result` : these errors are wrapped into an `anyhow::Error`
+/// and can be retrieved with [`Error::find`] (or `downcast_ref`) to match on the kind of failure
+/// instead of on the error message. Errors that don't belong to any of these kinds are plain
+/// `anyhow::Error`s.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::Error;
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// let err = fst.set_start(3).unwrap_err();
+/// assert!(matches!(Error::find(&err), Some(Error::InvalidStateId(3))));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input (binary or text FST, symbol table, ...) couldn't be parsed.
+    Parse(String),
+    /// The state doesn't exist in the FST.
+    InvalidStateId(StateId),
+    /// The FST doesn't have the properties required by the algorithm, e.g it must be an acceptor.
+    PropertyViolation(String),
+    /// A symbol table is missing or doesn't contain a label or a symbol.
+    IncompatibleSymbols(String),
+    /// The FST must be sorted on its input or output labels, e.g for composition.
+    NotSorted(String),
+    /// The FST is not functional (an input string is mapped to several output strings),
+    /// e.g when determinizing it with `DeterminizeType::DeterminizeFunctional`.
+    NonFunctional(String),
+    /// One of the `AlgorithmLimits` has been hit.
+    LimitExceeded(LimitExceeded),
+    /// An I/O error occurred while reading or writing a file.
+    Io(io::Error),
+}
+
+impl Error {
+    /// Look for a rustfst `Error` in the chain of causes of an `anyhow::Error`. It is found
+    /// even if context has been attached to the error afterwards.
+    pub fn find(err: &anyhow::Error) -> Option<&Error> {
+        // `downcast_ref` also looks at the context attached with `anyhow`, which `chain` hides.
+        err.downcast_ref::<Error>()
+            .or_else(|| err.chain().find_map(|e| e.downcast_ref::<Error>()))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(msg) => write!(f, "{}", msg),
+            Error::InvalidStateId(state) => write!(f, "State {} doesn't exist", state),
+            Error::PropertyViolation(msg) => write!(f, "{}", msg),
+            Error::IncompatibleSymbols(msg) => write!(f, "{}", msg),
+            Error::NotSorted(msg) => write!(f, "{}", msg),
+            Error::NonFunctional(msg) => write!(f, "{}", msg),
+            Error::LimitExceeded(limit) => write!(f, "{}", limit),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::LimitExceeded(limit) => Some(limit),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<LimitExceeded> for Error {
+    fn from(limit: LimitExceeded) -> Self {
+        Error::LimitExceeded(limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{Context, Result};
+
+    use crate::algorithms::closure::{ClosureFst, ClosureType};
+    use crate::algorithms::compose::compose;
+    use crate::algorithms::determinize::{
+        determinize_with_config, DeterminizeConfig, DeterminizeType,
+    };
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, Fst, MutableFst, SerializableFst};
+    use crate::semirings::TropicalWeight;
+    use crate::Tr;
+
+    #[test]
+    fn test_find_error() -> Result<()> {
+        let err: anyhow::Error = Error::InvalidStateId(2).into();
+        assert_eq!(err.to_string(), "State 2 doesn't exist");
+        assert!(matches!(Error::find(&err), Some(Error::InvalidStateId(2))));
+
+        let err = err.context("While doing something");
+        assert!(matches!(Error::find(&err), Some(Error::InvalidStateId(2))));
+
+        let err = Err::<(), _>(Error::from(io::Error::from(io::ErrorKind::NotFound)))
+            .context("Reading a file")
+            .unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::Io(_))));
+
+        let err = LimitExceeded::MaxStates(3)
+            .into_error()
+            .context("While determinizing");
+        assert!(matches!(
+            Error::find(&err),
+            Some(Error::LimitExceeded(LimitExceeded::MaxStates(3)))
+        ));
+        assert_eq!(
+            err.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::MaxStates(3))
+        );
+
+        assert!(Error::find(&format_err!("Some error")).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_kinds() -> Result<()> {
+        let err = VectorFst::<TropicalWeight>::load(b"not an fst").unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::Parse(_))));
+
+        let dir = tempfile::tempdir()?;
+        let err = VectorFst::<TropicalWeight>::read(dir.path().join("missing.fst")).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::Io(_))));
+
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.set_final(1, 0.0)?;
+        let err = fst.add_tr(2, Tr::new(1, 1, 1.0, 0)).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::InvalidStateId(2))));
+
+        let err = fst.string_paths_iter().err().unwrap();
+        assert!(matches!(
+            Error::find(&err),
+            Some(Error::IncompatibleSymbols(_))
+        ));
+
+        // The start state of a lazy FST is valid, it just hasn't been expanded.
+        let closure: ClosureFst<_, VectorFst<_>> = ClosureFst::new(fst, ClosureType::ClosureStar)?;
+        assert_eq!(closure.start(), Some(0));
+        let err = closure.num_trs(0).unwrap_err();
+        assert!(Error::find(&err).is_none());
+        closure.get_trs(0)?;
+        assert_eq!(closure.num_trs(0)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_algorithm_errors() -> Result<()> {
+        // Not functional : "a" is mapped to both "b" and "c".
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(1, 3, 1.0, 2))?;
+        fst.set_final(1, 0.0)?;
+        fst.set_final(2, 0.0)?;
+
+        let config =
+            DeterminizeConfig::default().with_det_type(DeterminizeType::DeterminizeFunctional);
+        let err = determinize_with_config::<_, _, VectorFst<_>>(&fst, config).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::NonFunctional(_))));
+
+        // The output labels of the first FST are not sorted.
+        let mut fst1 = VectorFst::<TropicalWeight>::new();
+        fst1.add_states(2);
+        fst1.set_start(0)?;
+        fst1.add_tr(0, Tr::new(1, 3, 1.0, 1))?;
+        fst1.add_tr(0, Tr::new(2, 2, 1.0, 1))?;
+        fst1.set_final(1, 0.0)?;
+        let mut fst2 = fst1.clone();
+        fst2.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        let err = compose::<_, _, _, VectorFst<_>, _, _>(fst1, fst2).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::NotSorted(_))));
+        Ok(())
+    }
+}
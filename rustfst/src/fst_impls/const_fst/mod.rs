@@ -12,3 +12,5 @@ pub(super) static CONST_MIN_FILE_VERSION: i32 = 1;
 pub(super) static CONST_ALIGNED_FILE_VERSION: i32 = 1;
 pub(super) static CONST_FILE_VERSION: i32 = 2;
 pub(super) static CONST_ARCH_ALIGNMENT: usize = 16;
+/// Minimum size of a serialized state : four i32, the weight may be empty.
+pub(super) static CONST_MIN_STATE_SIZE: usize = 16;
@@ -4,21 +4,24 @@ use std::sync::Arc;
 use anyhow::Result;
 use itertools::Itertools;
 use nom::bytes::complete::take;
+use nom::combinator::verify;
+use nom::error::ErrorKind;
 use nom::multi::count;
 use nom::IResult;
 
 use crate::fst_impls::const_fst::data_structure::ConstState;
 use crate::fst_impls::const_fst::{
     CONST_ALIGNED_FILE_VERSION, CONST_ARCH_ALIGNMENT, CONST_FILE_VERSION, CONST_MIN_FILE_VERSION,
+    CONST_MIN_STATE_SIZE,
 };
 use crate::fst_impls::ConstFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::{ExpandedFst, Fst, SerializableFst};
 use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
 use crate::parsers::bin_fst::utils_parsing::{
-    parse_bin_fst_tr, parse_final_weight, parse_start_state,
+    parse_bin_fst_tr, parse_final_weight, parse_start_state, MIN_BIN_TR_SIZE,
 };
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::{binary_parse_error, check_count, NomCustomError};
 use crate::parsers::parse_bin_i32;
 use crate::parsers::text_fst::ParsedTextFst;
 use crate::parsers::write_bin_i32;
@@ -31,12 +34,16 @@ impl<W: SerializableSemiring> SerializableFst<W> for ConstFst<W> {
     }
 
     fn load(data: &[u8]) -> Result<Self> {
-        let (_, parsed_fst) = parse_const_fst(data)
-            .map_err(|_| format_err!("Error while parsing binary ConstFst"))?;
+        let (_, parsed_fst) =
+            parse_const_fst(data).map_err(|e| binary_parse_error(e, "ConstFst"))?;
 
         Ok(parsed_fst)
     }
 
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        parse_const_fst(i)
+    }
+
     fn store<O: Write>(&self, mut output: O) -> Result<()> {
         let mut flags = FstFlags::empty();
         if self.input_symbols().is_some() {
@@ -175,14 +182,26 @@ impl<W: SerializableSemiring> SerializableFst<W> for ConstFst<W> {
     }
 }
 
+/// Checks that the trs of a state are within `trs` and that its epsilon counts are correct.
+fn consistent_const_state<W>(state: &ConstState<W>, trs: &[Tr<W>]) -> bool {
+    state
+        .pos
+        .checked_add(state.ntrs)
+        .and_then(|end| trs.get(state.pos..end))
+        .is_some_and(|trs| {
+            trs.iter().filter(|t| t.ilabel == EPS_LABEL).count() == state.niepsilons
+                && trs.iter().filter(|t| t.olabel == EPS_LABEL).count() == state.noepsilons
+        })
+}
+
 fn parse_const_state<W: SerializableSemiring>(
     i: &[u8],
 ) -> IResult<&[u8], ConstState<W>, NomCustomError<&[u8]>> {
     let (i, final_weight) = W::parse_binary(i)?;
-    let (i, pos) = parse_bin_i32(i)?;
-    let (i, ntrs) = parse_bin_i32(i)?;
-    let (i, niepsilons) = parse_bin_i32(i)?;
-    let (i, noepsilons) = parse_bin_i32(i)?;
+    let (i, pos) = verify(parse_bin_i32, |v| *v >= 0)(i)?;
+    let (i, ntrs) = verify(parse_bin_i32, |v| *v >= 0)(i)?;
+    let (i, niepsilons) = verify(parse_bin_i32, |v| *v >= 0)(i)?;
+    let (i, noepsilons) = verify(parse_bin_i32, |v| *v >= 0)(i)?;
 
     Ok((
         i,
@@ -214,14 +233,23 @@ fn parse_const_fst<W: SerializableSemiring>(
     if aligned && hdr.num_states > 0 && !pos.is_multiple_of(CONST_ARCH_ALIGNMENT) {
         i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
     }
-    let (mut i, const_states) = count(parse_const_state, hdr.num_states as usize)(i)?;
+    let num_states = check_count(i, hdr.num_states, CONST_MIN_STATE_SIZE)?;
+    let (mut i, const_states) = count(parse_const_state, num_states)(i)?;
     let pos = stream_len - i.len();
 
     // Align input
     if aligned && hdr.num_trs > 0 && !pos.is_multiple_of(CONST_ARCH_ALIGNMENT) {
         i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
     }
-    let (i, const_trs) = count(parse_bin_fst_tr, hdr.num_trs as usize)(i)?;
+    let num_trs = check_count(i, hdr.num_trs, MIN_BIN_TR_SIZE)?;
+    let (i, const_trs) = count(parse_bin_fst_tr(num_states), num_trs)(i)?;
+
+    if !const_states
+        .iter()
+        .all(|s| consistent_const_state(s, &const_trs))
+    {
+        return Err(nom::Err::Error(NomCustomError::Nom(i, ErrorKind::Verify)));
+    }
 
     Ok((
         i,
@@ -0,0 +1,241 @@
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{Error, StateId, SymbolTable, Tr, Trs, TrsConst, TrsVec};
+
+/// Object-safe counterpart of `ExpandedFst`, implemented for every `ExpandedFst` that can be
+/// shared across threads.
+pub(crate) trait DynFstImpl<W: Semiring>: Debug + Send + Sync {
+    fn dyn_start(&self) -> Option<StateId>;
+    unsafe fn dyn_final_weight_unchecked(&self, state: StateId) -> Option<W>;
+    unsafe fn dyn_num_trs_unchecked(&self, state: StateId) -> usize;
+    unsafe fn dyn_get_trs_unchecked(&self, state: StateId) -> DynTrs<W>;
+    fn dyn_properties(&self) -> FstProperties;
+    fn dyn_num_input_epsilons(&self, state: StateId) -> Result<usize>;
+    fn dyn_num_output_epsilons(&self, state: StateId) -> Result<usize>;
+    fn dyn_num_states(&self) -> usize;
+    fn dyn_input_symbols(&self) -> Option<&Arc<SymbolTable>>;
+    fn dyn_output_symbols(&self) -> Option<&Arc<SymbolTable>>;
+    fn dyn_set_input_symbols(&mut self, symt: Arc<SymbolTable>);
+    fn dyn_set_output_symbols(&mut self, symt: Arc<SymbolTable>);
+    fn dyn_take_input_symbols(&mut self) -> Option<Arc<SymbolTable>>;
+    fn dyn_take_output_symbols(&mut self) -> Option<Arc<SymbolTable>>;
+    fn dyn_clone(&self) -> Box<dyn DynFstImpl<W>>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<W: Semiring, F: ExpandedFst<W> + Send + Sync + 'static> DynFstImpl<W> for F {
+    fn dyn_start(&self) -> Option<StateId> {
+        self.start()
+    }
+
+    unsafe fn dyn_final_weight_unchecked(&self, state: StateId) -> Option<W> {
+        self.final_weight_unchecked(state)
+    }
+
+    unsafe fn dyn_num_trs_unchecked(&self, state: StateId) -> usize {
+        self.num_trs_unchecked(state)
+    }
+
+    unsafe fn dyn_get_trs_unchecked(&self, state: StateId) -> DynTrs<W> {
+        DynTrs::from_trs(self.get_trs_unchecked(state))
+    }
+
+    fn dyn_properties(&self) -> FstProperties {
+        self.properties()
+    }
+
+    fn dyn_num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.num_input_epsilons(state)
+    }
+
+    fn dyn_num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.num_output_epsilons(state)
+    }
+
+    fn dyn_num_states(&self) -> usize {
+        self.num_states()
+    }
+
+    fn dyn_input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.input_symbols()
+    }
+
+    fn dyn_output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.output_symbols()
+    }
+
+    fn dyn_set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.set_input_symbols(symt)
+    }
+
+    fn dyn_set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.set_output_symbols(symt)
+    }
+
+    fn dyn_take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.take_input_symbols()
+    }
+
+    fn dyn_take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.take_output_symbols()
+    }
+
+    fn dyn_clone(&self) -> Box<dyn DynFstImpl<W>> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Object-safe counterpart of `Trs`, implemented for every `Trs`.
+pub(crate) trait DynTrsImpl<W: Semiring>: Debug {
+    fn dyn_trs(&self) -> &[Tr<W>];
+    fn dyn_to_trs_vec(&self) -> TrsVec<W>;
+    fn dyn_shallow_clone(&self) -> Box<dyn DynTrsImpl<W>>;
+}
+
+impl<W: Semiring, T: Trs<W> + 'static> DynTrsImpl<W> for T {
+    fn dyn_trs(&self) -> &[Tr<W>] {
+        self.trs()
+    }
+
+    fn dyn_to_trs_vec(&self) -> TrsVec<W> {
+        self.to_trs_vec()
+    }
+
+    fn dyn_shallow_clone(&self) -> Box<dyn DynTrsImpl<W>> {
+        Box::new(self.shallow_clone())
+    }
+}
+
+/// Trs of a state of a `DynFst` : the trs of the underlying FST with their type erased.
+/// The transitions are not copied.
+#[derive(Debug)]
+pub struct DynTrs<W: Semiring>(pub(crate) DynTrsInner<W>);
+
+/// The trs of `VectorFst` and `ConstFst` are stored as is, only the other types are boxed.
+#[derive(Debug)]
+pub(crate) enum DynTrsInner<W: Semiring> {
+    Vec(TrsVec<W>),
+    Const(TrsConst<W>),
+    Boxed(Box<dyn DynTrsImpl<W>>),
+}
+
+impl<W: Semiring> DynTrs<W> {
+    fn from_trs<T: Trs<W> + 'static>(trs: T) -> Self {
+        let mut trs = Some(trs);
+        let any_trs = &mut trs as &mut dyn Any;
+        if let Some(trs) = any_trs.downcast_mut::<Option<TrsVec<W>>>() {
+            return DynTrs(DynTrsInner::Vec(trs.take().unwrap()));
+        }
+        if let Some(trs) = any_trs.downcast_mut::<Option<TrsConst<W>>>() {
+            return DynTrs(DynTrsInner::Const(trs.take().unwrap()));
+        }
+        DynTrs(DynTrsInner::Boxed(Box::new(trs.unwrap())))
+    }
+}
+
+impl<W: Semiring> Deref for DynTrs<W> {
+    type Target = [Tr<W>];
+
+    fn deref(&self) -> &Self::Target {
+        self.trs()
+    }
+}
+
+impl<W: Semiring> Trs<W> for DynTrs<W> {
+    fn trs(&self) -> &[Tr<W>] {
+        match &self.0 {
+            DynTrsInner::Vec(trs) => trs.trs(),
+            DynTrsInner::Const(trs) => trs.trs(),
+            DynTrsInner::Boxed(trs) => trs.dyn_trs(),
+        }
+    }
+
+    fn to_trs_vec(&self) -> TrsVec<W> {
+        match &self.0 {
+            DynTrsInner::Vec(trs) => trs.to_trs_vec(),
+            DynTrsInner::Const(trs) => trs.to_trs_vec(),
+            DynTrsInner::Boxed(trs) => trs.dyn_to_trs_vec(),
+        }
+    }
+
+    fn shallow_clone(&self) -> Self {
+        DynTrs(match &self.0 {
+            DynTrsInner::Vec(trs) => DynTrsInner::Vec(trs.shallow_clone()),
+            DynTrsInner::Const(trs) => DynTrsInner::Const(trs.shallow_clone()),
+            DynTrsInner::Boxed(trs) => DynTrsInner::Boxed(trs.dyn_shallow_clone()),
+        })
+    }
+}
+
+/// FST whose implementation is selected at runtime. The underlying FST is boxed and
+/// every call is dynamically dispatched, which allows manipulating FSTs of different
+/// types (e.g `VectorFst` and `ConstFst`) with the same type. It can be used in all
+/// the algorithms taking an `ExpandedFst` as input. Like `VectorFst` and `ConstFst`,
+/// a `DynFst` can be shared across threads.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::fst_impls::DynFst;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(2);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+/// fst.set_final(1, 0.0)?;
+/// let const_fst: ConstFst<_> = fst.clone().into();
+///
+/// let fsts = vec![DynFst::new(fst), DynFst::new(const_fst)];
+/// for dyn_fst in fsts.iter() {
+///     let inverted: VectorFst<_> = invert_fst(dyn_fst)?;
+///     assert_eq!(inverted.get_trs(0)?.trs(), &[Tr::new(2, 1, 1.0, 1)]);
+/// }
+/// assert!(fsts[1].downcast_ref::<ConstFst<_>>().is_some());
+/// # Ok(())
+/// # }
+/// fn invert_fst<F: ExpandedFst<TropicalWeight>>(fst: &F) -> Result<VectorFst<TropicalWeight>> {
+///     let mut res: VectorFst<_> = fst_convert_from_ref(fst);
+///     invert(&mut res);
+///     Ok(res)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DynFst<W: Semiring>(pub(crate) Box<dyn DynFstImpl<W>>);
+
+impl<W: Semiring> DynFst<W> {
+    /// Erases the type of `fst`.
+    pub fn new<F: ExpandedFst<W> + Send + Sync + 'static>(fst: F) -> Self {
+        DynFst(Box::new(fst))
+    }
+
+    /// Returns a reference to the underlying FST if it is of type `F`.
+    pub fn downcast_ref<F: ExpandedFst<W> + 'static>(&self) -> Option<&F> {
+        self.0.as_any().downcast_ref::<F>()
+    }
+
+    pub(crate) fn check_state(&self, state: StateId) -> Result<()> {
+        if (state as usize) >= self.0.dyn_num_states() {
+            return Err(Error::InvalidStateId(state).into());
+        }
+        Ok(())
+    }
+}
+
+impl<W: Semiring> Clone for DynFst<W> {
+    fn clone(&self) -> Self {
+        DynFst(self.0.dyn_clone())
+    }
+}
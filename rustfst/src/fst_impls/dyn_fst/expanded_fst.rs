@@ -0,0 +1,22 @@
+use crate::fst_impls::DynFst;
+use crate::fst_traits::{CoreFst, ExpandedFst};
+use crate::semirings::Semiring;
+use crate::Trs;
+
+impl<W: Semiring + 'static> ExpandedFst<W> for DynFst<W> {
+    fn num_states(&self) -> usize {
+        self.0.dyn_num_states()
+    }
+}
+
+impl<W: Semiring + 'static> PartialEq for DynFst<W> {
+    fn eq(&self, other: &Self) -> bool {
+        // Indended: Doesn't check properties and symbol tables.
+        self.num_states() == other.num_states()
+            && self.start() == other.start()
+            && self.states_range().all(|s| unsafe {
+                self.final_weight_unchecked(s) == other.final_weight_unchecked(s)
+                    && self.get_trs_unchecked(s).trs() == other.get_trs_unchecked(s).trs()
+            })
+    }
+}
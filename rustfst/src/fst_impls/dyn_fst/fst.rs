@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::fst_impls::dyn_fst::{DynFst, DynTrs};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, Fst};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable};
+
+impl<W: Semiring + 'static> Fst<W> for DynFst<W> {
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.dyn_input_symbols()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.0.dyn_output_symbols()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.dyn_set_input_symbols(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.0.dyn_set_output_symbols(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.dyn_take_input_symbols()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.0.dyn_take_output_symbols()
+    }
+}
+
+impl<W: Semiring> CoreFst<W> for DynFst<W> {
+    type TRS = DynTrs<W>;
+
+    fn start(&self) -> Option<StateId> {
+        self.0.dyn_start()
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.check_state(state_id)?;
+        Ok(unsafe { self.final_weight_unchecked(state_id) })
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        self.0.dyn_final_weight_unchecked(state_id)
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.check_state(s)?;
+        Ok(unsafe { self.num_trs_unchecked(s) })
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        self.0.dyn_num_trs_unchecked(s)
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.check_state(state_id)?;
+        Ok(unsafe { self.get_trs_unchecked(state_id) })
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        self.0.dyn_get_trs_unchecked(state_id)
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.0.dyn_properties()
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.dyn_num_input_epsilons(state)
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.0.dyn_num_output_epsilons(state)
+    }
+}
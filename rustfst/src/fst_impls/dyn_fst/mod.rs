@@ -0,0 +1,9 @@
+pub use data_structure::{DynFst, DynTrs};
+pub use read::{load_any_fst, read_any_fst};
+
+mod data_structure;
+mod expanded_fst;
+mod fst;
+mod iterators;
+mod read;
+mod test;
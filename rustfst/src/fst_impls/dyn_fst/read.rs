@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::fst_impls::{ConstFst, DynFst, VectorFst};
+use crate::fst_traits::SerializableFst;
+use crate::parsers::bin_fst::fst_header::FstHeader;
+use crate::semirings::SerializableSemiring;
+use crate::Error;
+
+/// Loads an FST from binary format data without knowing its type beforehand.
+/// The type is read from the header and the matching implementation is used to load the FST.
+pub fn load_any_fst<W: SerializableSemiring + Send + 'static>(data: &[u8]) -> Result<DynFst<W>> {
+    let (_, fst_type) = FstHeader::parse_fst_type(data)
+        .map_err(|_| Error::Parse("Error while parsing the header of a binary FST".to_string()))?;
+
+    if fst_type == VectorFst::<W>::fst_type() {
+        Ok(DynFst::new(VectorFst::<W>::load(data)?))
+    } else if fst_type == ConstFst::<W>::fst_type() {
+        Ok(DynFst::new(ConstFst::<W>::load(data)?))
+    } else {
+        Err(Error::Parse(format!("Unsupported FST type : {}", fst_type)).into())
+    }
+}
+
+/// Loads an FST from a file in binary format without knowing its type beforehand.
+///
+/// # Example
+/// ```
+/// # use rustfst::prelude::*;
+/// # use rustfst::fst_impls::read_any_fst;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// # let dir = tempfile::tempdir()?;
+/// # let path = dir.path().join("fst.bin");
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(2);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+/// fst.set_final(1, 0.0)?;
+/// let const_fst: ConstFst<_> = fst.into();
+/// const_fst.write(&path)?;
+///
+/// let loaded = read_any_fst::<TropicalWeight, _>(&path)?;
+/// assert_eq!(loaded.downcast_ref::<ConstFst<_>>(), Some(&const_fst));
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_any_fst<W: SerializableSemiring + Send + 'static, P: AsRef<Path>>(
+    path_bin_fst: P,
+) -> Result<DynFst<W>> {
+    let data: Vec<u8> = std::fs::read(path_bin_fst.as_ref())
+        .map_err(Error::Io)
+        .with_context(|| format!("Can't open Fst binary file : {:?}", path_bin_fst.as_ref()))?;
+    load_any_fst(&data)
+}
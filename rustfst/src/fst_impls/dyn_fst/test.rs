@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+
+    use crate::algorithms::compose::compose;
+    use crate::algorithms::tr_compares::ILabelCompare;
+    use crate::algorithms::{fst_convert_from_ref, tr_sort};
+    use crate::fst_impls::{load_any_fst, read_any_fst, ConstFst, DynFst, VectorFst};
+    use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst, SerializableFst};
+    use crate::semirings::TropicalWeight;
+    use crate::tr::Tr;
+    use crate::{symt, SymbolTable, Trs};
+
+    fn build_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(0, 3, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 0, 3.0, 2))?;
+        fst.set_final(2, 0.5)?;
+        Ok(fst)
+    }
+
+    fn check_same<F: ExpandedFst<TropicalWeight>>(
+        dyn_fst: &DynFst<TropicalWeight>,
+        fst: &F,
+    ) -> Result<()> {
+        assert_eq!(dyn_fst.num_states(), fst.num_states());
+        assert_eq!(dyn_fst.start(), fst.start());
+        assert_eq!(dyn_fst.properties(), fst.properties());
+        for s in fst.states_range() {
+            assert_eq!(dyn_fst.final_weight(s)?, fst.final_weight(s)?);
+            assert_eq!(dyn_fst.num_trs(s)?, fst.num_trs(s)?);
+            assert_eq!(dyn_fst.get_trs(s)?.trs(), fst.get_trs(s)?.trs());
+            assert_eq!(dyn_fst.num_input_epsilons(s)?, fst.num_input_epsilons(s)?);
+            assert_eq!(dyn_fst.num_output_epsilons(s)?, fst.num_output_epsilons(s)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_fst_delegates() -> Result<()> {
+        let fst = build_fst()?;
+        let const_fst: ConstFst<_> = fst.clone().into();
+
+        check_same(&DynFst::new(fst.clone()), &fst)?;
+        check_same(&DynFst::new(const_fst.clone()), &const_fst)?;
+        assert!(DynFst::new(fst.clone()).get_trs(3).is_err());
+
+        // Equality only depends on the content.
+        assert_eq!(DynFst::new(fst.clone()), DynFst::new(const_fst));
+        let dyn_fst = DynFst::new(fst.clone());
+        assert_eq!(dyn_fst.clone(), dyn_fst);
+        let converted: VectorFst<_> = fst_convert_from_ref(&dyn_fst);
+        assert_eq!(converted, fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_fst_downcast() -> Result<()> {
+        let fst = build_fst()?;
+        let dyn_fst = DynFst::new(fst.clone());
+        assert_eq!(dyn_fst.downcast_ref::<VectorFst<_>>(), Some(&fst));
+        assert!(dyn_fst.downcast_ref::<ConstFst<_>>().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_fst_send_sync() -> Result<()> {
+        let fst = build_fst()?;
+        let dyn_fst = Arc::new(DynFst::new(fst.clone()));
+        let handle = {
+            let dyn_fst = Arc::clone(&dyn_fst);
+            std::thread::spawn(move || dyn_fst.num_states())
+        };
+        assert_eq!(handle.join().unwrap(), fst.num_states());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_fst_symbol_tables() -> Result<()> {
+        let mut dyn_fst = DynFst::new(build_fst()?);
+        let symt = Arc::new(symt!["a", "b", "c"]);
+        dyn_fst.set_input_symbols(Arc::clone(&symt));
+        assert_eq!(dyn_fst.input_symbols(), Some(&symt));
+        assert!(dyn_fst.output_symbols().is_none());
+        assert_eq!(dyn_fst.take_input_symbols(), Some(symt));
+        assert!(dyn_fst.input_symbols().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dyn_fst_compose() -> Result<()> {
+        let mut fst = build_fst()?;
+        tr_sort(&mut fst, ILabelCompare {});
+        let const_fst: ConstFst<_> = fst.clone().into();
+
+        let ref_composed: VectorFst<_> = compose(fst.clone(), fst.clone())?;
+        let composed: VectorFst<_> = compose(DynFst::new(const_fst), DynFst::new(fst.clone()))?;
+        assert_eq!(composed, ref_composed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_any_fst() -> Result<()> {
+        let fst = build_fst()?;
+        let const_fst: ConstFst<_> = fst.clone().into();
+
+        let mut data = vec![];
+        fst.store(&mut data)?;
+        let loaded = load_any_fst::<TropicalWeight>(&data)?;
+        assert_eq!(loaded.downcast_ref::<VectorFst<_>>(), Some(&fst));
+
+        let mut data = vec![];
+        const_fst.store(&mut data)?;
+        let loaded = load_any_fst::<TropicalWeight>(&data)?;
+        assert_eq!(loaded.downcast_ref::<ConstFst<_>>(), Some(&const_fst));
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("fst.bin");
+        fst.write(&path)?;
+        let loaded = read_any_fst::<TropicalWeight, _>(&path)?;
+        check_same(&loaded, &fst)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_any_fst_errors() -> Result<()> {
+        assert!(load_any_fst::<TropicalWeight>(&[1, 2, 3]).is_err());
+
+        // Valid header of an unknown type of FST.
+        let mut data = vec![];
+        build_fst()?.store(&mut data)?;
+        let mut data_unknown = data[..8].to_vec();
+        data_unknown.extend_from_slice(b"foobar");
+        data_unknown.extend_from_slice(&data[14..]);
+        let err = load_any_fst::<TropicalWeight>(&data_unknown).unwrap_err();
+        assert!(err.to_string().contains("foobar"));
+
+        assert!(read_any_fst::<TropicalWeight, _>("/non/existing/path.fst").is_err());
+        Ok(())
+    }
+}
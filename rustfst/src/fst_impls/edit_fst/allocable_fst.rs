@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::fst_impls::EditFst;
+use crate::fst_traits::{AllocableFst, CoreFst, ExpandedFst};
+use crate::semirings::Semiring;
+use crate::StateId;
+
+impl<W: Semiring, F: ExpandedFst<W> + 'static> AllocableFst<W> for EditFst<W, F> {
+    fn reserve_trs(&mut self, source: StateId, additional: usize) -> Result<()> {
+        self.edited_state_mut(source)?;
+        unsafe { self.reserve_trs_unchecked(source, additional) };
+        Ok(())
+    }
+
+    unsafe fn reserve_trs_unchecked(&mut self, source: StateId, additional: usize) {
+        let trs = &mut self.edited_state_mut_unchecked(source).trs;
+        Arc::make_mut(&mut trs.0).reserve(additional)
+    }
+
+    fn reserve_states(&mut self, additional: usize) {
+        self.edited_states.reserve(additional);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.edited_states.shrink_to_fit();
+        self.edited_final_weights.shrink_to_fit();
+        for state in self.edited_states.values_mut() {
+            Arc::make_mut(&mut state.trs.0).shrink_to_fit();
+        }
+    }
+
+    fn shrink_to_fit_states(&mut self) {
+        self.edited_states.shrink_to_fit();
+        self.edited_final_weights.shrink_to_fit();
+    }
+
+    fn shrink_to_fit_trs(&mut self, source: StateId) -> Result<()> {
+        self.check_state(source)?;
+        unsafe { self.shrink_to_fit_trs_unchecked(source) };
+        Ok(())
+    }
+
+    unsafe fn shrink_to_fit_trs_unchecked(&mut self, source: StateId) {
+        // States of the wrapped FST are left untouched.
+        if let Some(state) = self.edited_states.get_mut(&source) {
+            Arc::make_mut(&mut state.trs.0).shrink_to_fit()
+        }
+    }
+
+    fn states_capacity(&self) -> usize {
+        self.num_wrapped_states + self.edited_states.capacity()
+    }
+
+    fn trs_capacity(&self, source: StateId) -> Result<usize> {
+        self.check_state(source)?;
+        Ok(unsafe { self.trs_capacity_unchecked(source) })
+    }
+
+    unsafe fn trs_capacity_unchecked(&self, source: StateId) -> usize {
+        match self.edited_states.get(&source) {
+            Some(state) => state.trs.0.capacity(),
+            None => self.num_trs_unchecked(source),
+        }
+    }
+}
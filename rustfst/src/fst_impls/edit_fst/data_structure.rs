@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::fst_impls::vector_fst::VectorFstState;
+use crate::fst_properties::mutable_properties::set_final_properties;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, ExpandedFst};
+use crate::semirings::Semiring;
+use crate::symbol_table::SymbolTable;
+use crate::{Error, StateId, Tr, Trs, TrsVec, EPS_LABEL};
+
+/// Mutable FST wrapping an immutable `ExpandedFst` and recording the modifications
+/// in an overlay. The wrapped FST is never copied : a state is only copied to the overlay
+/// the first time its transitions are modified. The final weights of the wrapped states are
+/// stored separately so that changing them doesn't require copying the transitions.
+///
+/// The cost of a modification is therefore proportional to the size of the edited states
+/// and not to the size of the whole FST. The only exception is the deletion of states which
+/// requires renumbering every state : all the states are then copied to the overlay.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use rustfst::prelude::*;
+/// # use rustfst::fst_impls::EditFst;
+/// # use rustfst::algorithms::fst_convert_from_ref;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut fst = VectorFst::<TropicalWeight>::new();
+/// fst.add_states(2);
+/// fst.set_start(0)?;
+/// fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+/// fst.set_final(1, 0.0)?;
+/// let const_fst: Arc<ConstFst<_>> = Arc::new(fst.into());
+///
+/// let mut edit_fst = EditFst::from_fst(Arc::clone(&const_fst));
+/// let s = edit_fst.add_state();
+/// edit_fst.add_tr(1, Tr::new(2, 2, 0.5, s))?;
+/// edit_fst.set_final(s, 1.0)?;
+///
+/// // Only the state 1 has been copied, next to the added state.
+/// assert_eq!(edit_fst.num_edited_states(), 2);
+/// assert_eq!(const_fst.num_trs(1)?, 0);
+///
+/// let edited: VectorFst<_> = fst_convert_from_ref(&edit_fst);
+/// let edited: ConstFst<_> = edited.into();
+/// assert_eq!(edited.num_states(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditFst<W: Semiring, F> {
+    pub(crate) wrapped: Option<Arc<F>>,
+    pub(crate) num_wrapped_states: usize,
+    // States of the wrapped FST that have been modified and states added since.
+    pub(crate) edited_states: HashMap<StateId, VectorFstState<W>>,
+    // Modified final weights of the states of the wrapped FST that are not in `edited_states`.
+    pub(crate) edited_final_weights: HashMap<StateId, Option<W>>,
+    pub(crate) num_states: usize,
+    pub(crate) start_state: Option<StateId>,
+    pub(crate) isymt: Option<Arc<SymbolTable>>,
+    pub(crate) osymt: Option<Arc<SymbolTable>>,
+    pub(crate) properties: FstProperties,
+}
+
+/// Trs of a state of an `EditFst` : either those of the wrapped FST or those of the overlay.
+#[derive(Debug)]
+pub enum EditFstTrs<W: Semiring, T: Trs<W>> {
+    Wrapped(T),
+    Edited(TrsVec<W>),
+}
+
+impl<W: Semiring, T: Trs<W>> Deref for EditFstTrs<W, T> {
+    type Target = [Tr<W>];
+
+    fn deref(&self) -> &Self::Target {
+        self.trs()
+    }
+}
+
+impl<W: Semiring, T: Trs<W>> Trs<W> for EditFstTrs<W, T> {
+    fn trs(&self) -> &[Tr<W>] {
+        match self {
+            EditFstTrs::Wrapped(trs) => trs.trs(),
+            EditFstTrs::Edited(trs) => trs.trs(),
+        }
+    }
+
+    fn to_trs_vec(&self) -> TrsVec<W> {
+        match self {
+            EditFstTrs::Wrapped(trs) => trs.to_trs_vec(),
+            EditFstTrs::Edited(trs) => trs.shallow_clone(),
+        }
+    }
+
+    fn shallow_clone(&self) -> Self {
+        match self {
+            EditFstTrs::Wrapped(trs) => EditFstTrs::Wrapped(trs.shallow_clone()),
+            EditFstTrs::Edited(trs) => EditFstTrs::Edited(trs.shallow_clone()),
+        }
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>> EditFst<W, F> {
+    /// Creates an `EditFst` with no modification on top of `fst`.
+    pub fn from_fst(fst: Arc<F>) -> Self {
+        Self {
+            num_wrapped_states: fst.num_states(),
+            edited_states: HashMap::new(),
+            edited_final_weights: HashMap::new(),
+            num_states: fst.num_states(),
+            start_state: fst.start(),
+            isymt: fst.input_symbols().cloned(),
+            osymt: fst.output_symbols().cloned(),
+            properties: fst.properties(),
+            wrapped: Some(fst),
+        }
+    }
+
+    /// Returns the wrapped FST, if any.
+    pub fn wrapped(&self) -> Option<&Arc<F>> {
+        self.wrapped.as_ref()
+    }
+
+    /// Number of states stored in the overlay, either because they have been
+    /// modified or because they have been added.
+    pub fn num_edited_states(&self) -> usize {
+        self.edited_states.len()
+    }
+
+    pub(crate) fn check_state(&self, state: StateId) -> anyhow::Result<()> {
+        if (state as usize) >= self.num_states {
+            return Err(Error::InvalidStateId(state).into());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_wrapped(&self, state: StateId) -> bool {
+        (state as usize) < self.num_wrapped_states && !self.edited_states.contains_key(&state)
+    }
+
+    /// Copies a state of the wrapped FST to the overlay if needed and returns it.
+    pub(crate) fn edited_state_mut(
+        &mut self,
+        state: StateId,
+    ) -> anyhow::Result<&mut VectorFstState<W>> {
+        self.check_state(state)?;
+        if self.is_wrapped(state) {
+            // Checked : only states of the wrapped FST are not in the overlay.
+            let wrapped = self.wrapped.as_ref().unwrap();
+            let trs = wrapped.get_trs(state)?.to_trs_vec();
+            let niepsilons = wrapped.num_input_epsilons(state)?;
+            let noepsilons = wrapped.num_output_epsilons(state)?;
+            let final_weight = match self.edited_final_weights.remove(&state) {
+                Some(final_weight) => final_weight,
+                None => wrapped.final_weight(state)?,
+            };
+            let edited_state = VectorFstState {
+                final_weight,
+                trs,
+                niepsilons,
+                noepsilons,
+            };
+            self.edited_states.insert(state, edited_state);
+        }
+        Ok(self.edited_states.get_mut(&state).unwrap())
+    }
+
+    /// Same as `edited_state_mut` for a valid state. The numbers of epsilons are counted
+    /// from the transitions so that nothing can fail.
+    pub(crate) unsafe fn edited_state_mut_unchecked(
+        &mut self,
+        state: StateId,
+    ) -> &mut VectorFstState<W> {
+        if self.is_wrapped(state) {
+            // Checked : only states of the wrapped FST are not in the overlay.
+            let wrapped = self.wrapped.as_ref().unwrap();
+            let final_weight = match self.edited_final_weights.remove(&state) {
+                Some(final_weight) => final_weight,
+                None => wrapped.final_weight_unchecked(state),
+            };
+            let trs = wrapped.get_trs_unchecked(state).to_trs_vec();
+            let niepsilons = trs.trs().iter().filter(|tr| tr.ilabel == EPS_LABEL).count();
+            let noepsilons = trs.trs().iter().filter(|tr| tr.olabel == EPS_LABEL).count();
+            let edited_state = VectorFstState {
+                final_weight,
+                trs,
+                niepsilons,
+                noepsilons,
+            };
+            self.edited_states.insert(state, edited_state);
+        }
+        self.edited_states.get_mut(&state).unwrap()
+    }
+
+    /// Replaces the final weight of a state without copying its trs and returns the previous one.
+    pub(crate) unsafe fn replace_final_weight_unchecked(
+        &mut self,
+        state: StateId,
+        final_weight: Option<W>,
+    ) -> Option<W> {
+        let old_final_weight = self.final_weight_unchecked(state);
+        self.properties = set_final_properties(
+            self.properties,
+            old_final_weight.as_ref(),
+            final_weight.as_ref(),
+        );
+        match self.edited_states.get_mut(&state) {
+            Some(edited_state) => edited_state.final_weight = final_weight,
+            None => {
+                self.edited_final_weights.insert(state, final_weight);
+            }
+        };
+        old_final_weight
+    }
+
+    /// Copies all the states of the wrapped FST to the overlay.
+    pub(crate) fn edit_all_states(&mut self) -> anyhow::Result<()> {
+        for state in 0..(self.num_wrapped_states as StateId) {
+            self.edited_state_mut(state)?;
+        }
+        self.wrapped = None;
+        self.num_wrapped_states = 0;
+        self.edited_final_weights.clear();
+        Ok(())
+    }
+}
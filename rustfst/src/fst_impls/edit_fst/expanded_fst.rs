@@ -0,0 +1,22 @@
+use crate::fst_impls::EditFst;
+use crate::fst_traits::{CoreFst, ExpandedFst};
+use crate::semirings::Semiring;
+use crate::Trs;
+
+impl<W: Semiring, F: ExpandedFst<W> + 'static> ExpandedFst<W> for EditFst<W, F> {
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W> + 'static> PartialEq for EditFst<W, F> {
+    fn eq(&self, other: &Self) -> bool {
+        // Indended: Doesn't check properties and symbol tables.
+        self.num_states == other.num_states
+            && self.start_state == other.start_state
+            && self.states_range().all(|s| unsafe {
+                self.final_weight_unchecked(s) == other.final_weight_unchecked(s)
+                    && self.get_trs_unchecked(s).trs() == other.get_trs_unchecked(s).trs()
+            })
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::fst_impls::edit_fst::{EditFst, EditFstTrs};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{CoreFst, ExpandedFst, Fst};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable, Trs};
+
+impl<W: Semiring, F: ExpandedFst<W> + 'static> Fst<W> for EditFst<W, F> {
+    fn input_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.isymt.as_ref()
+    }
+
+    fn output_symbols(&self) -> Option<&Arc<SymbolTable>> {
+        self.osymt.as_ref()
+    }
+
+    fn set_input_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.isymt = Some(symt)
+    }
+
+    fn set_output_symbols(&mut self, symt: Arc<SymbolTable>) {
+        self.osymt = Some(symt)
+    }
+
+    fn take_input_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.isymt.take()
+    }
+
+    fn take_output_symbols(&mut self) -> Option<Arc<SymbolTable>> {
+        self.osymt.take()
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>> CoreFst<W> for EditFst<W, F> {
+    type TRS = EditFstTrs<W, F::TRS>;
+
+    fn start(&self) -> Option<StateId> {
+        self.start_state
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<W>> {
+        self.check_state(state_id)?;
+        Ok(unsafe { self.final_weight_unchecked(state_id) })
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<W> {
+        if let Some(state) = self.edited_states.get(&state_id) {
+            state.final_weight.clone()
+        } else if let Some(final_weight) = self.edited_final_weights.get(&state_id) {
+            final_weight.clone()
+        } else {
+            self.wrapped
+                .as_ref()
+                .unwrap_unchecked()
+                .final_weight_unchecked(state_id)
+        }
+    }
+
+    fn num_trs(&self, s: StateId) -> Result<usize> {
+        self.check_state(s)?;
+        Ok(unsafe { self.num_trs_unchecked(s) })
+    }
+
+    unsafe fn num_trs_unchecked(&self, s: StateId) -> usize {
+        match self.edited_states.get(&s) {
+            Some(state) => state.trs.len(),
+            None => self
+                .wrapped
+                .as_ref()
+                .unwrap_unchecked()
+                .num_trs_unchecked(s),
+        }
+    }
+
+    fn get_trs(&self, state_id: StateId) -> Result<Self::TRS> {
+        self.check_state(state_id)?;
+        Ok(unsafe { self.get_trs_unchecked(state_id) })
+    }
+
+    unsafe fn get_trs_unchecked(&self, state_id: StateId) -> Self::TRS {
+        match self.edited_states.get(&state_id) {
+            // Data is not copied, only Arc
+            Some(state) => EditFstTrs::Edited(state.trs.shallow_clone()),
+            None => EditFstTrs::Wrapped(
+                self.wrapped
+                    .as_ref()
+                    .unwrap_unchecked()
+                    .get_trs_unchecked(state_id),
+            ),
+        }
+    }
+
+    fn properties(&self) -> FstProperties {
+        self.properties
+    }
+
+    fn num_input_epsilons(&self, state: StateId) -> Result<usize> {
+        self.check_state(state)?;
+        match self.edited_states.get(&state) {
+            Some(s) => Ok(s.niepsilons),
+            None => self.wrapped.as_ref().unwrap().num_input_epsilons(state),
+        }
+    }
+
+    fn num_output_epsilons(&self, state: StateId) -> Result<usize> {
+        self.check_state(state)?;
+        match self.edited_states.get(&state) {
+            Some(s) => Ok(s.noepsilons),
+            None => self.wrapped.as_ref().unwrap().num_output_epsilons(state),
+        }
+    }
+}
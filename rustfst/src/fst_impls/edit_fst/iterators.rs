@@ -0,0 +1,51 @@
+use std::ops::Range;
+
+use crate::fst_impls::EditFst;
+use crate::fst_traits::{
+    CoreFst, ExpandedFst, FstIntoIterator, FstIterData, FstIterator, StateIterator,
+};
+use crate::semirings::Semiring;
+use crate::{StateId, Tr, Trs};
+
+impl<'a, W: Semiring, F: ExpandedFst<W>> StateIterator<'a> for EditFst<W, F> {
+    type Iter = Range<StateId>;
+    fn states_iter(&'a self) -> Self::Iter {
+        0..(self.num_states as StateId)
+    }
+}
+
+impl<W: Semiring, F: ExpandedFst<W>> FstIntoIterator<W> for EditFst<W, F> {
+    type TrsIter = std::vec::IntoIter<Tr<W>>;
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::TrsIter>>>;
+
+    fn fst_into_iter(self) -> Self::FstIter {
+        let data: Vec<_> = self
+            .states_iter()
+            .map(|state_id| unsafe {
+                let trs = self.get_trs_unchecked(state_id).trs().to_vec();
+                FstIterData {
+                    state_id,
+                    num_trs: trs.len(),
+                    trs: trs.into_iter(),
+                    final_weight: self.final_weight_unchecked(state_id),
+                }
+            })
+            .collect();
+        Box::new(data.into_iter())
+    }
+}
+
+impl<'a, W: Semiring, F: ExpandedFst<W> + 'a> FstIterator<'a, W> for EditFst<W, F> {
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::TRS>> + 'a>;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        Box::new(self.states_iter().map(move |state_id| unsafe {
+            FstIterData {
+                state_id,
+                trs: self.get_trs_unchecked(state_id),
+                final_weight: self.final_weight_unchecked(state_id),
+                num_trs: self.num_trs_unchecked(state_id),
+            }
+        }))
+    }
+}
@@ -0,0 +1,9 @@
+pub use data_structure::{EditFst, EditFstTrs};
+
+mod allocable_fst;
+mod data_structure;
+mod expanded_fst;
+mod fst;
+mod iterators;
+mod mutable_fst;
+mod test;
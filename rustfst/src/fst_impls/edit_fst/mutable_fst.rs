@@ -0,0 +1,248 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::fst_impls::edit_fst::EditFst;
+use crate::fst_impls::vector_fst::{del_trs_id_sorted_properties, VectorFstState};
+use crate::fst_impls::VectorFst;
+use crate::fst_properties::mutable_properties::{
+    add_state_properties, add_tr_properties, delete_all_states_properties, delete_trs_properties,
+    set_start_properties,
+};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::trs_iter_mut::TrsIterMut;
+use crate::{StateId, Tr, Trs, EPS_LABEL};
+
+impl<W: Semiring, F: ExpandedFst<W> + 'static> MutableFst<W> for EditFst<W, F> {
+    fn new() -> Self {
+        EditFst {
+            wrapped: None,
+            num_wrapped_states: 0,
+            edited_states: HashMap::new(),
+            edited_final_weights: HashMap::new(),
+            num_states: 0,
+            start_state: None,
+            isymt: None,
+            osymt: None,
+            properties: FstProperties::null_properties(),
+        }
+    }
+
+    fn set_start(&mut self, state_id: StateId) -> Result<()> {
+        self.check_state(state_id)?;
+        unsafe { self.set_start_unchecked(state_id) };
+        Ok(())
+    }
+
+    unsafe fn set_start_unchecked(&mut self, state_id: StateId) {
+        self.start_state = Some(state_id);
+        self.properties = set_start_properties(self.properties);
+    }
+
+    fn set_final<S: Into<W>>(&mut self, state_id: StateId, final_weight: S) -> Result<()> {
+        self.check_state(state_id)?;
+        unsafe { self.set_final_unchecked(state_id, final_weight) };
+        Ok(())
+    }
+
+    unsafe fn set_final_unchecked<S: Into<W>>(&mut self, state_id: StateId, final_weight: S) {
+        self.replace_final_weight_unchecked(state_id, Some(final_weight.into()));
+    }
+
+    fn add_state(&mut self) -> StateId {
+        let id = self.num_states as StateId;
+        self.edited_states.insert(id, VectorFstState::new());
+        self.num_states += 1;
+        self.properties = add_state_properties(self.properties);
+        id
+    }
+
+    fn add_states(&mut self, n: usize) {
+        for _ in 0..n {
+            self.add_state();
+        }
+    }
+
+    fn tr_iter_mut(&mut self, state_id: StateId) -> Result<TrsIterMut<'_, W>> {
+        self.edited_state_mut(state_id)?;
+        Ok(unsafe { self.tr_iter_unchecked_mut(state_id) })
+    }
+
+    unsafe fn tr_iter_unchecked_mut(&mut self, state_id: StateId) -> TrsIterMut<'_, W> {
+        self.edited_state_mut_unchecked(state_id);
+        let state = self.edited_states.get_mut(&state_id).unwrap_unchecked();
+        TrsIterMut::new(
+            Arc::make_mut(&mut state.trs.0),
+            &mut self.properties,
+            &mut state.niepsilons,
+            &mut state.noepsilons,
+        )
+    }
+
+    fn del_state(&mut self, state_to_remove: StateId) -> Result<()> {
+        self.check_state(state_to_remove)?;
+        self.del_states(vec![state_to_remove])
+    }
+
+    fn del_states<T: IntoIterator<Item = StateId>>(&mut self, dstates: T) -> Result<()> {
+        // The states have to be renumbered : the whole FST is moved to the overlay.
+        self.edit_all_states()?;
+        let states = (0..(self.num_states as StateId))
+            .map(|s| self.edited_states.remove(&s).unwrap())
+            .collect();
+        let mut fst = VectorFst {
+            states,
+            start_state: self.start_state,
+            isymt: None,
+            osymt: None,
+            properties: self.properties,
+        };
+        fst.del_states(dstates)?;
+
+        self.num_states = fst.states.len();
+        self.edited_states = fst
+            .states
+            .into_iter()
+            .enumerate()
+            .map(|(s, state)| (s as StateId, state))
+            .collect();
+        self.start_state = fst.start_state;
+        self.properties = fst.properties;
+        Ok(())
+    }
+
+    fn del_all_states(&mut self) {
+        self.wrapped = None;
+        self.num_wrapped_states = 0;
+        self.edited_states.clear();
+        self.edited_final_weights.clear();
+        self.num_states = 0;
+        self.start_state = None;
+        self.properties = delete_all_states_properties();
+    }
+
+    unsafe fn del_trs_id_sorted_unchecked(&mut self, state: StateId, to_del: &[usize]) {
+        let state = self.edited_state_mut_unchecked(state);
+        state.del_trs_id_sorted(to_del);
+        let all_trs_deleted = state.trs.is_empty();
+        self.properties = del_trs_id_sorted_properties(self.properties, all_trs_deleted);
+    }
+
+    fn add_tr(&mut self, source: StateId, tr: Tr<W>) -> Result<()> {
+        self.edited_state_mut(source)?;
+        unsafe { self.add_tr_unchecked(source, tr) };
+        Ok(())
+    }
+
+    unsafe fn add_tr_unchecked(&mut self, source: StateId, tr: Tr<W>) {
+        let properties = self.properties;
+        let state = self.edited_state_mut_unchecked(source);
+        state.increment_num_epsilons(&tr);
+        state.trs.push(tr);
+
+        let trs = state.trs.trs();
+        let new_tr = &trs[trs.len() - 1];
+        let old_tr = if trs.len() > 1 {
+            Some(&trs[trs.len() - 2])
+        } else {
+            None
+        };
+        self.properties = add_tr_properties(properties, source, new_tr, old_tr);
+    }
+
+    unsafe fn set_trs_unchecked(&mut self, source: StateId, trs: Vec<Tr<W>>) {
+        let mut properties = self.properties;
+        let state = self.edited_state_mut_unchecked(source);
+        *Arc::make_mut(&mut state.trs.0) = trs;
+
+        let trs_slice = state.trs.trs();
+        let mut niepsilons = 0;
+        let mut noepsilons = 0;
+        for i in 0..trs_slice.len() {
+            let prev_tr = if i >= 1 {
+                Some(&trs_slice[i - 1])
+            } else {
+                None
+            };
+            properties = add_tr_properties(properties, source, &trs_slice[i], prev_tr);
+            if trs_slice[i].ilabel == EPS_LABEL {
+                niepsilons += 1;
+            }
+            if trs_slice[i].olabel == EPS_LABEL {
+                noepsilons += 1;
+            }
+        }
+        state.niepsilons = niepsilons;
+        state.noepsilons = noepsilons;
+        self.properties = properties;
+    }
+
+    fn delete_final_weight(&mut self, source: StateId) -> Result<()> {
+        self.check_state(source)?;
+        unsafe { self.delete_final_weight_unchecked(source) };
+        Ok(())
+    }
+
+    unsafe fn delete_final_weight_unchecked(&mut self, source: StateId) {
+        self.replace_final_weight_unchecked(source, None);
+    }
+
+    fn delete_trs(&mut self, source: StateId) -> Result<()> {
+        self.edited_state_mut(source)?;
+        unsafe { self.pop_trs_unchecked(source) };
+        Ok(())
+    }
+
+    fn pop_trs(&mut self, source: StateId) -> Result<Vec<Tr<W>>> {
+        self.edited_state_mut(source)?;
+        Ok(unsafe { self.pop_trs_unchecked(source) })
+    }
+
+    unsafe fn pop_trs_unchecked(&mut self, source: StateId) -> Vec<Tr<W>> {
+        self.properties = delete_trs_properties(self.properties);
+        let state = self.edited_state_mut_unchecked(source);
+        state.niepsilons = 0;
+        state.noepsilons = 0;
+        Arc::make_mut(&mut state.trs.0).drain(..).collect()
+    }
+
+    fn take_final_weight(&mut self, state_id: StateId) -> Result<Option<W>> {
+        self.check_state(state_id)?;
+        Ok(unsafe { self.take_final_weight_unchecked(state_id) })
+    }
+
+    unsafe fn take_final_weight_unchecked(&mut self, state_id: StateId) -> Option<W> {
+        self.replace_final_weight_unchecked(state_id, None)
+    }
+
+    /// DOESN'T MODIFY THE PROPERTIES
+    fn sort_trs_unchecked<C: Fn(&Tr<W>, &Tr<W>) -> Ordering>(&mut self, state: StateId, f: C) {
+        unsafe {
+            let trs = &mut self.edited_state_mut_unchecked(state).trs;
+            Arc::make_mut(&mut trs.0).sort_by(f);
+        }
+    }
+
+    /// DOESN'T MODIFY THE PROPERTIES
+    unsafe fn unique_trs_unchecked(&mut self, state: StateId) {
+        self.edited_state_mut_unchecked(state).unique_trs()
+    }
+
+    /// DOESN'T MODIFY THE PROPERTIES
+    unsafe fn sum_trs_unchecked(&mut self, state: StateId) {
+        self.edited_state_mut_unchecked(state).sum_trs()
+    }
+
+    fn set_properties(&mut self, props: FstProperties) {
+        self.properties = props;
+    }
+
+    fn set_properties_with_mask(&mut self, props: FstProperties, mask: FstProperties) {
+        self.properties &= !mask;
+        self.properties |= props & mask;
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+
+    use crate::algorithms::fst_convert_from_ref;
+    use crate::fst_impls::{ConstFst, EditFst, VectorFst};
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst, StateIterator};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::tr::Tr;
+    use crate::Trs;
+
+    fn build_fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 1, 1.0, 1))?;
+        fst.add_tr(0, Tr::new(2, 2, 2.0, 2))?;
+        fst.add_tr(1, Tr::new(3, 3, 3.0, 3))?;
+        fst.add_tr(2, Tr::new(0, 4, 4.0, 3))?;
+        fst.set_final(3, 0.5)?;
+        Ok(fst)
+    }
+
+    fn wrap(
+        fst: &VectorFst<TropicalWeight>,
+    ) -> (
+        Arc<ConstFst<TropicalWeight>>,
+        EditFst<TropicalWeight, ConstFst<TropicalWeight>>,
+    ) {
+        let const_fst = Arc::new(ConstFst::from(fst.clone()));
+        let edit_fst = EditFst::from_fst(Arc::clone(&const_fst));
+        (const_fst, edit_fst)
+    }
+
+    fn check_same(
+        edit_fst: &EditFst<TropicalWeight, ConstFst<TropicalWeight>>,
+        ref_fst: &VectorFst<TropicalWeight>,
+    ) -> Result<()> {
+        let converted: VectorFst<_> = fst_convert_from_ref(edit_fst);
+        assert_eq!(&converted, ref_fst);
+        assert_eq!(
+            edit_fst.properties() & FstProperties::binary_properties(),
+            ref_fst.properties() & FstProperties::binary_properties()
+        );
+        for s in ref_fst.states_range() {
+            assert_eq!(
+                edit_fst.num_input_epsilons(s)?,
+                ref_fst.num_input_epsilons(s)?
+            );
+            assert_eq!(
+                edit_fst.num_output_epsilons(s)?,
+                ref_fst.num_output_epsilons(s)?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_no_edit() -> Result<()> {
+        let fst = build_fst()?;
+        let (_, edit_fst) = wrap(&fst);
+        assert_eq!(edit_fst.num_edited_states(), 0);
+        check_same(&edit_fst, &fst)
+    }
+
+    #[test]
+    fn test_edit_fst_add_tr_copies_one_state() -> Result<()> {
+        let mut fst = build_fst()?;
+        let (const_fst, mut edit_fst) = wrap(&fst);
+
+        edit_fst.add_tr(1, Tr::new(5, 0, 1.5, 2))?;
+        fst.add_tr(1, Tr::new(5, 0, 1.5, 2))?;
+
+        assert_eq!(edit_fst.num_edited_states(), 1);
+        assert_eq!(const_fst.num_trs(1)?, 1);
+        check_same(&edit_fst, &fst)
+    }
+
+    #[test]
+    fn test_edit_fst_final_weight_doesnt_copy_trs() -> Result<()> {
+        let mut fst = build_fst()?;
+        let (const_fst, mut edit_fst) = wrap(&fst);
+
+        edit_fst.set_final(1, 2.0)?;
+        fst.set_final(1, 2.0)?;
+        assert_eq!(
+            edit_fst.take_final_weight(3)?,
+            Some(TropicalWeight::new(0.5))
+        );
+        fst.take_final_weight(3)?;
+
+        assert_eq!(edit_fst.num_edited_states(), 0);
+        assert_eq!(const_fst.final_weight(3)?, Some(TropicalWeight::new(0.5)));
+        check_same(&edit_fst, &fst)?;
+
+        // The edited final weight must be kept when the state is copied afterwards.
+        edit_fst.add_tr(1, Tr::new(1, 1, TropicalWeight::one(), 0))?;
+        fst.add_tr(1, Tr::new(1, 1, TropicalWeight::one(), 0))?;
+        assert_eq!(edit_fst.num_edited_states(), 1);
+        check_same(&edit_fst, &fst)
+    }
+
+    #[test]
+    fn test_edit_fst_add_states() -> Result<()> {
+        let mut fst = build_fst()?;
+        let (_, mut edit_fst) = wrap(&fst);
+
+        add_states(&mut fst)?;
+        add_states(&mut edit_fst)?;
+
+        assert_eq!(edit_fst.num_states(), 6);
+        assert_eq!(edit_fst.num_edited_states(), 3);
+        check_same(&edit_fst, &fst)
+    }
+
+    fn add_states<F: MutableFst<TropicalWeight>>(fst: &mut F) -> Result<()> {
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.add_tr(3, Tr::new(6, 6, 1.0, s1))?;
+        fst.add_tr(s1, Tr::new(0, 0, 1.0, s2))?;
+        fst.set_final(s2, 3.0)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_tr_iter_mut_and_sort() -> Result<()> {
+        let mut fst = build_fst()?;
+        let (_, mut edit_fst) = wrap(&fst);
+
+        mutate_trs(&mut fst)?;
+        mutate_trs(&mut edit_fst)?;
+
+        assert_eq!(edit_fst.num_edited_states(), 2);
+        check_same(&edit_fst, &fst)
+    }
+
+    fn mutate_trs<F: MutableFst<TropicalWeight>>(fst: &mut F) -> Result<()> {
+        let mut it = fst.tr_iter_mut(0)?;
+        it.set_ilabel(0, 0)?;
+        it.set_weight(1, TropicalWeight::new(0.1))?;
+        fst.sort_trs_unchecked(0, |a, b| a.olabel.cmp(&b.olabel).reverse());
+        unsafe {
+            fst.set_trs_unchecked(2, vec![Tr::new(0, 0, 1.0, 3), Tr::new(0, 0, 1.0, 3)]);
+            fst.sum_trs_unchecked(2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_del_states() -> Result<()> {
+        let mut fst = build_fst()?;
+        let (const_fst, mut edit_fst) = wrap(&fst);
+
+        edit_fst.set_final(0, 1.0)?;
+        fst.set_final(0, 1.0)?;
+        edit_fst.del_states(vec![1])?;
+        fst.del_states(vec![1])?;
+
+        assert_eq!(edit_fst.num_states(), 3);
+        assert!(edit_fst.wrapped().is_none());
+        assert_eq!(const_fst.num_states(), 4);
+        check_same(&edit_fst, &fst)?;
+
+        edit_fst.delete_trs(0)?;
+        fst.delete_trs(0)?;
+        check_same(&edit_fst, &fst)
+    }
+
+    #[test]
+    fn test_edit_fst_convert() -> Result<()> {
+        let mut fst = build_fst()?;
+        let (const_fst, mut edit_fst) = wrap(&fst);
+
+        let s = edit_fst.add_state();
+        edit_fst.add_tr(3, Tr::new(7, 7, 0.5, s))?;
+        edit_fst.set_final(s, 0.0)?;
+        edit_fst.pop_trs(2)?;
+        let s = fst.add_state();
+        fst.add_tr(3, Tr::new(7, 7, 0.5, s))?;
+        fst.set_final(s, 0.0)?;
+        fst.pop_trs(2)?;
+
+        let vector_fst: VectorFst<_> = fst_convert_from_ref(&edit_fst);
+        let edited_const_fst: ConstFst<_> = vector_fst.into();
+        assert_eq!(edited_const_fst, ConstFst::from(fst.clone()));
+        assert_eq!(*const_fst, ConstFst::from(build_fst()?));
+        for s in fst.states_iter() {
+            assert_eq!(edit_fst.get_trs(s)?.trs(), fst.get_trs(s)?.trs());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_errors() -> Result<()> {
+        let fst = build_fst()?;
+        let (_, mut edit_fst) = wrap(&fst);
+        assert!(edit_fst.set_start(4).is_err());
+        assert!(edit_fst.add_tr(4, Tr::new(1, 1, 1.0, 0)).is_err());
+        assert!(edit_fst.get_trs(4).is_err());
+        edit_fst.del_all_states();
+        assert_eq!(edit_fst.num_states(), 0);
+        assert_eq!(edit_fst.start(), None);
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 mod arc;
 pub(crate) mod const_fst;
+pub(crate) mod dyn_fst;
+pub(crate) mod edit_fst;
 pub(crate) mod vector_fst;
 
 pub use self::const_fst::ConstFst;
+pub use self::dyn_fst::{load_any_fst, read_any_fst, DynFst, DynTrs};
+pub use self::edit_fst::{EditFst, EditFstTrs};
 pub use self::vector_fst::VectorFst;
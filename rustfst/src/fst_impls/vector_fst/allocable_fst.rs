@@ -1,7 +1,7 @@
 use crate::fst_impls::vector_fst::VectorFst;
 use crate::fst_traits::AllocableFst;
 use crate::semirings::Semiring;
-use crate::StateId;
+use crate::{Error, StateId};
 use anyhow::Result;
 use std::sync::Arc;
 
@@ -10,7 +10,7 @@ impl<W: 'static + Semiring> AllocableFst<W> for VectorFst<W> {
         let trs = &mut self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?
+            .ok_or(Error::InvalidStateId(source))?
             .trs;
 
         Arc::make_mut(&mut trs.0).reserve(additional);
@@ -44,7 +44,7 @@ impl<W: 'static + Semiring> AllocableFst<W> for VectorFst<W> {
         let trs = &mut self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?
+            .ok_or(Error::InvalidStateId(source))?
             .trs;
         Arc::make_mut(&mut trs.0).shrink_to_fit();
         Ok(())
@@ -64,7 +64,7 @@ impl<W: 'static + Semiring> AllocableFst<W> for VectorFst<W> {
         Ok(self
             .states
             .get(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?
+            .ok_or(Error::InvalidStateId(source))?
             .trs
             .0
             .capacity())
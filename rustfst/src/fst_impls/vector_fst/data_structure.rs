@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::algorithms::tr_unique::tr_compare;
 use crate::fst_properties::mutable_properties::add_tr_properties;
 use crate::fst_properties::properties::{EXPANDED, MUTABLE};
 use crate::fst_properties::FstProperties;
@@ -76,6 +77,71 @@ impl<W: Semiring> VectorFstState<W> {
     }
 }
 
+#[inline]
+fn equal_tr<W: Semiring>(tr_1: &Tr<W>, tr_2: &Tr<W>) -> bool {
+    tr_1.ilabel == tr_2.ilabel && tr_1.olabel == tr_2.olabel && tr_1.nextstate == tr_2.nextstate
+}
+
+impl<W: Semiring> VectorFstState<W> {
+    pub(crate) fn del_trs_id_sorted(&mut self, to_del: &[usize]) {
+        for i in to_del.iter().rev() {
+            if self.trs[*i].ilabel == EPS_LABEL {
+                self.niepsilons -= 1;
+            }
+            if self.trs[*i].olabel == EPS_LABEL {
+                self.noepsilons -= 1;
+            }
+            self.trs.remove(*i);
+        }
+    }
+
+    pub(crate) fn unique_trs(&mut self) {
+        let trs_vec = Arc::make_mut(&mut self.trs.0);
+        trs_vec.sort_by(tr_compare);
+        trs_vec.dedup();
+
+        // There might be a better way to do this
+        if self.niepsilons != 0 || self.noepsilons != 0 {
+            self.niepsilons = 0;
+            self.noepsilons = 0;
+            for t in self.trs.trs() {
+                if t.ilabel == EPS_LABEL {
+                    self.niepsilons += 1;
+                }
+                if t.olabel == EPS_LABEL {
+                    self.noepsilons += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn sum_trs(&mut self) {
+        let trs_vec = Arc::make_mut(&mut self.trs.0);
+        trs_vec.sort_by(tr_compare);
+        let mut n_trs: usize = 0;
+        for i in 0..trs_vec.len() {
+            if n_trs > 0 && equal_tr(&trs_vec[i], &trs_vec[n_trs - 1]) {
+                if trs_vec[i].ilabel == EPS_LABEL {
+                    self.niepsilons -= 1;
+                }
+                if trs_vec[i].olabel == EPS_LABEL {
+                    self.noepsilons -= 1;
+                }
+                let (left, right) = trs_vec.split_at_mut(i);
+                left[n_trs - 1]
+                    .weight
+                    .plus_assign(&right[0].weight)
+                    .unwrap();
+            } else {
+                trs_vec.swap(n_trs, i);
+                n_trs += 1;
+            }
+        }
+        trs_vec.truncate(n_trs);
+        // Truncate doesn't modify the capacity of the vector. Maybe a shrink_to_fit ?
+    }
+}
+
 impl<W: Semiring> VectorFst<W> {
     pub fn update_properties_after_add_tr(&mut self, state: StateId) {
         let vector_state = unsafe { self.states.get_unchecked(state as usize) };
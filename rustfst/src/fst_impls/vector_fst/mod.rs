@@ -1,5 +1,6 @@
 pub use data_structure::VectorFst;
 pub(crate) use data_structure::VectorFstState;
+pub(crate) use mutable_fst::del_trs_id_sorted_properties;
 
 mod allocable_fst;
 mod data_structure;
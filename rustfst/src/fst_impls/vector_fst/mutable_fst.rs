@@ -3,7 +3,6 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::algorithms::tr_unique::tr_compare;
 use crate::fst_impls::vector_fst::{VectorFst, VectorFstState};
 use crate::fst_properties::mutable_properties::{
     add_state_properties, add_tr_properties, delete_all_states_properties,
@@ -14,11 +13,34 @@ use crate::fst_traits::CoreFst;
 use crate::fst_traits::MutableFst;
 use crate::semirings::Semiring;
 use crate::trs_iter_mut::TrsIterMut;
-use crate::{StateId, Tr, Trs, EPS_LABEL};
-
-#[inline]
-fn equal_tr<W: Semiring>(tr_1: &Tr<W>, tr_2: &Tr<W>) -> bool {
-    tr_1.ilabel == tr_2.ilabel && tr_1.olabel == tr_2.olabel && tr_1.nextstate == tr_2.nextstate
+use crate::{Error, StateId, Tr, Trs, EPS_LABEL};
+
+/// Properties of an FST after some of the trs of a state have been removed.
+pub(crate) fn del_trs_id_sorted_properties(
+    inprops: FstProperties,
+    all_trs_deleted: bool,
+) -> FstProperties {
+    if all_trs_deleted {
+        delete_trs_properties(inprops)
+    } else {
+        inprops
+            & (FstProperties::ACCEPTOR
+                | FstProperties::I_DETERMINISTIC
+                | FstProperties::O_DETERMINISTIC
+                | FstProperties::NO_EPSILONS
+                | FstProperties::NO_I_EPSILONS
+                | FstProperties::NO_O_EPSILONS
+                | FstProperties::I_LABEL_SORTED
+                | FstProperties::O_LABEL_SORTED
+                | FstProperties::UNWEIGHTED
+                // I believe it's correct to keep them but need to remove to be compliant with OpenFst.
+                // | FstProperties::ACYCLIC
+                // | FstProperties::INITIAL_ACYCLIC
+                | FstProperties::TOP_SORTED
+                | FstProperties::NOT_ACCESSIBLE
+                | FstProperties::NOT_COACCESSIBLE
+                | FstProperties::UNWEIGHTED_CYCLES)
+    }
 }
 
 impl<W: Semiring> MutableFst<W> for VectorFst<W> {
@@ -33,11 +55,9 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
     }
 
     fn set_start(&mut self, state_id: StateId) -> Result<()> {
-        ensure!(
-            self.states.get(state_id as usize).is_some(),
-            "The state {:?} doesn't exist",
-            state_id
-        );
+        if self.states.get(state_id as usize).is_none() {
+            return Err(Error::InvalidStateId(state_id).into());
+        }
         self.start_state = Some(state_id);
         self.properties = set_start_properties(self.properties);
         Ok(())
@@ -59,7 +79,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
             state.final_weight = Some(new_final_weight);
             Ok(())
         } else {
-            bail!("Stateid {:?} doesn't exist", state_id);
+            Err(Error::InvalidStateId(state_id).into())
         }
     }
 
@@ -94,7 +114,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = self
             .states
             .get_mut(state_id as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
+            .ok_or(Error::InvalidStateId(state_id))?;
         let trs = Arc::make_mut(&mut state.trs.0);
         Ok(TrsIterMut::new(
             trs,
@@ -119,11 +139,9 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         // Remove the state from the vector
         // Check the trs for trs going to this state
 
-        ensure!(
-            (state_to_remove as usize) < self.states.len(),
-            "State id {:?} doesn't exist",
-            state_to_remove
-        );
+        if (state_to_remove as usize) >= self.states.len() {
+            return Err(Error::InvalidStateId(state_to_remove).into());
+        }
         self.properties = delete_states_properties(self.properties);
         let v = vec![state_to_remove];
         self.del_states(v)
@@ -199,45 +217,16 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
     }
 
     unsafe fn del_trs_id_sorted_unchecked(&mut self, state: StateId, to_del: &[usize]) {
-        let state = state as usize;
-        let state = &mut self.states.get_unchecked_mut(state);
-        for i in to_del.iter().rev() {
-            if state.trs[*i].ilabel == EPS_LABEL {
-                state.niepsilons -= 1;
-            }
-            if state.trs[*i].olabel == EPS_LABEL {
-                state.noepsilons -= 1;
-            }
-            state.trs.remove(*i);
-        }
-        if state.trs.is_empty() {
-            // All Trs are removed
-            self.properties = delete_trs_properties(self.properties);
-        } else {
-            self.properties &= FstProperties::ACCEPTOR
-                | FstProperties::I_DETERMINISTIC
-                | FstProperties::O_DETERMINISTIC
-                | FstProperties::NO_EPSILONS
-                | FstProperties::NO_I_EPSILONS
-                | FstProperties::NO_O_EPSILONS
-                | FstProperties::I_LABEL_SORTED
-                | FstProperties::O_LABEL_SORTED
-                | FstProperties::UNWEIGHTED
-                // I believe it's correct to keep them but need to remove to be compliant with OpenFst.
-                // | FstProperties::ACYCLIC
-                // | FstProperties::INITIAL_ACYCLIC
-                | FstProperties::TOP_SORTED
-                | FstProperties::NOT_ACCESSIBLE
-                | FstProperties::NOT_COACCESSIBLE
-                | FstProperties::UNWEIGHTED_CYCLES;
-        }
+        let state = self.states.get_unchecked_mut(state as usize);
+        state.del_trs_id_sorted(to_del);
+        self.properties = del_trs_id_sorted_properties(self.properties, state.trs.is_empty());
     }
 
     fn add_tr(&mut self, source: StateId, tr: Tr<W>) -> Result<()> {
         let state = self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?;
+            .ok_or(Error::InvalidStateId(source))?;
         state.increment_num_epsilons(&tr);
         state.trs.push(tr);
         self.update_properties_after_add_tr(source);
@@ -285,7 +274,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
             self.properties = set_final_properties(self.properties, s.final_weight.as_ref(), None);
             s.final_weight = None;
         } else {
-            bail!("State {:?} doesn't exist", source)
+            return Err(Error::InvalidStateId(source).into());
         }
         Ok(())
     }
@@ -300,7 +289,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?;
+            .ok_or(Error::InvalidStateId(source))?;
 
         state.trs.clear();
         state.niepsilons = 0;
@@ -313,7 +302,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let state = &mut self
             .states
             .get_mut(source as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?;
+            .ok_or(Error::InvalidStateId(source))?;
 
         let v = Arc::make_mut(&mut state.trs.0).drain(..).collect();
         state.niepsilons = 0;
@@ -334,7 +323,7 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
         let s = self
             .states
             .get_mut(state_id as usize)
-            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
+            .ok_or(Error::InvalidStateId(state_id))?;
 
         self.properties = set_final_properties(self.properties, s.final_weight.as_ref(), None);
         Ok(s.final_weight.take())
@@ -356,52 +345,12 @@ impl<W: Semiring> MutableFst<W> for VectorFst<W> {
 
     /// DOESN'T MODIFY THE PROPERTIES
     unsafe fn unique_trs_unchecked(&mut self, state: StateId) {
-        let state = &mut self.states.get_unchecked_mut(state as usize);
-        let trs_vec = Arc::make_mut(&mut state.trs.0);
-        trs_vec.sort_by(tr_compare);
-        trs_vec.dedup();
-
-        // There might be a better way to do this
-        if state.niepsilons != 0 || state.noepsilons != 0 {
-            state.niepsilons = 0;
-            state.noepsilons = 0;
-            for t in state.trs.trs() {
-                if t.ilabel == EPS_LABEL {
-                    state.niepsilons += 1;
-                }
-                if t.olabel == EPS_LABEL {
-                    state.noepsilons += 1;
-                }
-            }
-        }
+        self.states.get_unchecked_mut(state as usize).unique_trs()
     }
 
     /// DOESN'T MODIFY THE PROPERTIES
     unsafe fn sum_trs_unchecked(&mut self, state: StateId) {
-        let state = &mut self.states.get_unchecked_mut(state as usize);
-        let trs_vec = Arc::make_mut(&mut state.trs.0);
-        trs_vec.sort_by(tr_compare);
-        let mut n_trs: usize = 0;
-        for i in 0..trs_vec.len() {
-            if n_trs > 0 && equal_tr(&trs_vec[i], &trs_vec[n_trs - 1]) {
-                if trs_vec[i].ilabel == EPS_LABEL {
-                    state.niepsilons -= 1;
-                }
-                if trs_vec[i].olabel == EPS_LABEL {
-                    state.noepsilons -= 1;
-                }
-                let (left, right) = trs_vec.split_at_mut(i);
-                left[n_trs - 1]
-                    .weight
-                    .plus_assign(&right[0].weight)
-                    .unwrap();
-            } else {
-                trs_vec.swap(n_trs, i);
-                n_trs += 1;
-            }
-        }
-        trs_vec.truncate(n_trs);
-        // Truncate doesn't modify the capacity of the vector. Maybe a shrink_to_fit ?
+        self.states.get_unchecked_mut(state as usize).sum_trs()
     }
 
     fn set_properties(&mut self, props: FstProperties) {
@@ -4,12 +4,14 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use nom::bytes::complete::take;
+use nom::combinator::verify;
+use nom::error::ErrorKind;
 use nom::multi::count;
 use nom::number::complete::le_i32;
 use nom::IResult;
 
 use crate::fst_impls::const_fst::{
-    CONST_ALIGNED_FILE_VERSION, CONST_ARCH_ALIGNMENT, CONST_MIN_FILE_VERSION,
+    CONST_ALIGNED_FILE_VERSION, CONST_ARCH_ALIGNMENT, CONST_MIN_FILE_VERSION, CONST_MIN_STATE_SIZE,
 };
 use crate::fst_impls::vector_fst::VectorFstState;
 use crate::fst_impls::{ConstFst, VectorFst};
@@ -17,24 +19,31 @@ use crate::fst_properties::FstProperties;
 use crate::fst_traits::SerializableFst;
 use crate::parsers::bin_fst::fst_header::FstHeader;
 use crate::parsers::bin_fst::utils_parsing::{
-    parse_bin_fst_tr, parse_final_weight, parse_start_state,
+    parse_bin_fst_tr, parse_final_weight, parse_start_state, MIN_BIN_TR_SIZE,
 };
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::{binary_parse_error, check_count, NomCustomError};
 use crate::semirings::SerializableSemiring;
-use crate::{Tr, TrsVec};
+use crate::{Error, Tr, TrsVec, EPS_LABEL};
 
 impl<W: SerializableSemiring> VectorFst<W> {
     /// Load a VectorFst directly from a ConstFst file.
     pub fn read_from_const<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
-        let data = read(path_bin_fst.as_ref()).with_context(|| {
-            format!(
-                "Can't open ConstFst binary file : {:?}",
-                path_bin_fst.as_ref()
-            )
-        })?;
+        let data = read(path_bin_fst.as_ref())
+            .map_err(Error::Io)
+            .with_context(|| {
+                format!(
+                    "Can't open ConstFst binary file : {:?}",
+                    path_bin_fst.as_ref()
+                )
+            })?;
 
-        let (_, parsed_fst) = parse_const_fst(&data)
-            .map_err(|_| format_err!("Error while parsing binary ConstFst file as a VectorFst"))?;
+        Self::load_from_const(&data)
+    }
+
+    /// Load a VectorFst directly from the binary content of a ConstFst file.
+    pub(crate) fn load_from_const(data: &[u8]) -> Result<Self> {
+        let (_, parsed_fst) = parse_const_fst(data)
+            .map_err(|e| binary_parse_error(e, "ConstFst file as a VectorFst"))?;
 
         Ok(parsed_fst)
     }
@@ -52,9 +61,9 @@ fn parse_const_state<W: SerializableSemiring>(
 ) -> IResult<&[u8], TempState<W>, NomCustomError<&[u8]>> {
     let (i, final_weight) = W::parse_binary(i)?;
     let (i, _pos) = le_i32(i)?;
-    let (i, ntrs) = le_i32(i)?;
-    let (i, niepsilons) = le_i32(i)?;
-    let (i, noepsilons) = le_i32(i)?;
+    let (i, ntrs) = verify(le_i32, |v| *v >= 0)(i)?;
+    let (i, niepsilons) = verify(le_i32, |v| *v >= 0)(i)?;
+    let (i, noepsilons) = verify(le_i32, |v| *v >= 0)(i)?;
 
     Ok((
         i,
@@ -86,7 +95,8 @@ fn parse_const_fst<W: SerializableSemiring>(
     if aligned && hdr.num_states > 0 && !pos.is_multiple_of(CONST_ARCH_ALIGNMENT) {
         i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
     }
-    let (mut i, temp_states) = count(parse_const_state::<W>, hdr.num_states as usize)(i)?;
+    let num_states = check_count(i, hdr.num_states, CONST_MIN_STATE_SIZE)?;
+    let (mut i, temp_states) = count(parse_const_state::<W>, num_states)(i)?;
     let pos = stream_len - i.len();
 
     // Align input
@@ -96,7 +106,13 @@ fn parse_const_fst<W: SerializableSemiring>(
 
     let mut vector_states = Vec::with_capacity(temp_states.len());
     for temp_state in temp_states {
-        let (j, trs) = count(parse_bin_fst_tr, temp_state.ntrs)(i)?;
+        let ntrs = check_count(i, temp_state.ntrs as i64, MIN_BIN_TR_SIZE)?;
+        let (j, trs) = count(parse_bin_fst_tr::<W>(num_states), ntrs)(i)?;
+        if trs.iter().filter(|t| t.ilabel == EPS_LABEL).count() != temp_state.niepsilons
+            || trs.iter().filter(|t| t.olabel == EPS_LABEL).count() != temp_state.noepsilons
+        {
+            return Err(nom::Err::Error(NomCustomError::Nom(i, ErrorKind::Verify)));
+        }
         i = j;
         vector_states.push(VectorFstState {
             final_weight: temp_state.final_weight,
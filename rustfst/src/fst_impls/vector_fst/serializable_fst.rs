@@ -12,10 +12,12 @@ use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst, SerializableFst};
 use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
 use crate::parsers::bin_fst::utils_parsing::{
-    parse_bin_fst_tr, parse_final_weight, parse_start_state,
+    parse_bin_fst_tr, parse_final_weight, parse_start_state, MIN_BIN_TR_SIZE,
 };
 use crate::parsers::bin_fst::utils_serialization::write_bin_fst_tr;
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::{
+    binary_parse_error, check_count, IResultCustomError, NomCustomError,
+};
 use crate::parsers::text_fst::ParsedTextFst;
 use crate::parsers::write_bin_i64;
 use crate::semirings::SerializableSemiring;
@@ -27,21 +29,16 @@ impl<W: SerializableSemiring> SerializableFst<W> for VectorFst<W> {
     }
 
     fn load(data: &[u8]) -> Result<Self> {
-        let (_, parsed_fst) = parse_vector_fst(data).map_err(|e| {
-            e.map(|e_inner| match e_inner {
-                NomCustomError::Nom(_, k) => {
-                    format_err!("Error while parsing binary VectorFst. Error kind {:?}", k)
-                }
-                NomCustomError::SymbolTableError(e) => format_err!(
-                    "Error while parsing symbolTable from binary VectorFst : {}",
-                    e
-                ),
-            })
-        })?;
+        let (_, parsed_fst) =
+            parse_vector_fst(data).map_err(|e| binary_parse_error(e, "VectorFst"))?;
 
         Ok(parsed_fst)
     }
 
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        parse_vector_fst(i)
+    }
+
     fn store<O: Write>(&self, mut output: O) -> Result<()> {
         let num_trs: usize = (0..self.num_states())
             .map(|s: usize| unsafe { self.num_trs_unchecked(s as StateId) })
@@ -126,23 +123,29 @@ impl<W: SerializableSemiring> SerializableFst<W> for VectorFst<W> {
 
 static VECTOR_MIN_FILE_VERSION: i32 = 2;
 
+/// Minimum size of a serialized state : the number of transitions, the weight may be empty.
+static VECTOR_MIN_STATE_SIZE: usize = 8;
+
 fn parse_vector_fst_state<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], VectorFstState<W>, NomCustomError<&[u8]>> {
-    let (i, final_weight) = W::parse_binary(i)?;
-    let (i, num_trs) = le_i64(i)?;
-    let (i, trs) = count(parse_bin_fst_tr, num_trs as usize)(i)?;
-    let niepsilons = trs.iter().filter(|t| t.ilabel == EPS_LABEL).count();
-    let noepsilons = trs.iter().filter(|t| t.olabel == EPS_LABEL).count();
-    Ok((
-        i,
-        VectorFstState {
-            final_weight: parse_final_weight(final_weight),
-            trs: TrsVec(Arc::new(trs)),
-            niepsilons,
-            noepsilons,
-        },
-    ))
+    num_states: usize,
+) -> impl Fn(&[u8]) -> IResultCustomError<&[u8], VectorFstState<W>> {
+    move |i: &[u8]| {
+        let (i, final_weight) = W::parse_binary(i)?;
+        let (i, num_trs) = le_i64(i)?;
+        let num_trs = check_count(i, num_trs, MIN_BIN_TR_SIZE)?;
+        let (i, trs) = count(parse_bin_fst_tr(num_states), num_trs)(i)?;
+        let niepsilons = trs.iter().filter(|t| t.ilabel == EPS_LABEL).count();
+        let noepsilons = trs.iter().filter(|t| t.olabel == EPS_LABEL).count();
+        Ok((
+            i,
+            VectorFstState {
+                final_weight: parse_final_weight(final_weight),
+                trs: TrsVec(Arc::new(trs)),
+                niepsilons,
+                noepsilons,
+            },
+        ))
+    }
 }
 
 fn parse_vector_fst<W: SerializableSemiring>(
@@ -154,7 +157,8 @@ fn parse_vector_fst<W: SerializableSemiring>(
         VectorFst::<W>::fst_type(),
         Tr::<W>::tr_type(),
     )?;
-    let (i, states) = count(parse_vector_fst_state, header.num_states as usize)(i)?;
+    let num_states = check_count(i, header.num_states, VECTOR_MIN_STATE_SIZE)?;
+    let (i, states) = count(parse_vector_fst_state(num_states), num_states)(i)?;
     Ok((
         i,
         VectorFst {
@@ -9,7 +9,7 @@ use crate::fst_traits::{ExpandedFst, Fst};
 use crate::semirings::Semiring;
 use crate::tr::Tr;
 use crate::trs_iter_mut::TrsIterMut;
-use crate::{Label, StateId, SymbolTable};
+use crate::{Error, Label, StateId, SymbolTable};
 
 /// Trait defining the methods to modify a wFST.
 pub trait MutableFst<W: Semiring>: ExpandedFst<W> {
@@ -485,16 +485,18 @@ pub trait MutableFst<W: Semiring>: ExpandedFst<W> {
         let old_isymbols = if let Some(s) = old_isymbols {
             s
         } else {
-            self.input_symbols()
-                .ok_or_else(|| format_err!("No input SymbolTable found"))?
+            self.input_symbols().ok_or_else(|| {
+                Error::IncompatibleSymbols("No input SymbolTable found".to_string())
+            })?
         };
         let old_isymbols = Arc::clone(old_isymbols);
 
         let old_osymbols = if let Some(s) = old_osymbols {
             s
         } else {
-            self.output_symbols()
-                .ok_or_else(|| format_err!("No output SymbolTable found"))?
+            self.output_symbols().ok_or_else(|| {
+                Error::IncompatibleSymbols("No output SymbolTable found".to_string())
+            })?
         };
         let old_osymbols = Arc::clone(old_osymbols);
 
@@ -505,24 +507,30 @@ pub trait MutableFst<W: Semiring>: ExpandedFst<W> {
 
                 let old_ilabel = tr.ilabel;
                 let isymbol = old_isymbols.get_symbol(old_ilabel).ok_or_else(|| {
-                    format_err!("Old Input SymbolTable doesn't contain label {}", old_ilabel)
+                    Error::IncompatibleSymbols(format!(
+                        "Old Input SymbolTable doesn't contain label {}",
+                        old_ilabel
+                    ))
                 })?;
                 let new_ilabel = new_isymbols.get_label(isymbol).ok_or_else(|| {
-                    format_err!("New Input SymbolTable doesn't contain symbol {:?}", isymbol)
+                    Error::IncompatibleSymbols(format!(
+                        "New Input SymbolTable doesn't contain symbol {:?}",
+                        isymbol
+                    ))
                 })?;
 
                 let old_olabel = tr.olabel;
                 let osymbol = old_osymbols.get_symbol(old_olabel).ok_or_else(|| {
-                    format_err!(
+                    Error::IncompatibleSymbols(format!(
                         "Old Output SymbolTable doesn't contain label {}",
                         old_olabel
-                    )
+                    ))
                 })?;
                 let new_olabel = new_osymbols.get_label(osymbol).ok_or_else(|| {
-                    format_err!(
+                    Error::IncompatibleSymbols(format!(
                         "New Output SymbolTable doesn't contain symbol {:?}",
                         osymbol
-                    )
+                    ))
                 })?;
 
                 trs.set_ilabel(i, new_ilabel)?;
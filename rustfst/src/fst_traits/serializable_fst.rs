@@ -3,12 +3,15 @@ use std::io::{BufWriter, LineWriter, Write};
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use nom::error::ErrorKind;
+use nom::IResult;
 
 use crate::fst_traits::ExpandedFst;
+use crate::parsers::nom_utils::NomCustomError;
 use crate::parsers::text_fst::ParsedTextFst;
 use crate::semirings::SerializableSemiring;
 use crate::Trs;
-use crate::{DrawingConfig, StateId};
+use crate::{DrawingConfig, Error, StateId};
 
 /// Trait definining the methods an Fst must implement to be serialized and deserialized.
 pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
@@ -21,29 +24,43 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
     /// Loads an FST from the binary format data in a `Read`.
     fn load(input: &[u8]) -> Result<Self>;
 
+    /// Parses an FST in binary format at the beginning of `i` and returns the remaining data.
+    /// Useful to read FSTs embedded in a larger binary stream, e.g. in a
+    /// [`MatcherFst`](crate::algorithms::compose::MatcherFst).
+    ///
+    /// The default implementation always fails : FST types that can be embedded must
+    /// override it.
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        Err(nom::Err::Failure(NomCustomError::Nom(i, ErrorKind::Fail)))
+    }
+
     /// Store the FST in binary format to a `Write`.
     fn store<O: Write>(&self, output: O) -> Result<()>;
 
     /// Loads an FST from a file in binary format.
     fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
-        let data: Vec<u8> = std::fs::read(path_bin_fst.as_ref()).with_context(|| {
-            format!(
-                "Can't open {}Fst binary file : {:?}",
-                Self::fst_type(),
-                path_bin_fst.as_ref()
-            )
-        })?;
+        let data: Vec<u8> = std::fs::read(path_bin_fst.as_ref())
+            .map_err(Error::Io)
+            .with_context(|| {
+                format!(
+                    "Can't open {}Fst binary file : {:?}",
+                    Self::fst_type(),
+                    path_bin_fst.as_ref()
+                )
+            })?;
         Self::load(&data)
     }
     /// Writes the FST to a file in binary format.
     fn write<P: AsRef<Path>>(&self, path_bin_fst: P) -> Result<()> {
-        let output = std::fs::File::create(path_bin_fst.as_ref()).with_context(|| {
-            format!(
-                "Cannot create {}Fst binary file : {:?}",
-                Self::fst_type(),
-                path_bin_fst.as_ref(),
-            )
-        })?;
+        let output = std::fs::File::create(path_bin_fst.as_ref())
+            .map_err(Error::Io)
+            .with_context(|| {
+                format!(
+                    "Cannot create {}Fst binary file : {:?}",
+                    Self::fst_type(),
+                    path_bin_fst.as_ref(),
+                )
+            })?;
         self.store(BufWriter::new(output))
     }
 
@@ -66,7 +83,7 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
 
     /// Serializes the FST as a text file in a format compatible with OpenFST.
     fn write_text<P: AsRef<Path>>(&self, path_output: P) -> Result<()> {
-        let buffer = File::create(path_output.as_ref())?;
+        let buffer = File::create(path_output.as_ref()).map_err(Error::Io)?;
         let mut line_writer = LineWriter::new(buffer);
         write_fst!(self, line_writer, true, false);
         Ok(())
@@ -82,7 +99,7 @@ pub trait SerializableFst<W: SerializableSemiring>: ExpandedFst<W> {
 
     /// Serializes the FST as a DOT file compatible with GraphViz binaries.
     fn draw<P: AsRef<Path>>(&self, path_output: P, config: &DrawingConfig) -> Result<()> {
-        let buffer = File::create(path_output.as_ref())?;
+        let buffer = File::create(path_output.as_ref()).map_err(Error::Io)?;
         let mut f = BufWriter::new(LineWriter::new(buffer));
 
         if let Some(start_state) = self.start() {
@@ -166,7 +183,12 @@ fn draw_single_fst_state<S: SerializableSemiring, F: SerializableFst<S>, W: Writ
             |symt| {
                 symt.get_symbol(tr.ilabel)
                     .map(|v| v.to_string())
-                    .ok_or_else(|| format_err!("Missing {} in input SymbolTable", tr.ilabel))
+                    .ok_or_else(|| {
+                        Error::IncompatibleSymbols(format!(
+                            "Missing {} in input SymbolTable",
+                            tr.ilabel
+                        ))
+                    })
             },
         )?;
 
@@ -175,7 +197,12 @@ fn draw_single_fst_state<S: SerializableSemiring, F: SerializableFst<S>, W: Writ
             |symt| {
                 symt.get_symbol(tr.olabel)
                     .map(|v| v.to_string())
-                    .ok_or_else(|| format_err!("Missing {} in output SymbolTable", tr.olabel))
+                    .ok_or_else(|| {
+                        Error::IncompatibleSymbols(format!(
+                            "Missing {} in output SymbolTable",
+                            tr.olabel
+                        ))
+                    })
             },
         )?;
 
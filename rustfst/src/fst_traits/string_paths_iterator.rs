@@ -1,7 +1,7 @@
 use crate::fst_traits::paths_iterator::PathsIterator;
 use crate::fst_traits::Fst;
-use crate::{Semiring, StringPath, SymbolTable};
-use anyhow::{format_err, Result};
+use crate::{Error, Semiring, StringPath, SymbolTable};
+use anyhow::Result;
 use std::sync::Arc;
 
 /// Iterator on the paths recognized by an Fst. Plus handles the SymbolTable
@@ -25,10 +25,10 @@ where
         let paths_iter = PathsIterator::new(fst);
         let isymt = fst
             .input_symbols()
-            .ok_or_else(|| format_err!("Missing input symbol table"))?;
+            .ok_or_else(|| Error::IncompatibleSymbols("Missing input symbol table".to_string()))?;
         let osymt = fst
             .output_symbols()
-            .ok_or_else(|| format_err!("Missing output symbol table"))?;
+            .ok_or_else(|| Error::IncompatibleSymbols("Missing output symbol table".to_string()))?;
         Ok(Self {
             paths_iter,
             isymt: Arc::clone(isymt),
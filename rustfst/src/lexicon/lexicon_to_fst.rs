@@ -0,0 +1,508 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::algorithms::relabel_pairs;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{Fst, MutableFst};
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{Label, SymbolTable, Tr, EPS_LABEL};
+
+/// Pronunciation of a word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexiconEntry {
+    /// Word being pronounced.
+    pub word: String,
+    /// Phones of the pronunciation, in order.
+    pub phones: Vec<String>,
+    /// Probability of the pronunciation. Use `1.0` for an unweighted lexicon.
+    pub prob: f32,
+}
+
+impl LexiconEntry {
+    /// Creates the pronunciation `phones` of `word` with probability `prob`.
+    pub fn new<S: Into<String>>(word: S, phones: Vec<S>, prob: f32) -> Self {
+        Self {
+            word: word.into(),
+            phones: phones.into_iter().map(|p| p.into()).collect(),
+            prob,
+        }
+    }
+}
+
+/// Configuration for the construction of a lexicon FST.
+#[derive(Debug, Clone)]
+pub struct LexiconConfig {
+    /// Phone optionally inserted between words. If `None`, no silence is added.
+    pub silence_phone: Option<String>,
+    /// Probability of inserting the silence phone after a word (and at the beginning).
+    pub silence_prob: f32,
+    /// Adds word-position markers to the phones : `_B` (begin), `_I` (internal), `_E` (end)
+    /// and `_S` (singleton) so that the word boundaries can be recovered from the phones.
+    pub position_dependent_phones: bool,
+    /// Disambiguation symbol used on the backoff transitions of the grammar. It is added to both
+    /// symbol tables and a self-loop is added between words so that it can go through `L o G`.
+    pub backoff_disambig_symbol: Option<String>,
+    /// Phone symbol table to extend. If `None`, a new one is created.
+    pub phone_symbol_table: Option<SymbolTable>,
+    /// Word symbol table to extend, typically the one of the grammar. If `None`, a new one
+    /// is created.
+    pub word_symbol_table: Option<SymbolTable>,
+}
+
+impl Default for LexiconConfig {
+    fn default() -> Self {
+        Self {
+            silence_phone: None,
+            silence_prob: 0.5,
+            position_dependent_phones: false,
+            backoff_disambig_symbol: Some("#0".to_string()),
+            phone_symbol_table: None,
+            word_symbol_table: None,
+        }
+    }
+}
+
+impl LexiconConfig {
+    /// Optionally inserts `silence_phone` at the beginning and after each word, with probability
+    /// `silence_prob`.
+    pub fn with_silence(self, silence_phone: impl Into<String>, silence_prob: f32) -> Self {
+        Self {
+            silence_phone: Some(silence_phone.into()),
+            silence_prob,
+            ..self
+        }
+    }
+
+    /// Sets whether word-position markers are added to the phones.
+    pub fn with_position_dependent_phones(self, position_dependent_phones: bool) -> Self {
+        Self {
+            position_dependent_phones,
+            ..self
+        }
+    }
+
+    /// Sets the disambiguation symbol of the backoff transitions, `#0` by default.
+    pub fn with_backoff_disambig_symbol(self, backoff_disambig_symbol: Option<String>) -> Self {
+        Self {
+            backoff_disambig_symbol,
+            ..self
+        }
+    }
+
+    /// Sets the phone symbol table to extend.
+    pub fn with_phone_symbol_table(self, phone_symbol_table: SymbolTable) -> Self {
+        Self {
+            phone_symbol_table: Some(phone_symbol_table),
+            ..self
+        }
+    }
+
+    /// Sets the word symbol table to extend.
+    pub fn with_word_symbol_table(self, word_symbol_table: SymbolTable) -> Self {
+        Self {
+            word_symbol_table: Some(word_symbol_table),
+            ..self
+        }
+    }
+}
+
+/// Lexicon transducer along with its symbol tables.
+#[derive(Debug, Clone)]
+pub struct LexiconFst {
+    /// Transducer mapping phone sequences to word sequences.
+    pub fst: VectorFst<TropicalWeight>,
+    /// Phone symbol table extended with the disambiguation symbols.
+    pub phone_symbols: Arc<SymbolTable>,
+    /// Word symbol table extended with the backoff disambiguation symbol.
+    pub word_symbols: Arc<SymbolTable>,
+    /// Labels of the disambiguation symbols in the phone symbol table.
+    pub phone_disambig_labels: Vec<Label>,
+    /// Labels of the disambiguation symbols in the word symbol table.
+    pub word_disambig_labels: Vec<Label>,
+}
+
+fn position_dependent(phones: &[String]) -> Vec<String> {
+    let n = phones.len();
+    phones
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let suffix = match (i, n) {
+                (_, 1) => "_S",
+                (0, _) => "_B",
+                (i, n) if i == n - 1 => "_E",
+                _ => "_I",
+            };
+            format!("{}{}", p, suffix)
+        })
+        .collect()
+}
+
+// Appends `#1`, `#2`... to the pronunciations that are homophones or prefix of another
+// pronunciation, so that the lexicon becomes determinizable. Returns the highest index used.
+fn add_disambig_symbols(prons: &mut [Vec<String>]) -> usize {
+    let mut counts: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut prefixes: HashSet<Vec<String>> = HashSet::new();
+    for pron in prons.iter() {
+        *counts.entry(pron.clone()).or_insert(0) += 1;
+        for i in 0..pron.len() {
+            prefixes.insert(pron[..i].to_vec());
+        }
+    }
+
+    let mut last_disambig: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut max_disambig = 0;
+    for pron in prons.iter_mut() {
+        if counts[pron] == 1 && !prefixes.contains(pron) {
+            continue;
+        }
+        let n = last_disambig.entry(pron.clone()).or_insert(0);
+        *n += 1;
+        max_disambig = max_disambig.max(*n);
+        pron.push(format!("#{}", n));
+    }
+    max_disambig
+}
+
+fn get_or_add(symt: &mut SymbolTable, symbol: &str) -> Label {
+    match symt.get_label(symbol) {
+        Some(label) => label,
+        None => symt.add_symbol(symbol),
+    }
+}
+
+/// Builds the lexicon transducer (usually called L) mapping phone sequences to words.
+///
+/// Disambiguation symbols `#1`, `#2`... are appended to the pronunciations that are homophones
+/// or prefix of another pronunciation so that `L o G` can be determinized. The first phone of
+/// each pronunciation outputs the word, the next ones output epsilon. The weight of a
+/// pronunciation is the negated natural logarithm of its probability.
+///
+/// If a silence phone is configured, it can optionally be inserted at the beginning and after
+/// each word with the configured probability.
+///
+/// # Example
+/// ```
+/// # use rustfst::lexicon::{lexicon_to_fst, LexiconConfig, LexiconEntry};
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let entries = vec![
+///     LexiconEntry::new("red", vec!["r", "eh", "d"], 1.0),
+///     LexiconEntry::new("read", vec!["r", "eh", "d"], 1.0),
+/// ];
+/// let lexicon = lexicon_to_fst(&entries, &LexiconConfig::default())?;
+///
+/// assert!(lexicon.phone_symbols.contains_symbol("#2"));
+/// assert_eq!(lexicon.phone_disambig_labels.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+pub fn lexicon_to_fst(entries: &[LexiconEntry], config: &LexiconConfig) -> Result<LexiconFst> {
+    let mut phone_symt = config.phone_symbol_table.clone().unwrap_or_default();
+    let mut word_symt = config.word_symbol_table.clone().unwrap_or_default();
+
+    let mut prons: Vec<Vec<String>> = entries
+        .iter()
+        .map(|e| {
+            if config.position_dependent_phones {
+                position_dependent(&e.phones)
+            } else {
+                e.phones.clone()
+            }
+        })
+        .collect();
+    for pron in prons.iter() {
+        for phone in pron.iter() {
+            get_or_add(&mut phone_symt, phone);
+        }
+    }
+    if let Some(silence_phone) = &config.silence_phone {
+        get_or_add(&mut phone_symt, silence_phone);
+    }
+
+    let max_disambig = add_disambig_symbols(&mut prons);
+    let mut phone_disambig_labels = vec![];
+    let mut word_disambig_labels = vec![];
+    if let Some(backoff_symbol) = &config.backoff_disambig_symbol {
+        phone_disambig_labels.push(get_or_add(&mut phone_symt, backoff_symbol));
+        word_disambig_labels.push(get_or_add(&mut word_symt, backoff_symbol));
+    }
+    for n in 1..=max_disambig {
+        phone_disambig_labels.push(get_or_add(&mut phone_symt, &format!("#{}", n)));
+    }
+
+    let mut fst = VectorFst::<TropicalWeight>::new();
+    let start = fst.add_state();
+    fst.set_start(start)?;
+    let (loop_state, silence) = match &config.silence_phone {
+        Some(silence_phone) => {
+            if config.silence_prob <= 0.0 || config.silence_prob >= 1.0 {
+                bail!(
+                    "Lexicon: silence probability must be in ]0, 1[, got {}",
+                    config.silence_prob
+                );
+            }
+            let loop_state = fst.add_state();
+            let silence_state = fst.add_state();
+            let silence_cost = TropicalWeight::new(-config.silence_prob.ln());
+            let no_silence_cost = TropicalWeight::new(-(1.0 - config.silence_prob).ln());
+            let silence_label = phone_symt.get_label(silence_phone).unwrap();
+            fst.add_tr(
+                start,
+                Tr::new(EPS_LABEL, EPS_LABEL, no_silence_cost, loop_state),
+            )?;
+            fst.add_tr(
+                start,
+                Tr::new(EPS_LABEL, EPS_LABEL, silence_cost, silence_state),
+            )?;
+            fst.add_tr(
+                silence_state,
+                Tr::new(silence_label, EPS_LABEL, TropicalWeight::one(), loop_state),
+            )?;
+            (
+                loop_state,
+                Some((silence_state, silence_cost, no_silence_cost)),
+            )
+        }
+        None => (start, None),
+    };
+    fst.set_final(loop_state, TropicalWeight::one())?;
+
+    for (entry, pron) in entries.iter().zip(prons.iter()) {
+        if pron.is_empty() {
+            bail!("Lexicon: empty pronunciation for word {:?}", entry.word);
+        }
+        if entry.prob <= 0.0 {
+            bail!(
+                "Lexicon: pronunciation probability must be positive for word {:?}",
+                entry.word
+            );
+        }
+        let word_label = get_or_add(&mut word_symt, &entry.word);
+        let pron_cost = TropicalWeight::new(-entry.prob.ln());
+
+        let mut state = loop_state;
+        for (i, phone) in pron.iter().enumerate() {
+            let phone_label = phone_symt.get_label(phone).unwrap();
+            let olabel = if i == 0 { word_label } else { EPS_LABEL };
+            let weight = if i == 0 {
+                pron_cost
+            } else {
+                TropicalWeight::one()
+            };
+            if i + 1 < pron.len() {
+                let nextstate = fst.add_state();
+                fst.add_tr(state, Tr::new(phone_label, olabel, weight, nextstate))?;
+                state = nextstate;
+            } else if let Some((silence_state, silence_cost, no_silence_cost)) = silence {
+                fst.add_tr(
+                    state,
+                    Tr::new(
+                        phone_label,
+                        olabel,
+                        weight.times(no_silence_cost)?,
+                        loop_state,
+                    ),
+                )?;
+                fst.add_tr(
+                    state,
+                    Tr::new(
+                        phone_label,
+                        olabel,
+                        weight.times(silence_cost)?,
+                        silence_state,
+                    ),
+                )?;
+            } else {
+                fst.add_tr(state, Tr::new(phone_label, olabel, weight, loop_state))?;
+            }
+        }
+    }
+
+    if let (Some(&phone_backoff), Some(&word_backoff)) =
+        (phone_disambig_labels.first(), word_disambig_labels.first())
+    {
+        fst.add_tr(
+            loop_state,
+            Tr::new(
+                phone_backoff,
+                word_backoff,
+                TropicalWeight::one(),
+                loop_state,
+            ),
+        )?;
+    }
+
+    let phone_symbols = Arc::new(phone_symt);
+    let word_symbols = Arc::new(word_symt);
+    fst.set_input_symbols(Arc::clone(&phone_symbols));
+    fst.set_output_symbols(Arc::clone(&word_symbols));
+
+    Ok(LexiconFst {
+        fst,
+        phone_symbols,
+        word_symbols,
+        phone_disambig_labels,
+        word_disambig_labels,
+    })
+}
+
+/// Replaces the disambiguation symbols by epsilons, typically on the input side of a decoding
+/// graph once it has been determinized.
+pub fn rm_disambig_symbols<W, F>(
+    fst: &mut F,
+    idisambig_labels: &[Label],
+    odisambig_labels: &[Label],
+) -> Result<()>
+where
+    W: Semiring,
+    F: MutableFst<W>,
+{
+    relabel_pairs(
+        fst,
+        idisambig_labels.iter().map(|l| (*l, EPS_LABEL)),
+        odisambig_labels.iter().map(|l| (*l, EPS_LABEL)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::compose::compose;
+    use crate::fst_traits::{CoreFst, ExpandedFst, StateIterator};
+    use crate::utils::acceptor;
+    use crate::Trs;
+
+    fn entries() -> Vec<LexiconEntry> {
+        vec![
+            LexiconEntry::new("red", vec!["r", "eh", "d"], 1.0),
+            LexiconEntry::new("read", vec!["r", "eh", "d"], 1.0),
+            LexiconEntry::new("a", vec!["ax"], 0.5),
+            LexiconEntry::new("a", vec!["ey"], 0.5),
+            LexiconEntry::new("about", vec!["ax", "b", "aw", "t"], 1.0),
+        ]
+    }
+
+    fn transduce(lexicon: &LexiconFst, phones: &[&str]) -> Result<Vec<Vec<Label>>> {
+        let labels: Vec<_> = phones
+            .iter()
+            .map(|p| lexicon.phone_symbols.get_label(p).unwrap())
+            .collect();
+        let input: VectorFst<TropicalWeight> = acceptor(&labels, TropicalWeight::one());
+        let composed: VectorFst<TropicalWeight> = compose(input, lexicon.fst.clone())?;
+        Ok(composed.paths_iter().map(|p| p.olabels).collect())
+    }
+
+    #[test]
+    fn test_lexicon_disambig_symbols() -> Result<()> {
+        let lexicon = lexicon_to_fst(&entries(), &LexiconConfig::default())?;
+        let phones = &lexicon.phone_symbols;
+        let words = &lexicon.word_symbols;
+
+        for symbol in ["#0", "#1", "#2"].iter() {
+            assert!(phones.contains_symbol(symbol));
+        }
+        assert!(!phones.contains_symbol("#3"));
+        assert_eq!(lexicon.phone_disambig_labels.len(), 3);
+        assert_eq!(
+            lexicon.word_disambig_labels,
+            vec![words.get_label("#0").unwrap()]
+        );
+
+        let red = words.get_label("red").unwrap();
+        let read = words.get_label("read").unwrap();
+        let a = words.get_label("a").unwrap();
+        assert_eq!(
+            transduce(&lexicon, &["r", "eh", "d", "#1"])?,
+            vec![vec![red]]
+        );
+        assert_eq!(
+            transduce(&lexicon, &["r", "eh", "d", "#2"])?,
+            vec![vec![read]]
+        );
+        // "ax" is a prefix of "ax b aw t".
+        assert!(transduce(&lexicon, &["ax"])?.is_empty());
+        assert_eq!(transduce(&lexicon, &["ax", "#1"])?, vec![vec![a]]);
+        assert_eq!(transduce(&lexicon, &["ey"])?, vec![vec![a]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicon_silence() -> Result<()> {
+        let config = LexiconConfig::default().with_silence("sil", 0.25);
+        let lexicon = lexicon_to_fst(&entries(), &config)?;
+        let about = lexicon.word_symbols.get_label("about").unwrap();
+        let a = lexicon.word_symbols.get_label("a").unwrap();
+
+        let paths = transduce(&lexicon, &["sil", "ey", "ax", "b", "aw", "t", "sil"])?;
+        assert_eq!(paths, vec![vec![a, about]]);
+
+        let input: VectorFst<TropicalWeight> = acceptor(
+            &[lexicon.phone_symbols.get_label("ey").unwrap()],
+            TropicalWeight::one(),
+        );
+        let composed: VectorFst<TropicalWeight> = compose(input, lexicon.fst.clone())?;
+        let path = composed.paths_iter().next().unwrap();
+        let expected = -(0.75f32.ln()) * 2.0 - 0.5f32.ln();
+        assert!((path.weight.value() - expected).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicon_position_dependent_phones() -> Result<()> {
+        let config = LexiconConfig::default()
+            .with_position_dependent_phones(true)
+            .with_backoff_disambig_symbol(None);
+        let lexicon = lexicon_to_fst(&entries(), &config)?;
+        let phones = &lexicon.phone_symbols;
+
+        for symbol in ["r_B", "eh_I", "d_E", "ax_S", "ax_B", "t_E"].iter() {
+            assert!(phones.contains_symbol(symbol));
+        }
+        assert!(!phones.contains_symbol("ax"));
+        assert!(!phones.contains_symbol("#0"));
+        // "ax_S" is not a prefix of "ax_B b_I aw_I t_E" anymore.
+        assert_eq!(lexicon.phone_disambig_labels.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicon_extends_word_symbol_table() -> Result<()> {
+        let mut words = SymbolTable::new();
+        words.add_symbol("about");
+        words.add_symbol("hello");
+        let config = LexiconConfig::default().with_word_symbol_table(words);
+        let lexicon = lexicon_to_fst(&entries(), &config)?;
+
+        assert_eq!(lexicon.word_symbols.get_label("about"), Some(1));
+        assert_eq!(lexicon.word_symbols.get_label("hello"), Some(2));
+        assert!(lexicon.word_symbols.contains_symbol("red"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rm_disambig_symbols() -> Result<()> {
+        let mut lexicon = lexicon_to_fst(&entries(), &LexiconConfig::default())?;
+        rm_disambig_symbols(
+            &mut lexicon.fst,
+            &lexicon.phone_disambig_labels,
+            &lexicon.word_disambig_labels,
+        )?;
+        for s in lexicon.fst.states_iter() {
+            for tr in lexicon.fst.get_trs(s)?.trs() {
+                assert!(!lexicon.phone_disambig_labels.contains(&tr.ilabel));
+                assert!(!lexicon.word_disambig_labels.contains(&tr.olabel));
+            }
+        }
+        assert!(lexicon.fst.num_states() > 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexicon_empty_pronunciation() {
+        let entries = vec![LexiconEntry::new("hmm", vec![], 1.0)];
+        assert!(lexicon_to_fst(&entries, &LexiconConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+mod lexicon_to_fst;
+
+pub use self::lexicon_to_fst::{
+    lexicon_to_fst, rm_disambig_symbols, LexiconConfig, LexiconEntry, LexiconFst,
+};
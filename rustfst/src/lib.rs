@@ -79,6 +79,30 @@
 //! [iterate over the paths](fst_traits::Fst::string_paths_iter) in
 //! the resulting FST.
 //!
+//! ## Features
+//!
+//! - `state-label-u32` (enabled by default): states and labels are `u32` instead of
+//!   `usize`.
+//! - `serde`: implements `Serialize` and `Deserialize` for [`Tr`], the weights,
+//!   [`SymbolTable`] and the [`VectorFst`](fst_impls::VectorFst) and
+//!   [`ConstFst`](fst_impls::ConstFst) implementations, e.g to embed FSTs in JSON or bincode
+//!   documents.
+//! - `petgraph`: implements the [`petgraph`](https://docs.rs/petgraph) graph traits for
+//!   references to [`VectorFst`](fst_impls::VectorFst) and [`ConstFst`](fst_impls::ConstFst),
+//!   so that the `petgraph` algorithms can run on FSTs without copying them, and adds
+//!   `VectorFst::from_petgraph` to convert a `petgraph::Graph` into an FST.
+//! - `proptest`: exposes the `proptest_fst` module and its [`proptest`](https://docs.rs/proptest)
+//!   strategies generating random FSTs (acyclic or cyclic, acceptors or transducers, with a
+//!   configurable epsilon density, label alphabet and semiring), to property-test FST pipelines.
+//!
+//! ## Errors
+//!
+//! Errors are returned as [`anyhow::Error`]. Failures that callers may want to handle
+//! (invalid state id, unsorted or non-functional FST, parse and I/O errors, ...) wrap an
+//! [`Error`], which can be retrieved with [`Error::find`] to match on the kind of failure.
+//! When one of the [`AlgorithmLimits`](algorithms::AlgorithmLimits) is hit, the
+//! [`LimitExceeded`](algorithms::LimitExceeded) can also be retrieved with `downcast_ref`.
+//!
 //! ## References
 //!
 //! Implementation heavily inspired from Mehryar Mohri's, Cyril Allauzen's and Michael Riley's work :
@@ -185,9 +209,7 @@
 //!   preferably checking for overflows
 //! - Symbol IDs are also unsigned and 32-bits, with [`NO_LABEL`] used
 //!   for a missing value.
-//! - Floating-point weights are not generic, so are always single-precision.
 
-#[warn(missing_docs)]
 #[cfg(test)]
 extern crate counter;
 #[macro_use]
@@ -198,6 +220,7 @@ extern crate serde;
 extern crate serde_json;
 
 pub use crate::drawing_config::DrawingConfig;
+pub use crate::error::Error;
 pub use crate::fst_path::{check_path_in_fst, FstPath};
 pub use crate::string_path::StringPath;
 pub use crate::symbol_table::SymbolTable;
@@ -243,6 +266,15 @@ pub mod utils;
 /// Provides algorithms that are generic to all Fst.
 pub mod algorithms;
 
+/// Parsing of ARPA n-gram language models and conversion to grammar FSTs.
+pub mod arpa;
+
+/// Construction of lexicon FSTs from pronunciation dictionaries.
+pub mod lexicon;
+
+/// Viterbi beam search decoder over an FST driven by an acoustic model.
+pub mod decoder;
+
 /// Provides the `FstProperties` struct and some utils functions around it.
 /// Useful to assert some properties on a Fst.
 pub mod fst_properties;
@@ -258,12 +290,18 @@ pub mod fst_impls;
 pub mod semirings;
 
 mod drawing_config;
+mod error;
 /// Implementation of a successful path inside a wFST.
 mod fst_path;
 mod parsers;
+#[cfg(feature = "petgraph")]
+pub mod petgraph_impls;
+#[cfg(feature = "serde")]
+mod serde_impls;
 mod string_path;
 
 pub use crate::parsers::nom_utils::NomCustomError;
+pub use crate::parsers::text_fst::read_text_fst;
 
 /// A representable float near .001. (Used in Quantize)
 pub const KDELTA: f32 = 1.0f32 / 1024.0f32;
@@ -284,7 +322,7 @@ pub mod prelude {
     pub use crate::*;
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "proptest"))]
 pub mod proptest_fst;
 
 /// Used to indicate a transition with no label.
@@ -3,12 +3,14 @@ use std::io::Write;
 use anyhow::Result;
 use nom::bytes::complete::take;
 use nom::combinator::{map_res, verify};
+use nom::sequence::tuple;
 use nom::IResult;
 
 use bitflags::bitflags;
 
+use crate::fst_properties::FstProperties;
 use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::{check_count, NomCustomError};
 use crate::parsers::{parse_bin_i32, parse_bin_i64, parse_bin_u32, parse_bin_u64};
 use crate::parsers::{write_bin_i32, write_bin_i64, write_bin_u32, write_bin_u64};
 use crate::SymbolTable;
@@ -67,7 +69,22 @@ fn optionally_write_symt<W: Write>(file: &mut W, symt: &Option<Arc<SymbolTable>>
     }
 }
 
+/// A property and its negation can't be both set.
+fn consistent_properties(properties: u64) -> bool {
+    let properties = FstProperties::from_bits_truncate(properties);
+    let pos = properties & FstProperties::pos_trinary_properties();
+    let neg = properties & FstProperties::neg_trinary_properties();
+    (pos.bits() & (neg.bits() >> 1)) == 0
+}
+
 impl FstHeader {
+    /// Parses the type of the FST stored at the beginning of `i` without checking it.
+    pub(crate) fn parse_fst_type(i: &[u8]) -> IResult<&[u8], String, NomCustomError<&[u8]>> {
+        let (i, _) = verify(parse_bin_i32, |v: &i32| *v == FST_MAGIC_NUMBER)(i)?;
+        let (i, fst_type) = OpenFstString::parse(i)?;
+        Ok((i, fst_type.s))
+    }
+
     pub(crate) fn parse<S1: AsRef<str>, S2: AsRef<str>>(
         i: &[u8],
         min_file_version: i32,
@@ -85,10 +102,15 @@ impl FstHeader {
         let (i, flags) = map_res(parse_bin_u32, |v: u32| {
             FstFlags::from_bits(v).ok_or("Could not parse Fst Flags")
         })(i)?;
-        let (i, properties) = parse_bin_u64(i)?;
-        let (i, start) = parse_bin_i64(i)?;
-        let (i, num_states) = parse_bin_i64(i)?;
-        let (i, num_trs) = parse_bin_i64(i)?;
+        let (i, properties) = verify(parse_bin_u64, |v: &u64| consistent_properties(*v))(i)?;
+        let (i, num_states_and_start) = verify(
+            tuple((parse_bin_i64, parse_bin_i64)),
+            |(start, num_states): &(i64, i64)| {
+                *num_states >= 0 && (*start == -1 || (*start >= 0 && *start < *num_states))
+            },
+        )(i)?;
+        let (start, num_states) = num_states_and_start;
+        let (i, num_trs) = verify(parse_bin_i64, |v: &i64| *v >= 0)(i)?;
 
         let (i, isymt) = optionally_parse_symt(i, flags.contains(FstFlags::HAS_ISYMBOLS))?;
         let (i, osymt) = optionally_parse_symt(i, flags.contains(FstFlags::HAS_OSYMBOLS))?;
@@ -146,14 +168,9 @@ impl OpenFstString {
     }
     pub(crate) fn parse(i: &[u8]) -> IResult<&[u8], OpenFstString, NomCustomError<&[u8]>> {
         let (i, n) = parse_bin_i32(i)?;
-        let (i, s) = take(n as usize)(i)?;
-        Ok((
-            i,
-            OpenFstString {
-                n,
-                s: String::from_utf8(s.to_vec()).unwrap(),
-            },
-        ))
+        let len = check_count(i, n as i64, 1)?;
+        let (i, s) = map_res(take(len), |s: &[u8]| String::from_utf8(s.to_vec()))(i)?;
+        Ok((i, OpenFstString { n, s }))
     }
 
     pub(crate) fn write<W: Write>(&self, file: &mut W) -> Result<()> {
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use ::proptest::prelude::*;
+use ::proptest::sample::Index;
+use anyhow::Result;
+
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst, SerializableFst};
+use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
+use crate::semirings::TropicalWeight;
+use crate::{Error, SymbolTable, Trs};
+
+/// Corruption applied to a serialized object.
+#[derive(Debug, Clone)]
+enum Corruption {
+    Truncate(Index),
+    FlipByte(Index, u8),
+    OverwriteI32(Index, i32),
+    OverwriteI64(Index, i64),
+}
+
+fn extreme_i32() -> impl Strategy<Value = i32> {
+    prop_oneof![
+        Just(-1),
+        Just(0),
+        Just(i32::MIN),
+        Just(i32::MAX),
+        Just(1 << 20),
+        any::<i32>(),
+    ]
+}
+
+fn extreme_i64() -> impl Strategy<Value = i64> {
+    prop_oneof![
+        Just(-2),
+        Just(-1),
+        Just(0),
+        Just(i64::MIN),
+        Just(i64::MAX),
+        Just(1 << 40),
+        any::<i64>(),
+    ]
+}
+
+fn corruption() -> impl Strategy<Value = Corruption> {
+    prop_oneof![
+        any::<Index>().prop_map(Corruption::Truncate),
+        (any::<Index>(), 1..=u8::MAX).prop_map(|(idx, mask)| Corruption::FlipByte(idx, mask)),
+        (any::<Index>(), extreme_i32()).prop_map(|(idx, v)| Corruption::OverwriteI32(idx, v)),
+        (any::<Index>(), extreme_i64()).prop_map(|(idx, v)| Corruption::OverwriteI64(idx, v)),
+    ]
+}
+
+fn overwrite(data: &mut [u8], idx: &Index, bytes: &[u8]) {
+    if data.len() >= bytes.len() {
+        let pos = idx.index(data.len() - bytes.len() + 1);
+        data[pos..pos + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+fn corrupt(mut data: Vec<u8>, corruptions: &[Corruption]) -> Vec<u8> {
+    for c in corruptions {
+        if data.is_empty() {
+            break;
+        }
+        match c {
+            Corruption::Truncate(idx) => {
+                let len = idx.index(data.len());
+                data.truncate(len);
+            }
+            Corruption::FlipByte(idx, mask) => {
+                let pos = idx.index(data.len());
+                data[pos] ^= mask;
+            }
+            Corruption::OverwriteI32(idx, v) => overwrite(&mut data, idx, &v.to_le_bytes()),
+            Corruption::OverwriteI64(idx, v) => overwrite(&mut data, idx, &v.to_le_bytes()),
+        }
+    }
+    data
+}
+
+fn with_symbols(mut fst: VectorFst<TropicalWeight>, symbols: bool) -> VectorFst<TropicalWeight> {
+    if symbols {
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(vec!["a", "b", "c"]);
+        let symt = Arc::new(symt);
+        fst.set_input_symbols(Arc::clone(&symt));
+        fst.set_output_symbols(symt);
+    }
+    fst
+}
+
+/// Checks that a successfully parsed FST can be walked through without panicking.
+fn check_parsed_fst<F: ExpandedFst<TropicalWeight>>(fst: &F) -> Result<()> {
+    let num_states = fst.num_states() as u32;
+    if let Some(start) = fst.start() {
+        assert!(start < num_states);
+    }
+    for state in fst.states_iter() {
+        fst.final_weight(state)?;
+        for tr in fst.get_trs(state)?.trs() {
+            assert!(tr.nextstate < num_states);
+        }
+        assert!(fst.num_input_epsilons(state)? <= fst.num_trs(state)?);
+        assert!(fst.num_output_epsilons(state)? <= fst.num_trs(state)?);
+    }
+    Ok(())
+}
+
+fn check_parse_error<T>(res: Result<T>) {
+    if let Err(e) = res {
+        assert!(matches!(Error::find(&e), Some(Error::Parse(_))), "{:?}", e);
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_fuzz_vector_fst_bin(
+        fst in any::<VectorFst<TropicalWeight>>(),
+        symbols in any::<bool>(),
+        corruptions in prop::collection::vec(corruption(), 1..4),
+    ) {
+        let mut data = vec![];
+        with_symbols(fst, symbols).store(&mut data).unwrap();
+        let res = VectorFst::<TropicalWeight>::load(&corrupt(data, &corruptions));
+        if let Ok(parsed) = &res {
+            check_parsed_fst(parsed).unwrap();
+        }
+        check_parse_error(res);
+    }
+
+    #[test]
+    fn test_fuzz_const_fst_bin(
+        fst in any::<VectorFst<TropicalWeight>>(),
+        symbols in any::<bool>(),
+        corruptions in prop::collection::vec(corruption(), 1..4),
+    ) {
+        let mut data = vec![];
+        ConstFst::from(with_symbols(fst, symbols)).store(&mut data).unwrap();
+        let data = corrupt(data, &corruptions);
+
+        let res = ConstFst::<TropicalWeight>::load(&data);
+        if let Ok(parsed) = &res {
+            check_parsed_fst(parsed).unwrap();
+        }
+        check_parse_error(res);
+
+        let res = VectorFst::<TropicalWeight>::load_from_const(&data);
+        if let Ok(parsed) = &res {
+            check_parsed_fst(parsed).unwrap();
+        }
+        check_parse_error(res);
+    }
+
+    #[test]
+    fn test_fuzz_symbol_table_bin(
+        symbols in prop::collection::vec("[a-z]{0,5}", 0..10),
+        corruptions in prop::collection::vec(corruption(), 1..4),
+    ) {
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(symbols);
+        let mut data = vec![];
+        write_bin_symt(&mut data, &symt).unwrap();
+        if let Ok((_, parsed)) = parse_symbol_table_bin(&corrupt(data, &corruptions)) {
+            for (label, symbol) in parsed.iter() {
+                prop_assert_eq!(parsed.get_label(symbol), Some(label));
+            }
+        }
+    }
+}
+
+fn vector_fst_bin() -> Result<Vec<u8>> {
+    let mut fst = VectorFst::<TropicalWeight>::new();
+    let s0 = fst.add_state();
+    let s1 = fst.add_state();
+    fst.set_start(s0)?;
+    fst.emplace_tr(s0, 1, 2, 0.5, s1)?;
+    fst.set_final(s1, 1.0)?;
+    let mut data = vec![];
+    fst.store(&mut data)?;
+    Ok(data)
+}
+
+/// Offset of the properties in the header of `vector_fst_bin`.
+/// Magic number, "vector", "standard" and version, flags.
+static PROPERTIES_OFFSET: usize = 4 + (4 + 6) + (4 + 8) + 4 + 4;
+
+fn check_rejected(data: &[u8]) {
+    let err = VectorFst::<TropicalWeight>::load(data).unwrap_err();
+    assert!(
+        matches!(Error::find(&err), Some(Error::Parse(_))),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_bin_fst_huge_num_states() -> Result<()> {
+    let mut data = vector_fst_bin()?;
+    let offset = PROPERTIES_OFFSET + 8 + 8;
+    data[offset..offset + 8].copy_from_slice(&(i64::MAX / 2).to_le_bytes());
+    check_rejected(&data);
+    data[offset..offset + 8].copy_from_slice(&(-5i64).to_le_bytes());
+    check_rejected(&data);
+    Ok(())
+}
+
+#[test]
+fn test_bin_fst_invalid_start() -> Result<()> {
+    let mut data = vector_fst_bin()?;
+    let offset = PROPERTIES_OFFSET + 8;
+    data[offset..offset + 8].copy_from_slice(&2i64.to_le_bytes());
+    check_rejected(&data);
+    data[offset..offset + 8].copy_from_slice(&(-2i64).to_le_bytes());
+    check_rejected(&data);
+    // No start state is allowed.
+    data[offset..offset + 8].copy_from_slice(&(-1i64).to_le_bytes());
+    let fst = VectorFst::<TropicalWeight>::load(&data)?;
+    assert_eq!(fst.start(), None);
+    Ok(())
+}
+
+#[test]
+fn test_bin_fst_contradictory_properties() -> Result<()> {
+    let mut data = vector_fst_bin()?;
+    let properties = crate::fst_properties::FstProperties::ACCEPTOR
+        | crate::fst_properties::FstProperties::NOT_ACCEPTOR;
+    data[PROPERTIES_OFFSET..PROPERTIES_OFFSET + 8]
+        .copy_from_slice(&properties.bits().to_le_bytes());
+    check_rejected(&data);
+    Ok(())
+}
+
+#[test]
+fn test_bin_fst_invalid_nextstate() -> Result<()> {
+    let mut data = vector_fst_bin()?;
+    // Nextstate of the only transition : followed by the final weight of s1 and its number of trs.
+    let offset = data.len() - 4 - 8 - 4;
+    assert_eq!(data[offset..offset + 4], 1i32.to_le_bytes());
+    data[offset..offset + 4].copy_from_slice(&2i32.to_le_bytes());
+    check_rejected(&data);
+    Ok(())
+}
+
+#[test]
+fn test_bin_fst_invalid_utf8_type() -> Result<()> {
+    let mut data = vector_fst_bin()?;
+    // First byte of "vector".
+    data[8] = 0xff;
+    check_rejected(&data);
+    Ok(())
+}
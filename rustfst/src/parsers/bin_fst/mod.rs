@@ -1,3 +1,9 @@
 pub(crate) mod fst_header;
 pub(crate) mod utils_parsing;
 pub(crate) mod utils_serialization;
+
+#[cfg(test)]
+mod fuzz_tests;
+
+#[cfg(test)]
+mod serialization_tests;
@@ -0,0 +1,30 @@
+use anyhow::Result;
+
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::{CoreFst, MutableFst, SerializableFst};
+use crate::semirings::{LogWeight, LogWeight64, Semiring, TropicalWeight64};
+use crate::{Tr, Trs};
+
+#[test]
+fn test_serialize_fst_64() -> Result<()> {
+    let mut fst = VectorFst::<LogWeight64>::new();
+    fst.add_states(2);
+    fst.set_start(0)?;
+    fst.add_tr(0, Tr::new(1, 2, 1e-9, 1))?;
+    fst.set_final(1, 0.25)?;
+    assert_eq!(Tr::<LogWeight64>::tr_type(), "log64");
+    assert_eq!(Tr::<TropicalWeight64>::tr_type(), "tropical64");
+
+    let mut data = vec![];
+    fst.store(&mut data)?;
+    let loaded = VectorFst::<LogWeight64>::load(&data)?;
+    assert_eq!(loaded, fst);
+    assert_eq!(*loaded.get_trs(0)?.trs()[0].weight.value(), 1e-9);
+    assert!(VectorFst::<LogWeight>::load(&data).is_err());
+
+    let const_fst: ConstFst<_> = fst.clone().into();
+    let mut data = vec![];
+    const_fst.store(&mut data)?;
+    assert_eq!(ConstFst::<LogWeight64>::load(&data)?, const_fst);
+    Ok(())
+}
@@ -1,7 +1,7 @@
+use nom::combinator::verify;
 use nom::number::complete::le_i32;
-use nom::IResult;
 
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::IResultCustomError;
 use crate::semirings::SerializableSemiring;
 use crate::{Label, StateId, Tr};
 
@@ -25,20 +25,26 @@ pub(crate) fn parse_final_weight<W: SerializableSemiring>(weight: W) -> Option<W
     }
 }
 
+/// Minimum size of a serialized transition : labels and nextstate, the weight may be empty.
+pub(crate) static MIN_BIN_TR_SIZE: usize = 12;
+
+/// Parses a transition, checking that its nextstate is lower than `num_states`.
 pub(crate) fn parse_bin_fst_tr<W: SerializableSemiring>(
-    i: &[u8],
-) -> IResult<&[u8], Tr<W>, NomCustomError<&[u8]>> {
-    let (i, ilabel) = le_i32(i)?;
-    let (i, olabel) = le_i32(i)?;
-    let (i, weight) = W::parse_binary(i)?;
-    let (i, nextstate) = le_i32(i)?;
-    Ok((
-        i,
-        Tr {
-            ilabel: ilabel as Label,
-            olabel: olabel as Label,
-            weight,
-            nextstate: nextstate as StateId,
-        },
-    ))
+    num_states: usize,
+) -> impl Fn(&[u8]) -> IResultCustomError<&[u8], Tr<W>> {
+    move |i: &[u8]| {
+        let (i, ilabel) = le_i32(i)?;
+        let (i, olabel) = le_i32(i)?;
+        let (i, weight) = W::parse_binary(i)?;
+        let (i, nextstate) = verify(le_i32, |v: &i32| *v >= 0 && (*v as usize) < num_states)(i)?;
+        Ok((
+            i,
+            Tr {
+                ilabel: ilabel as Label,
+                olabel: olabel as Label,
+                weight,
+                nextstate: nextstate as StateId,
+            },
+        ))
+    }
 }
@@ -4,7 +4,7 @@ use nom::IResult;
 use std::hash::BuildHasher;
 
 use crate::parsers::bin_fst::fst_header::OpenFstString;
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::{check_count, NomCustomError};
 use crate::parsers::{parse_bin_i32, parse_bin_i64};
 use crate::parsers::{write_bin_i32, write_bin_i64};
 use crate::{Label, SymbolTable};
@@ -26,7 +26,9 @@ pub(crate) fn parse_symbol_table_bin(
     let (i, _name) = OpenFstString::parse(i)?;
     let (i, _available_key) = parse_bin_i64(i)?;
     let (i, num_symbols) = parse_bin_i64(i)?;
-    let (i, pairs_idx_symbols) = count(parse_row_symt, num_symbols as usize)(i)?;
+    // Each row contains at least the length of the symbol and its key.
+    let num_symbols = check_count(i, num_symbols, 12)?;
+    let (i, pairs_idx_symbols) = count(parse_row_symt, num_symbols)(i)?;
 
     let mut symt = SymbolTable::empty();
     for (key, symbol) in pairs_idx_symbols.into_iter() {
@@ -2,11 +2,14 @@ use nom::bytes::complete::take_while;
 use nom::character::complete::digit1;
 use nom::combinator::map_res;
 use nom::IResult;
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 use nom::error::ErrorKind;
 use nom::error::{FromExternalError, ParseError};
 
+use crate::Error;
+
 #[derive(Debug, PartialEq)]
 pub enum NomCustomError<I> {
     SymbolTableError(String),
@@ -29,6 +32,43 @@ impl<I, E> FromExternalError<I, E> for NomCustomError<I> {
     }
 }
 
+pub(crate) type IResultCustomError<A, B> = IResult<A, B, NomCustomError<A>>;
+
+/// Validates a number of items read from a binary input : it must be non-negative and the
+/// remaining input must be large enough to contain that many items of at least
+/// `min_item_size` bytes. This prevents corrupted counts from triggering huge allocations.
+pub(crate) fn check_count(
+    i: &[u8],
+    n: i64,
+    min_item_size: usize,
+) -> Result<usize, nom::Err<NomCustomError<&[u8]>>> {
+    let n = usize::try_from(n)
+        .map_err(|_| nom::Err::Error(NomCustomError::Nom(i, ErrorKind::Verify)))?;
+    match n.checked_mul(min_item_size) {
+        Some(size) if size <= i.len() => Ok(n),
+        _ => Err(nom::Err::Error(NomCustomError::Nom(i, ErrorKind::TooLarge))),
+    }
+}
+
+/// Turns the error returned by a binary parser into a `rustfst::Error::Parse`.
+pub(crate) fn binary_parse_error<I>(err: nom::Err<NomCustomError<I>>, what: &str) -> Error {
+    let msg = match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => match e {
+            NomCustomError::Nom(_, k) => {
+                format!("Error while parsing binary {}. Error kind {:?}", what, k)
+            }
+            NomCustomError::SymbolTableError(e) => {
+                format!(
+                    "Error while parsing symbolTable from binary {} : {}",
+                    what, e
+                )
+            }
+        },
+        nom::Err::Incomplete(_) => format!("Error while parsing binary {}. Incomplete data", what),
+    };
+    Error::Parse(msg)
+}
+
 pub fn num<V: FromStr>(i: &str) -> IResult<&str, V> {
     map_res(digit1, |s: &str| s.parse())(i)
 }
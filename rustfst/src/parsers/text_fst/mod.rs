@@ -1,4 +1,6 @@
 mod nom_parser;
 mod parsed_text_fst;
+mod text_fst_reader;
 
 pub use self::parsed_text_fst::ParsedTextFst;
+pub use self::text_fst_reader::read_text_fst;
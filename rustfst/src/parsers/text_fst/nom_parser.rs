@@ -49,7 +49,7 @@ fn infinity_final_state<W: SerializableSemiring>(i: &str) -> IResult<&str, RowPa
     Ok((i, RowParsed::InfinityFinalState(state)))
 }
 
-fn row_parsed<W: SerializableSemiring>(i: &str) -> IResult<&str, RowParsed<W>> {
+pub(crate) fn row_parsed<W: SerializableSemiring>(i: &str) -> IResult<&str, RowParsed<W>> {
     alt((transition, infinity_final_state, final_state))(i)
 }
 
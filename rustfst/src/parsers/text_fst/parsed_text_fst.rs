@@ -5,7 +5,7 @@ use anyhow::Result;
 
 use crate::parsers::text_fst::nom_parser::vec_rows_parsed;
 use crate::semirings::SerializableSemiring;
-use crate::{Label, StateId};
+use crate::{Error, Label, StateId};
 
 #[derive(Debug, PartialEq)]
 pub enum RowParsed<W: SerializableSemiring> {
@@ -85,8 +85,8 @@ impl<W: SerializableSemiring> ParsedTextFst<W> {
     /// 3   0.67
     /// ```
     pub fn from_string(fst_string: &str) -> Result<Self> {
-        let (_, vec_rows_parsed) =
-            vec_rows_parsed(fst_string).map_err(|_| format_err!("Error while parsing text fst"))?;
+        let (_, vec_rows_parsed) = vec_rows_parsed(fst_string)
+            .map_err(|_| Error::Parse("Error while parsing text fst".to_string()))?;
 
         Ok(Self::from_vec_rows_parsed(vec_rows_parsed))
     }
@@ -135,7 +135,7 @@ impl<W: SerializableSemiring> ParsedTextFst<W> {
     /// 3   0.67
     /// ```
     pub fn from_path<P: AsRef<Path>>(path_fst_text: P) -> Result<Self> {
-        let fst_string = read_to_string(path_fst_text)?;
+        let fst_string = read_to_string(path_fst_text).map_err(Error::Io)?;
         Self::from_string(&fst_string)
     }
 
@@ -192,12 +192,12 @@ mod tests {
         let parsed_fst =
             ParsedTextFst::<TropicalWeight>::from_string("0\t2\t0\t0\n1\n2\t1\t12\t25\n")?;
 
-        let mut transitions = vec![];
-        transitions.push(Transition::new(0, 0, 0, None, 2));
-        transitions.push(Transition::new(2, 12, 25, None, 1));
+        let transitions = vec![
+            Transition::new(0, 0, 0, None, 2),
+            Transition::new(2, 12, 25, None, 1),
+        ];
 
-        let mut final_states = vec![];
-        final_states.push(FinalState::new(1, None));
+        let final_states = vec![FinalState::new(1, None)];
 
         let parsed_fst_ref = ParsedTextFst {
             start_state: Some(0),
@@ -215,11 +215,9 @@ mod tests {
         // Check that parsing transitions, then final states then transition is working
         let parsed_fst = ParsedTextFst::<TropicalWeight>::from_string("0\t1\t0\t0\n1")?;
 
-        let mut transitions = vec![];
-        transitions.push(Transition::new(0, 0, 0, None, 1));
+        let transitions = vec![Transition::new(0, 0, 0, None, 1)];
 
-        let mut final_states = vec![];
-        final_states.push(FinalState::new(1, None));
+        let final_states = vec![FinalState::new(1, None)];
 
         let parsed_fst_ref = ParsedTextFst {
             start_state: Some(0),
@@ -237,17 +235,15 @@ mod tests {
         let parsed_fst =
             ParsedTextFst::<TropicalWeight>::from_string("0\t1\t12\t25\t0.3\n1\tInfinity\n0\t0\n")?;
 
-        let mut transitions = vec![];
-        transitions.push(Transition::new(
+        let transitions = vec![Transition::new(
             0,
             12,
             25,
             Some(TropicalWeight::new(0.3)),
             1,
-        ));
+        )];
 
-        let mut final_states = vec![];
-        final_states.push(FinalState::new(0, Some(TropicalWeight::new(0.0))));
+        let final_states = vec![FinalState::new(0, Some(TropicalWeight::new(0.0)))];
 
         let parsed_fst_ref = ParsedTextFst {
             start_state: Some(0),
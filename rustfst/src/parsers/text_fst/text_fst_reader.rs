@@ -0,0 +1,190 @@
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+
+use crate::fst_traits::MutableFst;
+use crate::parsers::text_fst::nom_parser::row_parsed;
+use crate::parsers::text_fst::parsed_text_fst::RowParsed;
+use crate::semirings::SerializableSemiring;
+use crate::{Error, StateId, Tr};
+
+/// Loads an FST in text format (`At&T FSM format`) by reading it line by line from `reader`.
+///
+/// Contrary to [`SerializableFst::read_text`](crate::fst_traits::SerializableFst::read_text),
+/// the whole content is never held in memory : states and transitions are added to the FST as
+/// soon as their line is parsed. This makes it possible to load very large text files or to read
+/// an FST from the standard input.
+///
+/// The format is the same as the one of `read_text` : the state of the first line is the start
+/// state and unspecified weights default to one. Empty lines are ignored. Weights are parsed
+/// with [`SerializableSemiring::parse_text`].
+///
+/// Errors contain the number of the line that couldn't be parsed.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::prelude::*;
+/// # use rustfst::read_text_fst;
+/// # fn main() -> Result<()> {
+/// let text = "0\t1\t32\t32\n1\t2\t45\t45\t0.25\n2\t0.67\n";
+/// let fst: VectorFst<TropicalWeight> = read_text_fst(text.as_bytes())?;
+///
+/// assert_eq!(fst.start(), Some(0));
+/// assert_eq!(fst.num_states(), 3);
+/// assert_eq!(fst.final_weight(2)?, Some(TropicalWeight::new(0.67)));
+///
+/// // Works with any `BufRead`, for instance the standard input.
+/// # if false {
+/// let fst: VectorFst<TropicalWeight> = read_text_fst(std::io::stdin().lock())?;
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_text_fst<W, F, R>(mut reader: R) -> Result<F>
+where
+    W: SerializableSemiring,
+    F: MutableFst<W>,
+    R: BufRead,
+{
+    let mut fst = F::new();
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        line_number += 1;
+        let n = reader
+            .read_line(&mut line)
+            .map_err(Error::Io)
+            .with_context(|| format!("Line {}: can't read text fst", line_number))?;
+        if n == 0 {
+            break;
+        }
+
+        let row = line.trim_end_matches(['\n', '\r'].as_ref());
+        if row.is_empty() {
+            continue;
+        }
+        let row = match row_parsed::<W>(row) {
+            Ok(("", row)) => row,
+            _ => {
+                return Err(Error::Parse(format!(
+                    "Line {}: invalid text fst row {:?}",
+                    line_number, row
+                ))
+                .into())
+            }
+        };
+
+        let state = match &row {
+            RowParsed::Transition(t) => {
+                add_states_until(&mut fst, t.state.max(t.nextstate));
+                t.state
+            }
+            RowParsed::FinalState(f) => {
+                add_states_until(&mut fst, f.state);
+                f.state
+            }
+            RowParsed::InfinityFinalState(s) => {
+                add_states_until(&mut fst, *s);
+                *s
+            }
+        };
+        if fst.start().is_none() {
+            fst.set_start(state)?;
+        }
+
+        match row {
+            RowParsed::Transition(t) => {
+                let weight = t.weight.unwrap_or_else(W::one);
+                fst.add_tr(t.state, Tr::new(t.ilabel, t.olabel, weight, t.nextstate))?;
+            }
+            RowParsed::FinalState(f) => {
+                fst.set_final(f.state, f.weight.unwrap_or_else(W::one))?;
+            }
+            RowParsed::InfinityFinalState(_) => {}
+        }
+    }
+
+    fst.compute_and_update_properties_all()?;
+
+    Ok(fst)
+}
+
+fn add_states_until<W: SerializableSemiring, F: MutableFst<W>>(fst: &mut F, state: StateId) {
+    let num_states = fst.num_states();
+    if state as usize >= num_states {
+        fst.add_states(state as usize + 1 - num_states);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use ::proptest::prelude::*;
+
+    use super::*;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, SerializableFst};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_read_text_fst_same_as_parsed_text_fst() -> Result<()> {
+        let text = "0\t2\t0\t0\n1\n2\t1\t12\t25\t0.3\n1\tInfinity\n0\t0\n";
+        let fst: VectorFst<TropicalWeight> = read_text_fst(Cursor::new(text))?;
+        let fst_ref = VectorFst::<TropicalWeight>::from_text_string(text)?;
+        assert_eq!(fst, fst_ref);
+        assert_eq!(fst.properties(), fst_ref.properties());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_text_fst_empty_lines_and_crlf() -> Result<()> {
+        let text = "\n0\t1\t3\t3\r\n\n1\t0.5\r\n";
+        let fst: VectorFst<TropicalWeight> = read_text_fst(text.as_bytes())?;
+        let fst_ref = VectorFst::<TropicalWeight>::from_text_string("0\t1\t3\t3\n1\t0.5\n")?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_text_fst_empty() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = read_text_fst("".as_bytes())?;
+        assert_eq!(fst, VectorFst::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_text_fst_error_line_number() -> Result<()> {
+        let text = "0\t1\t3\t3\n1\t2\t4\n2\n";
+        let err = read_text_fst::<TropicalWeight, VectorFst<_>, _>(text.as_bytes()).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::Parse(_))));
+        assert!(err.to_string().starts_with("Line 2:"), "{}", err);
+
+        let text = "0\t1\t3\t3\n\n1\tfoo\n";
+        let err = read_text_fst::<TropicalWeight, VectorFst<_>, _>(text.as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("Line 3:"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_text_fst_invalid_utf8() -> Result<()> {
+        let text = b"0\t1\t3\t3\n\xff\n";
+        let err = read_text_fst::<TropicalWeight, VectorFst<_>, _>(&text[..]).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::Io(_))));
+        assert!(err.to_string().starts_with("Line 2:"), "{}", err);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_read_text_fst_proptest(fst in any::<VectorFst::<TropicalWeight>>()) {
+            let text = fst.text().unwrap();
+            let parsed: VectorFst<TropicalWeight> = read_text_fst(text.as_bytes()).unwrap();
+            prop_assert_eq!(parsed, VectorFst::from_text_string(&text).unwrap());
+        }
+    }
+}
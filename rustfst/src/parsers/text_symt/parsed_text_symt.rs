@@ -4,7 +4,7 @@ use std::path::Path;
 use anyhow::Result;
 
 use crate::parsers::text_symt::nom_parser::parse_text_symt;
-use crate::{Label, Symbol};
+use crate::{Error, Label, Symbol};
 
 #[derive(Debug, PartialEq, Default)]
 pub(crate) struct ParsedTextSymt {
@@ -14,12 +14,12 @@ pub(crate) struct ParsedTextSymt {
 impl ParsedTextSymt {
     pub(crate) fn from_string(symt_string: &str) -> Result<Self> {
         let (_, parsed_symt) = parse_text_symt(symt_string)
-            .map_err(|_| format_err!("Error while parsing text symt"))?;
+            .map_err(|_| Error::Parse("Error while parsing text symt".to_string()))?;
         Ok(parsed_symt)
     }
 
     pub(crate) fn from_path<P: AsRef<Path>>(path_symt_text: P) -> Result<Self> {
-        let symt_string = read_to_string(path_symt_text)?;
+        let symt_string = read_to_string(path_symt_text).map_err(Error::Io)?;
         Self::from_string(&symt_string)
     }
 }
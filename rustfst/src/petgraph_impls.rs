@@ -0,0 +1,454 @@
+//! [`petgraph`] trait implementations for [`VectorFst`] and [`ConstFst`], enabled by the
+//! `petgraph` feature.
+//!
+//! A reference to an FST can be used directly with the graph algorithms of `petgraph` without
+//! copying it : nodes are the states, identified by their `StateId`, and edges are the
+//! transitions, identified by their source state and their index among the transitions leaving
+//! it. Node weights are the final weights and edge weights are the transitions themselves.
+//!
+//! [`VectorFst::from_petgraph`] converts a [`Graph`] whose edge weights are [`TrLike`] back into
+//! a `VectorFst`.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use rustfst::prelude::*;
+//! use petgraph::algo::dijkstra;
+//! use petgraph::visit::EdgeRef;
+//!
+//! # fn main() -> Result<()> {
+//! let mut fst = VectorFst::<TropicalWeight>::new();
+//! fst.add_states(3);
+//! fst.set_start(0)?;
+//! fst.emplace_tr(0, 1, 1, 1.0, 1)?;
+//! fst.emplace_tr(1, 2, 2, 1.0, 2)?;
+//! fst.emplace_tr(0, 3, 3, 5.0, 2)?;
+//! fst.set_final(2, 0.0)?;
+//!
+//! // Shortest paths counting the number of transitions instead of their weights.
+//! let distances = dijkstra(&fst, 0, None, |_tr| 1);
+//! assert_eq!(distances[&2], 1);
+//!
+//! // Shortest paths using the weights.
+//! let distances = dijkstra(&fst, 0, None, |tr| *tr.weight().weight.value());
+//! assert_eq!(distances[&2], 2.0);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::slice::Iter;
+
+use anyhow::Result;
+use fixedbitset::FixedBitSet;
+use petgraph::graph::{Graph, IndexType, NodeIndex};
+use petgraph::visit::{
+    Data, EdgeRef, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoNeighbors,
+    IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable, NodeCount, NodeIndexable,
+    Visitable,
+};
+use petgraph::Directed;
+
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::MutableFst;
+use crate::semirings::Semiring;
+use crate::{Error, Label, StateId, Tr, Trs};
+
+/// A transition of an FST seen as an edge of a graph.
+#[derive(Debug)]
+pub struct FstEdgeReference<'a, W> {
+    source: StateId,
+    index: usize,
+    tr: &'a Tr<W>,
+}
+
+impl<'a, W> Clone for FstEdgeReference<'a, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, W> Copy for FstEdgeReference<'a, W> {}
+
+impl<'a, W> EdgeRef for FstEdgeReference<'a, W> {
+    type NodeId = StateId;
+    type EdgeId = (StateId, usize);
+    type Weight = Tr<W>;
+
+    fn source(&self) -> StateId {
+        self.source
+    }
+
+    fn target(&self) -> StateId {
+        self.tr.nextstate
+    }
+
+    fn weight(&self) -> &Tr<W> {
+        self.tr
+    }
+
+    fn id(&self) -> (StateId, usize) {
+        (self.source, self.index)
+    }
+}
+
+/// Iterator over the states reached by the transitions leaving a state.
+#[derive(Debug)]
+pub struct FstNeighbors<'a, W> {
+    trs: Iter<'a, Tr<W>>,
+}
+
+impl<'a, W> Iterator for FstNeighbors<'a, W> {
+    type Item = StateId;
+
+    fn next(&mut self) -> Option<StateId> {
+        self.trs.next().map(|tr| tr.nextstate)
+    }
+}
+
+/// Iterator over the transitions leaving a state, as edges.
+#[derive(Debug)]
+pub struct FstEdges<'a, W> {
+    source: StateId,
+    trs: Enumerate<Iter<'a, Tr<W>>>,
+}
+
+impl<'a, W> Iterator for FstEdges<'a, W> {
+    type Item = FstEdgeReference<'a, W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let source = self.source;
+        self.trs
+            .next()
+            .map(|(index, tr)| FstEdgeReference { source, index, tr })
+    }
+}
+
+/// Iterator over all the transitions of an FST, as edges.
+#[derive(Debug)]
+pub struct FstEdgeReferences<'a, W, F> {
+    fst: &'a F,
+    edges: FstEdges<'a, W>,
+}
+
+/// Iterator over all the states of an FST along with their final weight.
+#[derive(Debug)]
+pub struct FstNodeReferences<'a, W, F> {
+    fst: &'a F,
+    state: StateId,
+    w: PhantomData<W>,
+}
+
+impl<W: Semiring> VectorFst<W> {
+    fn trs_slice(&self, state: StateId) -> &[Tr<W>] {
+        self.states.get(state as usize).map_or(&[], |s| s.trs.trs())
+    }
+}
+
+impl<W: Semiring> ConstFst<W> {
+    fn trs_slice(&self, state: StateId) -> &[Tr<W>] {
+        self.states
+            .get(state as usize)
+            .map_or(&[], |s| &self.trs[s.pos..s.pos + s.ntrs])
+    }
+}
+
+macro_rules! impl_petgraph_traits {
+    ($fst: ident) => {
+        impl<W: Semiring> GraphBase for $fst<W> {
+            type EdgeId = (StateId, usize);
+            type NodeId = StateId;
+        }
+
+        impl<W: Semiring> Data for $fst<W> {
+            type NodeWeight = Option<W>;
+            type EdgeWeight = Tr<W>;
+        }
+
+        impl<W: Semiring> GraphProp for $fst<W> {
+            type EdgeType = Directed;
+        }
+
+        impl<W: Semiring> NodeCount for $fst<W> {
+            fn node_count(&self) -> usize {
+                self.states.len()
+            }
+        }
+
+        impl<W: Semiring> NodeIndexable for $fst<W> {
+            fn node_bound(&self) -> usize {
+                self.states.len()
+            }
+
+            fn to_index(&self, a: StateId) -> usize {
+                a as usize
+            }
+
+            fn from_index(&self, i: usize) -> StateId {
+                i as StateId
+            }
+        }
+
+        impl<W: Semiring> NodeCompactIndexable for $fst<W> {}
+
+        impl<W: Semiring> Visitable for $fst<W> {
+            type Map = FixedBitSet;
+
+            fn visit_map(&self) -> FixedBitSet {
+                FixedBitSet::with_capacity(self.states.len())
+            }
+
+            fn reset_map(&self, map: &mut FixedBitSet) {
+                map.clear();
+                map.grow(self.states.len());
+            }
+        }
+
+        impl<'a, W: Semiring> IntoNeighbors for &'a $fst<W> {
+            type Neighbors = FstNeighbors<'a, W>;
+
+            fn neighbors(self, a: StateId) -> Self::Neighbors {
+                FstNeighbors {
+                    trs: self.trs_slice(a).iter(),
+                }
+            }
+        }
+
+        impl<'a, W: Semiring> IntoEdges for &'a $fst<W> {
+            type Edges = FstEdges<'a, W>;
+
+            fn edges(self, a: StateId) -> Self::Edges {
+                FstEdges {
+                    source: a,
+                    trs: self.trs_slice(a).iter().enumerate(),
+                }
+            }
+        }
+
+        impl<'a, W: Semiring> IntoEdgeReferences for &'a $fst<W> {
+            type EdgeRef = FstEdgeReference<'a, W>;
+            type EdgeReferences = FstEdgeReferences<'a, W, $fst<W>>;
+
+            fn edge_references(self) -> Self::EdgeReferences {
+                FstEdgeReferences {
+                    fst: self,
+                    edges: self.edges(0),
+                }
+            }
+        }
+
+        impl<'a, W: Semiring> Iterator for FstEdgeReferences<'a, W, $fst<W>> {
+            type Item = FstEdgeReference<'a, W>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                loop {
+                    if let Some(edge) = self.edges.next() {
+                        return Some(edge);
+                    }
+                    let state = self.edges.source + 1;
+                    if state as usize >= self.fst.states.len() {
+                        return None;
+                    }
+                    self.edges = self.fst.edges(state);
+                }
+            }
+        }
+
+        impl<'a, W: Semiring> IntoNodeIdentifiers for &'a $fst<W> {
+            type NodeIdentifiers = Range<StateId>;
+
+            fn node_identifiers(self) -> Range<StateId> {
+                0..self.states.len() as StateId
+            }
+        }
+
+        impl<'a, W: Semiring> IntoNodeReferences for &'a $fst<W> {
+            type NodeRef = (StateId, &'a Option<W>);
+            type NodeReferences = FstNodeReferences<'a, W, $fst<W>>;
+
+            fn node_references(self) -> Self::NodeReferences {
+                FstNodeReferences {
+                    fst: self,
+                    state: 0,
+                    w: PhantomData,
+                }
+            }
+        }
+
+        impl<'a, W: Semiring> Iterator for FstNodeReferences<'a, W, $fst<W>> {
+            type Item = (StateId, &'a Option<W>);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let state = self.state;
+                let fst_state = self.fst.states.get(state as usize)?;
+                self.state += 1;
+                Some((state, &fst_state.final_weight))
+            }
+        }
+    };
+}
+
+impl_petgraph_traits!(VectorFst);
+impl_petgraph_traits!(ConstFst);
+
+/// Edge weight of a [`Graph`] that can be turned into a transition by [`VectorFst::from_petgraph`].
+pub trait TrLike<W> {
+    /// Builds the transition to `nextstate` described by this edge weight.
+    fn to_tr(&self, nextstate: StateId) -> Tr<W>;
+}
+
+/// The `nextstate` of the transition is replaced by the target of the edge.
+impl<W: Semiring> TrLike<W> for Tr<W> {
+    fn to_tr(&self, nextstate: StateId) -> Tr<W> {
+        Tr::new(self.ilabel, self.olabel, self.weight.clone(), nextstate)
+    }
+}
+
+/// Input label, output label and weight of the transition.
+impl<W: Semiring> TrLike<W> for (Label, Label, W) {
+    fn to_tr(&self, nextstate: StateId) -> Tr<W> {
+        Tr::new(self.0, self.1, self.2.clone(), nextstate)
+    }
+}
+
+impl<W: Semiring> VectorFst<W> {
+    /// Builds an FST from a directed `petgraph` graph. Each node becomes the state with the same
+    /// index, final if its weight converts to `Some` final weight, and each edge becomes a
+    /// transition. `start` is the start state, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use rustfst::prelude::*;
+    /// use petgraph::Graph;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut graph = Graph::new();
+    /// let s0 = graph.add_node(None);
+    /// let s1 = graph.add_node(Some(TropicalWeight::new(0.5)));
+    /// graph.add_edge(s0, s1, (1, 2, TropicalWeight::new(1.0)));
+    ///
+    /// let fst = VectorFst::from_petgraph(&graph, Some(s0))?;
+    /// assert_eq!(fst.start(), Some(0));
+    /// assert_eq!(fst.get_trs(0)?.trs(), &[Tr::new(1, 2, 1.0, 1)]);
+    /// assert_eq!(fst.final_weight(1)?, Some(TropicalWeight::new(0.5)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_petgraph<N, E, Ix>(
+        graph: &Graph<N, E, Directed, Ix>,
+        start: Option<NodeIndex<Ix>>,
+    ) -> Result<Self>
+    where
+        N: Clone + Into<Option<W>>,
+        E: TrLike<W>,
+        Ix: IndexType,
+    {
+        let mut fst = VectorFst::new();
+        fst.add_states(graph.node_count());
+        if let Some(start) = start {
+            if start.index() >= graph.node_count() {
+                return Err(Error::InvalidStateId(start.index() as StateId).into());
+            }
+            fst.set_start(start.index() as StateId)?;
+        }
+        for node in graph.node_indices() {
+            if let Some(final_weight) = graph[node].clone().into() {
+                fst.set_final(node.index() as StateId, final_weight)?;
+            }
+        }
+        for edge in graph.edge_references() {
+            let tr = edge.weight().to_tr(edge.target().index() as StateId);
+            fst.add_tr(edge.source().index() as StateId, tr)?;
+        }
+        fst.compute_and_update_properties_all()?;
+        Ok(fst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+    use petgraph::algo::{dijkstra, tarjan_scc};
+    use petgraph::visit::Dfs;
+
+    use super::*;
+    use crate::algorithms::visitors::scc_decomposition;
+    use crate::fst_traits::{CoreFst, ExpandedFst, StateIterator};
+    use crate::semirings::TropicalWeight;
+
+    fn to_graph<'a, F>(fst: &'a F) -> Graph<Option<TropicalWeight>, Tr<TropicalWeight>>
+    where
+        &'a F: IntoNodeReferences<NodeRef = (StateId, &'a Option<TropicalWeight>)>
+            + IntoEdgeReferences<NodeId = StateId, EdgeWeight = Tr<TropicalWeight>>,
+    {
+        let mut graph = Graph::new();
+        for (_, final_weight) in fst.node_references() {
+            graph.add_node(*final_weight);
+        }
+        for edge in fst.edge_references() {
+            graph.add_edge(
+                NodeIndex::new(edge.source() as usize),
+                NodeIndex::new(edge.target() as usize),
+                edge.weight().clone(),
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_from_petgraph_invalid_start() -> Result<()> {
+        let mut graph = Graph::<Option<TropicalWeight>, Tr<TropicalWeight>>::new();
+        graph.add_node(None);
+        let err = VectorFst::from_petgraph(&graph, Some(NodeIndex::new(1))).unwrap_err();
+        assert!(matches!(Error::find(&err), Some(Error::InvalidStateId(1))));
+        let fst: VectorFst<TropicalWeight> = VectorFst::from_petgraph(&graph, None)?;
+        assert_eq!(fst.num_states(), 1);
+        assert_eq!(fst.start(), None);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_petgraph_round_trip_proptest(fst in any::<VectorFst::<TropicalWeight>>()) {
+            let start = fst.start().map(|s| NodeIndex::new(s as usize));
+            let from_vector = VectorFst::from_petgraph(&to_graph(&fst), start).unwrap();
+            prop_assert_eq!(&from_vector, &fst);
+
+            let const_fst: ConstFst<_> = fst.clone().into();
+            let from_const = VectorFst::from_petgraph(&to_graph(&const_fst), start).unwrap();
+            prop_assert_eq!(&from_const, &fst);
+        }
+
+        #[test]
+        fn test_petgraph_algorithms_proptest(fst in any::<VectorFst::<TropicalWeight>>()) {
+            let const_fst: ConstFst<_> = fst.clone().into();
+            let sccs = scc_decomposition(&fst);
+            prop_assert_eq!(tarjan_scc(&fst).len(), sccs.num_sccs());
+            prop_assert_eq!(tarjan_scc(&const_fst).len(), sccs.num_sccs());
+
+            if let Some(start) = fst.start() {
+                let mut dfs = Dfs::new(&const_fst, start);
+                let mut num_accessible = 0;
+                while let Some(s) = dfs.next(&const_fst) {
+                    prop_assert!(sccs.is_accessible(s));
+                    num_accessible += 1;
+                }
+                let accessible = fst.states_iter().filter(|s| sccs.is_accessible(*s)).count();
+                prop_assert_eq!(num_accessible, accessible);
+
+                let distances = dijkstra(&fst, start, None, |_| 1);
+                prop_assert_eq!(distances.len(), accessible);
+                for edge in fst.edge_references() {
+                    if let Some(d) = distances.get(&edge.source()) {
+                        prop_assert!(distances[&edge.target()] <= d + 1);
+                    }
+                }
+            }
+        }
+    }
+}
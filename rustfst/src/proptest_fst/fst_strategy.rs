@@ -0,0 +1,335 @@
+use std::fmt::Debug;
+
+use ::proptest::collection::vec;
+use ::proptest::prelude::{any, BoxedStrategy, Strategy};
+use ::proptest::sample::Index;
+
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::MutableFst;
+use crate::proptest_fst::{MAX_ILABEL, MAX_WEIGHT_VALUE};
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{Label, StateId, EPS_LABEL};
+
+/// Shape of the FSTs generated by [`fst_strategy`].
+///
+/// States are numbered from `0`, which is always the start state. Each state gets up to
+/// `max_num_trs_per_state` transitions whose labels are drawn from `labels`, or are epsilons with
+/// probability `epsilon_density`. Densities outside of `[0, 1]` are clamped to that range, a NaN
+/// density is treated as `0`.
+#[derive(Debug, Clone)]
+pub struct FstStrategyConfig {
+    /// Maximum number of states, at least one state is always generated.
+    pub max_num_states: usize,
+    /// Maximum number of transitions leaving a state.
+    pub max_num_trs_per_state: usize,
+    /// Alphabet of the non-epsilon labels. If empty, all the labels are epsilons.
+    pub labels: Vec<Label>,
+    /// Probability, between 0 and 1, that a label is an epsilon.
+    pub epsilon_density: f64,
+    /// Probability, between 0 and 1, that a state is final.
+    pub final_density: f64,
+    /// If true, transitions always go to a state with a higher id so the FST has no cycle.
+    /// Otherwise transitions can go to any state and cycles may appear.
+    pub acyclic: bool,
+    /// If true, the input and output labels of each transition are equal.
+    pub acceptor: bool,
+}
+
+impl Default for FstStrategyConfig {
+    fn default() -> Self {
+        Self {
+            max_num_states: 10,
+            max_num_trs_per_state: 3,
+            labels: (1..MAX_ILABEL).collect(),
+            epsilon_density: 0.1,
+            final_density: 0.3,
+            acyclic: false,
+            acceptor: false,
+        }
+    }
+}
+
+impl FstStrategyConfig {
+    /// Creates the default configuration : up to 10 states with up to 3 transitions each,
+    /// labels in `[1, MAX_ILABEL)`, 10% of epsilons, 30% of final states and cycles allowed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of states.
+    pub fn with_max_num_states(self, max_num_states: usize) -> Self {
+        Self {
+            max_num_states,
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of transitions leaving a state.
+    pub fn with_max_num_trs_per_state(self, max_num_trs_per_state: usize) -> Self {
+        Self {
+            max_num_trs_per_state,
+            ..self
+        }
+    }
+
+    /// Sets the alphabet of the non-epsilon labels.
+    pub fn with_labels(self, labels: impl IntoIterator<Item = Label>) -> Self {
+        Self {
+            labels: labels.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Sets the probability that a label is an epsilon. It is clamped to `[0, 1]`.
+    pub fn with_epsilon_density(self, epsilon_density: f64) -> Self {
+        Self {
+            epsilon_density,
+            ..self
+        }
+    }
+
+    /// Sets the probability that a state is final. It is clamped to `[0, 1]`.
+    pub fn with_final_density(self, final_density: f64) -> Self {
+        Self {
+            final_density,
+            ..self
+        }
+    }
+
+    /// Sets whether the generated FSTs must be acyclic.
+    pub fn with_acyclic(self, acyclic: bool) -> Self {
+        Self { acyclic, ..self }
+    }
+
+    /// Sets whether the generated FSTs must be acceptors.
+    pub fn with_acceptor(self, acceptor: bool) -> Self {
+        Self { acceptor, ..self }
+    }
+
+    /// Strategy generating FSTs with this shape over the tropical semiring, with the same weights
+    /// as the `Arbitrary` implementation of `TropicalWeight`.
+    pub fn tropical_fst_strategy(self) -> BoxedStrategy<VectorFst<TropicalWeight>> {
+        fst_strategy(self, small_float_weight(MAX_WEIGHT_VALUE))
+    }
+}
+
+/// Strategy generating weights of a float semiring (e.g `TropicalWeight`, `LogWeight` or
+/// `ProbabilityWeight`) with integral values in `[0, max_value)`. Integral values keep the sums of
+/// weights exact, which avoids spurious failures when comparing the weights of two FSTs.
+pub fn small_float_weight<W>(max_value: usize) -> impl Strategy<Value = W> + Clone
+where
+    W: From<f32> + Debug,
+{
+    (0..max_value.max(1)).prop_map(|v| W::from(v as f32))
+}
+
+/// Strategy generating random [`VectorFst`]s with the shape described by `config` and weights,
+/// both for the transitions and the final states, generated by `weights`. Any semiring can be
+/// used by providing a strategy generating its weights.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "proptest")]
+/// # fn main() {
+/// use proptest::prelude::*;
+/// use rustfst::fst_properties::FstProperties;
+/// use rustfst::prelude::*;
+/// use rustfst::proptest_fst::{fst_strategy, small_float_weight, FstStrategyConfig};
+///
+/// fn acyclic_acceptor() -> impl Strategy<Value = VectorFst<LogWeight>> {
+///     let config = FstStrategyConfig::new()
+///         .with_acyclic(true)
+///         .with_acceptor(true)
+///         .with_labels(vec![1, 2, 3]);
+///     fst_strategy(config, small_float_weight(5))
+/// }
+///
+/// proptest! {
+///     fn test_acyclic_acceptor(fst in acyclic_acceptor()) {
+///         let props = fst.properties();
+///         prop_assert!(props.contains(FstProperties::ACYCLIC | FstProperties::ACCEPTOR));
+///     }
+/// }
+/// # test_acyclic_acceptor();
+/// # }
+/// # #[cfg(not(feature = "proptest"))]
+/// # fn main() {}
+/// ```
+pub fn fst_strategy<W, S>(config: FstStrategyConfig, weights: S) -> BoxedStrategy<VectorFst<W>>
+where
+    W: Semiring + 'static,
+    S: Strategy<Value = W> + Clone + 'static,
+{
+    let label = label_strategy(&config);
+    let tr = (label.clone(), label, weights.clone(), any::<Index>());
+    let state = (
+        ::proptest::bool::weighted(probability(config.final_density)),
+        weights,
+        vec(tr, 0..=config.max_num_trs_per_state),
+    );
+    vec(state, 1..=config.max_num_states.max(1))
+        .prop_map(move |states| {
+            let num_states = states.len();
+            let mut fst = VectorFst::new();
+            fst.add_states(num_states);
+            fst.set_start(0).unwrap();
+            for (state, (is_final, final_weight, trs)) in states.into_iter().enumerate() {
+                if is_final {
+                    fst.set_final(state as StateId, final_weight).unwrap();
+                }
+                for (ilabel, olabel, weight, nextstate) in trs {
+                    let nextstate = if config.acyclic {
+                        // The last state can't have any transition.
+                        if state + 1 == num_states {
+                            break;
+                        }
+                        state + 1 + nextstate.index(num_states - state - 1)
+                    } else {
+                        nextstate.index(num_states)
+                    };
+                    let olabel = if config.acceptor { ilabel } else { olabel };
+                    fst.emplace_tr(
+                        state as StateId,
+                        ilabel,
+                        olabel,
+                        weight,
+                        nextstate as StateId,
+                    )
+                    .unwrap();
+                }
+            }
+            fst.compute_and_update_properties_all().unwrap();
+            fst
+        })
+        .boxed()
+}
+
+// `proptest::bool::weighted` panics on probabilities outside of [0, 1].
+fn probability(density: f64) -> f64 {
+    if density.is_nan() {
+        0.0
+    } else {
+        density.clamp(0.0, 1.0)
+    }
+}
+
+fn label_strategy(config: &FstStrategyConfig) -> impl Strategy<Value = Label> + Clone {
+    let labels = config.labels.clone();
+    (
+        ::proptest::bool::weighted(probability(config.epsilon_density)),
+        any::<Index>(),
+    )
+        .prop_map(move |(epsilon, idx)| {
+            if epsilon || labels.is_empty() {
+                EPS_LABEL
+            } else {
+                labels[idx.index(labels.len())]
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+
+    use super::*;
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{CoreFst, ExpandedFst, StateIterator};
+    use crate::semirings::{BooleanWeight, LogWeight};
+    use crate::Trs;
+
+    fn boolean_weight() -> impl Strategy<Value = BooleanWeight> + Clone {
+        any::<bool>().prop_map(BooleanWeight::new)
+    }
+
+    proptest! {
+        #[test]
+        fn test_fst_strategy_default(
+            fst in FstStrategyConfig::new().tropical_fst_strategy()
+        ) {
+            prop_assert!(fst.num_states() > 0);
+            prop_assert!(fst.num_states() <= 10);
+            prop_assert_eq!(fst.start(), Some(0));
+            for s in fst.states_iter() {
+                prop_assert!(fst.num_trs(s).unwrap() <= 3);
+            }
+        }
+
+        #[test]
+        fn test_fst_strategy_acyclic(
+            fst in fst_strategy(
+                FstStrategyConfig::new().with_acyclic(true).with_max_num_trs_per_state(6),
+                small_float_weight::<LogWeight>(MAX_WEIGHT_VALUE),
+            )
+        ) {
+            prop_assert!(fst.properties().contains(FstProperties::ACYCLIC));
+        }
+
+        #[test]
+        fn test_fst_strategy_acceptor(
+            fst in fst_strategy(FstStrategyConfig::new().with_acceptor(true), boolean_weight())
+        ) {
+            prop_assert!(fst.properties().contains(FstProperties::ACCEPTOR));
+        }
+
+        #[test]
+        fn test_fst_strategy_labels(
+            fst in FstStrategyConfig::new()
+                .with_labels(vec![3, 7])
+                .with_epsilon_density(0.0)
+                .tropical_fst_strategy()
+        ) {
+            for s in fst.states_iter() {
+                for tr in fst.get_trs(s).unwrap().trs() {
+                    prop_assert!(tr.ilabel == 3 || tr.ilabel == 7);
+                    prop_assert!(tr.olabel == 3 || tr.olabel == 7);
+                }
+            }
+        }
+
+        #[test]
+        fn test_fst_strategy_only_epsilons(
+            fst in FstStrategyConfig::new().with_epsilon_density(1.0).tropical_fst_strategy()
+        ) {
+            for s in fst.states_iter() {
+                for tr in fst.get_trs(s).unwrap().trs() {
+                    prop_assert_eq!((tr.ilabel, tr.olabel), (EPS_LABEL, EPS_LABEL));
+                }
+            }
+        }
+
+        #[test]
+        fn test_fst_strategy_densities_clamped(
+            fst in FstStrategyConfig::new()
+                .with_epsilon_density(2.0)
+                .with_final_density(-1.0)
+                .tropical_fst_strategy()
+        ) {
+            for s in fst.states_iter() {
+                prop_assert!(!fst.is_final(s).unwrap());
+                for tr in fst.get_trs(s).unwrap().trs() {
+                    prop_assert_eq!((tr.ilabel, tr.olabel), (EPS_LABEL, EPS_LABEL));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fst_strategy_cyclic() {
+        // With self-loops allowed and many transitions per state, cycles must show up.
+        let config = FstStrategyConfig::new()
+            .with_max_num_states(2)
+            .with_max_num_trs_per_state(10);
+        let mut runner = proptest::test_runner::TestRunner::deterministic();
+        let strategy = fst_strategy(
+            config,
+            small_float_weight::<TropicalWeight>(MAX_WEIGHT_VALUE),
+        );
+        let num_cyclic = (0..100)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+            .filter(|fst| fst.properties().contains(FstProperties::CYCLIC))
+            .count();
+        assert!(num_cyclic > 0);
+    }
+}
@@ -1,9 +1,27 @@
+//! [`proptest`](https://docs.rs/proptest) strategies generating random FSTs and weights, to
+//! property-test algorithms and FST pipelines.
+//!
+//! - [`fst_strategy`] generates [`VectorFst`](crate::fst_impls::VectorFst)s over any semiring
+//!   whose shape is described by a [`FstStrategyConfig`] : acyclic or cyclic, acceptor or
+//!   transducer, density of epsilons and alphabet of the labels.
+//! - `VectorFst<TropicalWeight>`, [`SimpleTransducer`] and
+//!   [`TropicalWeight`](crate::semirings::TropicalWeight) implement `Arbitrary`, the FSTs being
+//!   built by applying random unions and concatenations to single transition transducers.
+//!
+//! Only available with the `proptest` feature.
+
 use crate::Label;
 
+mod fst_strategy;
 mod simple_transducer;
 mod tropical_weight;
 mod vector_fst;
 
+pub use self::fst_strategy::{fst_strategy, small_float_weight, FstStrategyConfig};
+pub use self::simple_transducer::{ProptestSimpleTransducerConfig, SimpleTransducer};
+pub use self::tropical_weight::ProptestTropicalWeightConfig;
+pub use self::vector_fst::{Operation, ProptestFstConfig};
+
 static MAX_NUM_OPERATIONS: usize = 30;
 static MAX_ILABEL: Label = 10;
 static MAX_OLABEL: Label = 10;
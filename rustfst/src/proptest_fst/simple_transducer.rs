@@ -5,13 +5,19 @@ use crate::{Label, Semiring};
 use proptest::arbitrary::any_with;
 use proptest::prelude::{Arbitrary, BoxedStrategy, Strategy};
 
+/// Transducer with a single transition, from the start state to a final state.
 #[derive(Debug, Clone)]
 pub struct SimpleTransducer(pub VectorFst<TropicalWeight>);
 
+/// Parameters of the `Arbitrary` implementation of [`SimpleTransducer`] : the labels and the
+/// weight of the transition are drawn below these values.
 #[derive(Debug, Clone)]
 pub struct ProptestSimpleTransducerConfig {
+    /// Input label of the transition.
     pub ilabel: Label,
+    /// Output label of the transition.
     pub olabel: Label,
+    /// Integral value of the weight of the transition.
     pub weight_value: usize,
 }
 
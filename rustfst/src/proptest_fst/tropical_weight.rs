@@ -5,8 +5,13 @@ use proptest::arbitrary::Arbitrary;
 use proptest::prelude::Strategy;
 use proptest::strategy::BoxedStrategy;
 
+/// Parameters of the `Arbitrary` implementation of `TropicalWeight` : the weights have integral
+/// values below this maximum.
 #[derive(Debug, Clone)]
-pub struct ProptestTropicalWeightConfig(usize);
+pub struct ProptestTropicalWeightConfig(
+    /// Exclusive maximum of the weight values.
+    pub usize,
+);
 
 impl Default for ProptestTropicalWeightConfig {
     fn default() -> Self {
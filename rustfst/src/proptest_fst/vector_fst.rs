@@ -8,17 +8,27 @@ use crate::proptest_fst::{MAX_ILABEL, MAX_NUM_OPERATIONS, MAX_OLABEL, MAX_WEIGHT
 use crate::utils::epsilon_machine;
 use crate::Label;
 
+/// Parameters of the `Arbitrary` implementation of `VectorFst<TropicalWeight>` : maximum number
+/// of operations, labels and weight values. The actual config of each generated FST is drawn
+/// below these maxima, which are exclusive.
 #[derive(Debug, Clone, Copy)]
 pub struct ProptestFstConfig {
+    /// Number of union or concatenation operations used to build the FST.
     pub n_operations: usize,
+    /// Bound on the input labels of the transitions.
     pub max_ilabel: Label,
+    /// Bound on the output labels of the transitions.
     pub max_olabel: Label,
+    /// Bound on the integral values of the weights.
     pub max_weight_value: usize,
 }
 
+/// Operation combining the generated FST with a [`SimpleTransducer`].
 #[derive(Debug, Clone)]
 pub enum Operation {
+    /// Union with the transducer.
     Union,
+    /// Concatenation with the transducer.
     Concat,
 }
 
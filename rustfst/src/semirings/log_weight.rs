@@ -1,5 +1,4 @@
 use std::borrow::Borrow;
-use std::f32;
 use std::hash::{Hash, Hasher};
 use std::io::Write;
 
@@ -21,158 +20,189 @@ use crate::semirings::{
 };
 use crate::KDELTA;
 
-/// Log semiring: (log(e^-x + e^-y), +, inf, 0).
-#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
-pub struct LogWeight {
-    value: OrderedFloat<f32>,
-}
-
-fn ln_pos_exp(x: f32) -> f32 {
-    ((-x).exp()).ln_1p()
-}
-
-impl Semiring for LogWeight {
-    type Type = f32;
-    type ReverseWeight = LogWeight;
-
-    fn zero() -> Self {
-        Self {
-            value: OrderedFloat(f32::INFINITY),
+/// Implements a log semiring over a float type. Shared by `LogWeight` and `LogWeight64`.
+macro_rules! log_weight {
+    (
+        $(#[$attr:meta])*
+        $semiring:ident,
+        $float:ident,
+        $weight_type:expr,
+        $parse_bin:ident,
+        $write_bin:ident,
+        $parse_float:ident,
+        $approx_equal:ident,
+        $quantize:ident,
+        $partial_eq_and_hash:ident
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+        pub struct $semiring {
+            value: OrderedFloat<$float>,
         }
-    }
-    fn one() -> Self {
-        Self {
-            value: OrderedFloat(0.0),
+
+        impl $semiring {
+            fn ln_pos_exp(x: $float) -> $float {
+                ((-x).exp()).ln_1p()
+            }
         }
-    }
 
-    fn new(value: <Self as Semiring>::Type) -> Self {
-        LogWeight {
-            value: OrderedFloat(value),
+        impl Semiring for $semiring {
+            type Type = $float;
+            type ReverseWeight = $semiring;
+
+            fn zero() -> Self {
+                Self {
+                    value: OrderedFloat($float::INFINITY),
+                }
+            }
+            fn one() -> Self {
+                Self {
+                    value: OrderedFloat(0.0),
+                }
+            }
+
+            fn new(value: <Self as Semiring>::Type) -> Self {
+                $semiring {
+                    value: OrderedFloat(value),
+                }
+            }
+
+            fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+                let f1 = self.value();
+                let f2 = rhs.borrow().value();
+                self.value.0 = if f1.eq(&$float::INFINITY) {
+                    *f2
+                } else if f2.eq(&$float::INFINITY) {
+                    *f1
+                } else if f1 > f2 {
+                    f2 - Self::ln_pos_exp(f1 - f2)
+                } else {
+                    f1 - Self::ln_pos_exp(f2 - f1)
+                };
+                Ok(())
+            }
+
+            fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+                let f1 = self.value();
+                let f2 = rhs.borrow().value();
+                if f1.eq(&$float::INFINITY) {
+                } else if f2.eq(&$float::INFINITY) {
+                    self.value.0 = *f2;
+                } else {
+                    self.value.0 += f2;
+                }
+                Ok(())
+            }
+
+            fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+                $approx_equal(self.value.0, rhs.borrow().value.0, delta)
+            }
+
+            fn value(&self) -> &Self::Type {
+                self.value.as_ref()
+            }
+
+            fn take_value(self) -> Self::Type {
+                self.value.into_inner()
+            }
+
+            fn set_value(&mut self, value: <Self as Semiring>::Type) {
+                self.value.0 = value
+            }
+
+            fn reverse(&self) -> Result<Self::ReverseWeight> {
+                Ok(*self)
+            }
+
+            fn properties() -> SemiringProperties {
+                SemiringProperties::LEFT_SEMIRING
+                    | SemiringProperties::RIGHT_SEMIRING
+                    | SemiringProperties::COMMUTATIVE
+            }
         }
-    }
-
-    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
-        let f1 = self.value();
-        let f2 = rhs.borrow().value();
-        self.value.0 = if f1.eq(&f32::INFINITY) {
-            *f2
-        } else if f2.eq(&f32::INFINITY) {
-            *f1
-        } else if f1 > f2 {
-            f2 - ln_pos_exp(f1 - f2)
-        } else {
-            f1 - ln_pos_exp(f2 - f1)
-        };
-        Ok(())
-    }
-
-    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
-        let f1 = self.value();
-        let f2 = rhs.borrow().value();
-        if f1.eq(&f32::INFINITY) {
-        } else if f2.eq(&f32::INFINITY) {
-            self.value.0 = *f2;
-        } else {
-            self.value.0 += f2;
+
+        impl ReverseBack<$semiring> for $semiring {
+            fn reverse_back(&self) -> Result<$semiring> {
+                Ok(*self)
+            }
         }
-        Ok(())
-    }
-
-    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
-        float_approx_equal(self.value.0, rhs.borrow().value.0, delta)
-    }
-
-    fn value(&self) -> &Self::Type {
-        self.value.as_ref()
-    }
-
-    fn take_value(self) -> Self::Type {
-        self.value.into_inner()
-    }
-
-    fn set_value(&mut self, value: <Self as Semiring>::Type) {
-        self.value.0 = value
-    }
-
-    fn reverse(&self) -> Result<Self::ReverseWeight> {
-        Ok(*self)
-    }
-
-    fn properties() -> SemiringProperties {
-        SemiringProperties::LEFT_SEMIRING
-            | SemiringProperties::RIGHT_SEMIRING
-            | SemiringProperties::COMMUTATIVE
-    }
-}
 
-impl ReverseBack<LogWeight> for LogWeight {
-    fn reverse_back(&self) -> Result<LogWeight> {
-        Ok(*self)
-    }
-}
+        impl AsRef<$semiring> for $semiring {
+            fn as_ref(&self) -> &$semiring {
+                self
+            }
+        }
 
-impl AsRef<LogWeight> for LogWeight {
-    fn as_ref(&self) -> &LogWeight {
-        self
-    }
-}
+        display_semiring!($semiring);
 
-display_semiring!(LogWeight);
+        impl CompleteSemiring for $semiring {}
 
-impl CompleteSemiring for LogWeight {}
+        impl StarSemiring for $semiring {
+            fn closure(&self) -> Self {
+                if self.value.0 >= 0.0 && self.value.0 < 1.0 {
+                    Self::new((1.0 - self.value.0).ln())
+                } else {
+                    Self::new($float::NEG_INFINITY)
+                }
+            }
+        }
 
-impl StarSemiring for LogWeight {
-    fn closure(&self) -> Self {
-        if self.value.0 >= 0.0 && self.value.0 < 1.0 {
-            Self::new((1.0 - self.value.0).ln())
-        } else {
-            Self::new(f32::NEG_INFINITY)
+        impl WeaklyDivisibleSemiring for $semiring {
+            fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+                self.value.0 -= rhs.value.0;
+                Ok(())
+            }
         }
-    }
-}
 
-impl WeaklyDivisibleSemiring for LogWeight {
-    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
-        self.value.0 -= rhs.value.0;
-        Ok(())
-    }
-}
+        $quantize!($semiring);
 
-impl_quantize_f32!(LogWeight);
+        $partial_eq_and_hash!($semiring);
 
-partial_eq_and_hash_f32!(LogWeight);
+        impl SerializableSemiring for $semiring {
+            fn weight_type() -> String {
+                $weight_type.to_string()
+            }
 
-impl SerializableSemiring for LogWeight {
-    fn weight_type() -> String {
-        "log".to_string()
-    }
+            fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+                let (i, weight) = $parse_bin(i)?;
+                Ok((i, Self::new(weight)))
+            }
 
-    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
-        let (i, weight) = parse_bin_f32(i)?;
-        Ok((i, Self::new(weight)))
-    }
+            fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+                $write_bin(file, *self.value())
+            }
 
-    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
-        write_bin_f32(file, *self.value())
-    }
+            fn parse_text(i: &str) -> IResult<&str, Self> {
+                // FIXME: nom 7 does not fully parse "infinity", therefore it is done manually here until
+                // the PR https://github.com/rust-bakery/nom/pull/1673 is merged.
+                let (i, f) = alt((map(tag_no_case("infinity"), |_| $float::INFINITY), $parse_float))(i)?;
+                Ok((i, Self::new(f)))
+            }
+        }
 
-    fn parse_text(i: &str) -> IResult<&str, Self> {
-        // FIXME: nom 7 does not fully parse "infinity", therefore it is done manually here until
-        // the PR https://github.com/rust-bakery/nom/pull/1673 is merged.
-        let (i, f) = alt((map(tag_no_case("infinity"), |_| f32::INFINITY), float))(i)?;
-        Ok((i, Self::new(f)))
-    }
+        impl From<$float> for $semiring {
+            fn from(f: $float) -> Self {
+                $semiring::new(f)
+            }
+        }
+    };
 }
 
+log_weight!(
+    /// Log semiring: (log(e^-x + e^-y), +, inf, 0).
+    LogWeight,
+    f32,
+    "log",
+    parse_bin_f32,
+    write_bin_f32,
+    float,
+    float_approx_equal,
+    impl_quantize_f32,
+    partial_eq_and_hash_f32
+);
+
 test_semiring_serializable!(
     tests_log_weight_serializable,
     LogWeight,
     LogWeight::new(0.3) LogWeight::new(0.5) LogWeight::new(0.0) LogWeight::new(-1.2)
 );
-
-impl From<f32> for LogWeight {
-    fn from(f: f32) -> Self {
-        LogWeight::new(f)
-    }
-}
@@ -0,0 +1,62 @@
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::combinator::map;
+use nom::number::complete::double;
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::parse_bin_f64;
+use crate::parsers::write_bin_f64;
+use crate::semirings::utils_float::float64_approx_equal;
+use crate::semirings::{
+    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+log_weight!(
+    /// Log semiring with double precision: (log(e^-x + e^-y), +, inf, 0).
+    LogWeight64,
+    f64,
+    "log64",
+    parse_bin_f64,
+    write_bin_f64,
+    double,
+    float64_approx_equal,
+    impl_quantize_f64,
+    partial_eq_and_hash_f64
+);
+
+test_semiring_serializable!(
+    tests_log_weight64_serializable,
+    LogWeight64,
+    LogWeight64::new(0.3) LogWeight64::new(0.5) LogWeight64::new(0.0) LogWeight64::new(-1.2)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semirings::LogWeight;
+
+    #[test]
+    fn test_log_weight64_precision() -> Result<()> {
+        // Summing many small probabilities loses precision with f32.
+        let p = LogWeight64::new(-(1e-7_f64).ln());
+        let mut sum = LogWeight64::zero();
+        let mut sum32 = LogWeight::zero();
+        for _ in 0..100_000 {
+            sum.plus_assign(p)?;
+            sum32.plus_assign(LogWeight::new(*p.value() as f32))?;
+        }
+        let expected = -(1e-2_f64).ln();
+        assert!((sum.value() - expected).abs() < 1e-9);
+        assert!((*sum32.value() as f64 - expected).abs() > 1e-6);
+        Ok(())
+    }
+}
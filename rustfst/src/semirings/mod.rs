@@ -7,14 +7,20 @@ mod macros;
 mod boolean_weight;
 mod gallic_weight;
 mod integer_weight;
+#[macro_use]
 mod log_weight;
+mod log_weight64;
+mod min_max_weight;
 mod power_weight;
 mod probability_weight;
 mod product_weight;
+mod real_weight;
 mod string_variant;
 mod string_weight;
 mod trivial_weight;
+#[macro_use]
 mod tropical_weight;
+mod tropical_weight64;
 mod union_weight;
 pub(crate) mod utils_float;
 
@@ -24,8 +30,11 @@ pub use self::gallic_weight::{
 };
 pub use self::integer_weight::IntegerWeight;
 pub use self::log_weight::LogWeight;
+pub use self::log_weight64::LogWeight64;
+pub use self::min_max_weight::MinMaxWeight;
 pub use self::probability_weight::ProbabilityWeight;
 pub use self::product_weight::ProductWeight;
+pub use self::real_weight::RealWeight;
 pub use self::semiring::{
     CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
     StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
@@ -36,4 +45,5 @@ pub use self::string_weight::{
 };
 pub use self::trivial_weight::TrivialWeight;
 pub use self::tropical_weight::TropicalWeight;
+pub use self::tropical_weight64::TropicalWeight64;
 pub use self::union_weight::{UnionWeight, UnionWeightOption};
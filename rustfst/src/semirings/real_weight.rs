@@ -0,0 +1,178 @@
+use std::borrow::Borrow;
+use std::f32;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::number::complete::float;
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::parse_bin_f32;
+use crate::parsers::write_bin_f32;
+use crate::semirings::utils_float::float_approx_equal;
+use crate::semirings::{
+    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+/// Real semiring: (+, x, 0.0, 1.0).
+///
+/// Unlike `ProbabilityWeight`, weights can be negative, which is useful to compute
+/// signed sums such as counts or expectations.
+#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+pub struct RealWeight {
+    value: OrderedFloat<f32>,
+}
+
+impl Semiring for RealWeight {
+    type Type = f32;
+    type ReverseWeight = RealWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: OrderedFloat(0.0),
+        }
+    }
+    fn one() -> Self {
+        Self {
+            value: OrderedFloat(1.0),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        RealWeight {
+            value: OrderedFloat(value),
+        }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.value.0 += rhs.borrow().value.0;
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.value.0 *= rhs.borrow().value.0;
+        Ok(())
+    }
+
+    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+        float_approx_equal(self.value.0, rhs.borrow().value.0, delta)
+    }
+
+    fn value(&self) -> &Self::Type {
+        self.value.as_ref()
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value.into_inner()
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value.0 = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+    }
+}
+
+impl ReverseBack<RealWeight> for RealWeight {
+    fn reverse_back(&self) -> Result<RealWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<RealWeight> for RealWeight {
+    fn as_ref(&self) -> &RealWeight {
+        self
+    }
+}
+
+display_semiring!(RealWeight);
+
+impl CompleteSemiring for RealWeight {}
+
+impl SerializableSemiring for RealWeight {
+    fn weight_type() -> String {
+        "real".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+        let (i, weight) = parse_bin_f32(i)?;
+        Ok((i, Self::new(weight)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_f32(file, *self.value())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, f) = float(i)?;
+        Ok((i, Self::new(f)))
+    }
+}
+
+impl StarSemiring for RealWeight {
+    fn closure(&self) -> Self {
+        Self::new(1.0 / (1.0 - self.value.0))
+    }
+}
+
+impl WeaklyDivisibleSemiring for RealWeight {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        if rhs.value.0 == 0.0 {
+            bail!("Division by 0")
+        }
+        self.value.0 /= rhs.value.0;
+        Ok(())
+    }
+}
+
+impl_quantize_f32!(RealWeight);
+
+partial_eq_and_hash_f32!(RealWeight);
+
+test_semiring_serializable!(
+    tests_real_weight_serializable,
+    RealWeight,
+    RealWeight::one() RealWeight::zero() RealWeight::new(0.3) RealWeight::new(0.5) RealWeight::new(0.0) RealWeight::new(1.0) RealWeight::new(-2.5)
+);
+
+impl From<f32> for RealWeight {
+    fn from(f: f32) -> Self {
+        Self::new(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_real_weight() -> Result<()> {
+        let w1 = RealWeight::new(2.0);
+        let w2 = RealWeight::new(-3.0);
+        assert_eq!(w1.plus(w2)?, RealWeight::new(-1.0));
+        assert_eq!(w1.times(w2)?, RealWeight::new(-6.0));
+        assert_eq!(w1.plus(RealWeight::zero())?, w1);
+        assert_eq!(w1.times(RealWeight::one())?, w1);
+        assert_eq!(
+            w2.divide(&w1, DivideType::DivideAny)?,
+            RealWeight::new(-1.5)
+        );
+        assert!(w1
+            .divide(&RealWeight::zero(), DivideType::DivideAny)
+            .is_err());
+        assert_eq!(RealWeight::new(0.5).closure(), RealWeight::new(2.0));
+        Ok(())
+    }
+}
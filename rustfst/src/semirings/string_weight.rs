@@ -8,7 +8,7 @@ use nom::bytes::complete::tag;
 use nom::multi::{count, separated_list0};
 use nom::IResult;
 
-use crate::parsers::nom_utils::{num, NomCustomError};
+use crate::parsers::nom_utils::{check_count, num, NomCustomError};
 use crate::parsers::parse_bin_i32;
 use crate::parsers::write_bin_i32;
 use crate::semirings::string_variant::StringWeightVariant;
@@ -16,7 +16,7 @@ use crate::semirings::{
     DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
     WeaklyDivisibleSemiring, WeightQuantize,
 };
-use crate::Label;
+use crate::{Error, Label};
 
 /// String semiring: (identity, ., Infinity, Epsilon)
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
@@ -93,11 +93,12 @@ macro_rules! string_semiring {
                     match $string_type {
                         StringType::StringRestrict => {
                             if self != rhs.borrow() {
-                                bail!(
+                                return Err(Error::NonFunctional(format!(
                                     "Unequal arguments : non-functional FST ? w1 = {:?} w2 = {:?}",
                                     &self,
                                     &rhs.borrow()
-                                );
+                                ))
+                                .into());
                             }
                         }
                         StringType::StringLeft => {
@@ -265,7 +266,8 @@ macro_rules! string_semiring {
 
             fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
                 let (i, n) = parse_bin_i32(i)?;
-                let (i, labels) = count(parse_bin_i32, n as usize)(i)?;
+                let n = check_count(i, n as i64, 4)?;
+                let (i, labels) = count(parse_bin_i32, n)(i)?;
                 // Check for infinity
                 let weight = if labels == vec![-1] {
                     Self::new(StringWeightVariant::Infinity)
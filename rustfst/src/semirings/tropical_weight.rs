@@ -1,5 +1,4 @@
 use std::borrow::Borrow;
-use std::f32;
 use std::hash::{Hash, Hasher};
 use std::io::Write;
 
@@ -22,150 +21,180 @@ use crate::semirings::{
 };
 use crate::KDELTA;
 
-/// Tropical semiring: (min, +, inf, 0).
-#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
-pub struct TropicalWeight {
-    value: OrderedFloat<f32>,
-}
-
-impl Semiring for TropicalWeight {
-    type Type = f32;
-    type ReverseWeight = TropicalWeight;
-
-    fn zero() -> Self {
-        Self {
-            value: OrderedFloat(f32::INFINITY),
+/// Implements a tropical semiring over a float type. Shared by `TropicalWeight` and
+/// `TropicalWeight64`.
+macro_rules! tropical_weight {
+    (
+        $(#[$attr:meta])*
+        $semiring:ident,
+        $float:ident,
+        $weight_type:expr,
+        $parse_bin:ident,
+        $write_bin:ident,
+        $parse_float:ident,
+        $approx_equal:ident,
+        $quantize:ident,
+        $partial_eq_and_hash:ident
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+        pub struct $semiring {
+            value: OrderedFloat<$float>,
         }
-    }
 
-    fn one() -> Self {
-        Self {
-            value: OrderedFloat(0.0),
+        impl Semiring for $semiring {
+            type Type = $float;
+            type ReverseWeight = $semiring;
+
+            fn zero() -> Self {
+                Self {
+                    value: OrderedFloat($float::INFINITY),
+                }
+            }
+
+            fn one() -> Self {
+                Self {
+                    value: OrderedFloat(0.0),
+                }
+            }
+
+            fn new(value: <Self as Semiring>::Type) -> Self {
+                $semiring {
+                    value: OrderedFloat(value),
+                }
+            }
+
+            fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+                if rhs.borrow().value < self.value {
+                    self.value = rhs.borrow().value;
+                }
+                Ok(())
+            }
+
+            fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+                let f1 = self.value();
+                let f2 = rhs.borrow().value();
+                if f1.eq(&$float::INFINITY) {
+                } else if f2.eq(&$float::INFINITY) {
+                    self.value.0 = *f2;
+                } else {
+                    self.value.0 += f2;
+                }
+                Ok(())
+            }
+
+            fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
+                $approx_equal(self.value.0, rhs.borrow().value.0, delta)
+            }
+
+            fn value(&self) -> &Self::Type {
+                &self.value.0
+            }
+
+            fn take_value(self) -> Self::Type {
+                self.value.0
+            }
+
+            fn set_value(&mut self, value: <Self as Semiring>::Type) {
+                self.value.0 = value
+            }
+
+            fn reverse(&self) -> Result<Self::ReverseWeight> {
+                Ok(*self)
+            }
+
+            fn properties() -> SemiringProperties {
+                SemiringProperties::LEFT_SEMIRING
+                    | SemiringProperties::RIGHT_SEMIRING
+                    | SemiringProperties::COMMUTATIVE
+                    | SemiringProperties::PATH
+                    | SemiringProperties::IDEMPOTENT
+            }
         }
-    }
 
-    fn new(value: <Self as Semiring>::Type) -> Self {
-        TropicalWeight {
-            value: OrderedFloat(value),
+        impl ReverseBack<$semiring> for $semiring {
+            fn reverse_back(&self) -> Result<$semiring> {
+                Ok(*self)
+            }
         }
-    }
 
-    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
-        if rhs.borrow().value < self.value {
-            self.value = rhs.borrow().value;
+        impl AsRef<$semiring> for $semiring {
+            fn as_ref(&self) -> &$semiring {
+                self
+            }
         }
-        Ok(())
-    }
-
-    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
-        let f1 = self.value();
-        let f2 = rhs.borrow().value();
-        if f1.eq(&f32::INFINITY) {
-        } else if f2.eq(&f32::INFINITY) {
-            self.value.0 = *f2;
-        } else {
-            self.value.0 += f2;
-        }
-        Ok(())
-    }
-
-    fn approx_equal<P: Borrow<Self>>(&self, rhs: P, delta: f32) -> bool {
-        float_approx_equal(self.value.0, rhs.borrow().value.0, delta)
-    }
-
-    fn value(&self) -> &Self::Type {
-        &self.value.0
-    }
-
-    fn take_value(self) -> Self::Type {
-        self.value.0
-    }
-
-    fn set_value(&mut self, value: <Self as Semiring>::Type) {
-        self.value.0 = value
-    }
-
-    fn reverse(&self) -> Result<Self::ReverseWeight> {
-        Ok(*self)
-    }
-
-    fn properties() -> SemiringProperties {
-        SemiringProperties::LEFT_SEMIRING
-            | SemiringProperties::RIGHT_SEMIRING
-            | SemiringProperties::COMMUTATIVE
-            | SemiringProperties::PATH
-            | SemiringProperties::IDEMPOTENT
-    }
-}
 
-impl ReverseBack<TropicalWeight> for TropicalWeight {
-    fn reverse_back(&self) -> Result<TropicalWeight> {
-        Ok(*self)
-    }
-}
-
-impl AsRef<TropicalWeight> for TropicalWeight {
-    fn as_ref(&self) -> &TropicalWeight {
-        self
-    }
-}
+        display_semiring!($semiring);
 
-display_semiring!(TropicalWeight);
+        impl CompleteSemiring for $semiring {}
 
-impl CompleteSemiring for TropicalWeight {}
+        impl StarSemiring for $semiring {
+            fn closure(&self) -> Self {
+                if self.value.is_sign_positive() && self.value.is_finite() {
+                    Self::new(0.0)
+                } else {
+                    Self::new($float::NEG_INFINITY)
+                }
+            }
+        }
 
-impl StarSemiring for TropicalWeight {
-    fn closure(&self) -> Self {
-        if self.value.is_sign_positive() && self.value.is_finite() {
-            Self::new(0.0)
-        } else {
-            Self::new(f32::NEG_INFINITY)
+        impl WeaklyDivisibleSemiring for $semiring {
+            fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+                self.value.0 -= rhs.value.0;
+                Ok(())
+            }
         }
-    }
-}
 
-impl WeaklyDivisibleSemiring for TropicalWeight {
-    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
-        self.value.0 -= rhs.value.0;
-        Ok(())
-    }
-}
+        $quantize!($semiring);
 
-impl_quantize_f32!(TropicalWeight);
+        $partial_eq_and_hash!($semiring);
 
-partial_eq_and_hash_f32!(TropicalWeight);
+        impl SerializableSemiring for $semiring {
+            fn weight_type() -> String {
+                $weight_type.to_string()
+            }
 
-impl SerializableSemiring for TropicalWeight {
-    fn weight_type() -> String {
-        "tropical".to_string()
-    }
+            fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
+                let (i, weight) = $parse_bin(i)?;
+                Ok((i, Self::new(weight)))
+            }
 
-    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
-        let (i, weight) = parse_bin_f32(i)?;
-        Ok((i, Self::new(weight)))
-    }
+            fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+                $write_bin(file, *self.value())
+            }
 
-    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
-        write_bin_f32(file, *self.value())
-    }
+            fn parse_text(i: &str) -> IResult<&str, Self> {
+                // FIXME: nom 7 does not fully parse "infinity", therefore it is done manually
+                // even after https://github.com/rust-bakery/nom/pull/1673 wass merged this issue persisted
+                // https://github.com/Garvys/rustfst/pull/253#discussion_r1494208294
+                let (i, f) = alt((map(tag_no_case("infinity"), |_| $float::INFINITY), $parse_float))(i)?;
+                Ok((i, Self::new(f)))
+            }
+        }
 
-    fn parse_text(i: &str) -> IResult<&str, Self> {
-        // FIXME: nom 7 does not fully parse "infinity", therefore it is done manually
-        // even after https://github.com/rust-bakery/nom/pull/1673 wass merged this issue persisted
-        // https://github.com/Garvys/rustfst/pull/253#discussion_r1494208294
-        let (i, f) = alt((map(tag_no_case("infinity"), |_| f32::INFINITY), float))(i)?;
-        Ok((i, Self::new(f)))
-    }
+        impl From<$float> for $semiring {
+            fn from(f: $float) -> Self {
+                Self::new(f)
+            }
+        }
+    };
 }
 
+tropical_weight!(
+    /// Tropical semiring: (min, +, inf, 0).
+    TropicalWeight,
+    f32,
+    "tropical",
+    parse_bin_f32,
+    write_bin_f32,
+    float,
+    float_approx_equal,
+    impl_quantize_f32,
+    partial_eq_and_hash_f32
+);
+
 test_semiring_serializable!(
     tests_tropical_weight_serializable,
     TropicalWeight,
     TropicalWeight::one() TropicalWeight::zero() TropicalWeight::new(0.3) TropicalWeight::new(0.5) TropicalWeight::new(0.0) TropicalWeight::new(-1.2)
 );
-
-impl From<f32> for TropicalWeight {
-    fn from(f: f32) -> Self {
-        Self::new(f)
-    }
-}
@@ -0,0 +1,41 @@
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::combinator::map;
+use nom::number::complete::double;
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::parse_bin_f64;
+use crate::parsers::write_bin_f64;
+use crate::semirings::semiring::SerializableSemiring;
+use crate::semirings::utils_float::float64_approx_equal;
+use crate::semirings::{
+    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, StarSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+tropical_weight!(
+    /// Tropical semiring with double precision: (min, +, inf, 0).
+    TropicalWeight64,
+    f64,
+    "tropical64",
+    parse_bin_f64,
+    write_bin_f64,
+    double,
+    float64_approx_equal,
+    impl_quantize_f64,
+    partial_eq_and_hash_f64
+);
+
+test_semiring_serializable!(
+    tests_tropical_weight64_serializable,
+    TropicalWeight64,
+    TropicalWeight64::one() TropicalWeight64::zero() TropicalWeight64::new(0.3) TropicalWeight64::new(0.5) TropicalWeight64::new(0.0) TropicalWeight64::new(-1.2)
+);
@@ -12,7 +12,7 @@ use nom::bytes::complete::tag;
 use nom::multi::{count, separated_list0};
 use nom::IResult;
 
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::{check_count, NomCustomError};
 use crate::parsers::parse_bin_i32;
 use crate::parsers::write_bin_i32;
 use crate::semirings::{
@@ -304,7 +304,10 @@ where
 
     fn parse_binary(i: &[u8]) -> IResult<&[u8], Self, NomCustomError<&[u8]>> {
         let (i, n) = parse_bin_i32(i)?;
-        let (i, labels) = count(W::parse_binary, n as usize)(i)?;
+        // The serialized size of W is unknown but each weight takes at least one byte, which
+        // bounds the count by the size of the input.
+        let n = check_count(i, n as i64, 1)?;
+        let (i, labels) = count(W::parse_binary, n)(i)?;
         Ok((i, Self::new(labels)))
     }
 
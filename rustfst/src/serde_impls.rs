@@ -0,0 +1,457 @@
+//! `Serialize` and `Deserialize` implementations, enabled by the `serde` feature.
+//!
+//! - Weights are serialized as their natural value : a number for the float and integer
+//!   weights, a boolean for `BooleanWeight`, a list of labels (or `null` for the infinity)
+//!   for the string weights and a pair for `ProductWeight`. In human-readable formats,
+//!   non-finite floats are written as the strings `"Infinity"`, `"-Infinity"` and `"NaN"`
+//!   as JSON can't represent them.
+//! - A `SymbolTable` is serialized as the ordered list of its symbols, the label of a symbol
+//!   being its index in the list.
+//! - `VectorFst` and `ConstFst` are serialized as their start state, the list of their
+//!   states (final weight and outgoing transitions) and their symbol tables.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use serde::de::{self, Visitor};
+use serde::ser::{self, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::{ExpandedFst, Fst, MutableFst};
+use crate::semirings::{
+    BooleanWeight, IntegerWeight, LogWeight, LogWeight64, MinMaxWeight, ProbabilityWeight,
+    ProductWeight, RealWeight, Semiring, StringWeightLeft, StringWeightRestrict, StringWeightRight,
+    StringWeightVariant, TrivialWeight, TropicalWeight, TropicalWeight64,
+};
+use crate::{Error, Label, StateId, SymbolTable, Tr, Trs};
+
+// WEIGHTS
+
+fn non_finite_name(value: f64) -> &'static str {
+    if value.is_nan() {
+        "NaN"
+    } else if value > 0.0 {
+        "Infinity"
+    } else {
+        "-Infinity"
+    }
+}
+
+struct FloatVisitor;
+
+impl Visitor<'_> for FloatVisitor {
+    type Value = f64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a number or one of \"Infinity\", \"-Infinity\" and \"NaN\""
+        )
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<f64, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<f64, E> {
+        Ok(v as f64)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<f64, E> {
+        Ok(v as f64)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<f64, E> {
+        match v {
+            "Infinity" | "inf" | "Inf" => Ok(f64::INFINITY),
+            "-Infinity" | "-inf" | "-Inf" => Ok(f64::NEG_INFINITY),
+            "NaN" | "nan" => Ok(f64::NAN),
+            _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+}
+
+macro_rules! impl_serde_float_weight {
+    ($semiring: ty, $float: ty) => {
+        impl Serialize for $semiring {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let value = *self.value();
+                if serializer.is_human_readable() && !value.is_finite() {
+                    serializer.serialize_str(non_finite_name(value as f64))
+                } else {
+                    value.serialize(serializer)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $semiring {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(FloatVisitor)? as $float
+                } else {
+                    <$float>::deserialize(deserializer)?
+                };
+                Ok(<$semiring>::new(value))
+            }
+        }
+    };
+}
+
+impl_serde_float_weight!(TropicalWeight, f32);
+impl_serde_float_weight!(TropicalWeight64, f64);
+impl_serde_float_weight!(LogWeight, f32);
+impl_serde_float_weight!(LogWeight64, f64);
+impl_serde_float_weight!(ProbabilityWeight, f32);
+impl_serde_float_weight!(RealWeight, f32);
+impl_serde_float_weight!(MinMaxWeight, f32);
+
+macro_rules! impl_serde_value_weight {
+    ($semiring: ty, $type: ty) => {
+        impl Serialize for $semiring {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.value().serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $semiring {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(<$semiring>::new(<$type>::deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+impl_serde_value_weight!(IntegerWeight, i32);
+impl_serde_value_weight!(BooleanWeight, bool);
+impl_serde_value_weight!(TrivialWeight, ());
+
+macro_rules! impl_serde_string_weight {
+    ($semiring: ty) => {
+        impl Serialize for $semiring {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self.value() {
+                    StringWeightVariant::Infinity => serializer.serialize_none(),
+                    StringWeightVariant::Labels(labels) => serializer.serialize_some(labels),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $semiring {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = match Option::<Vec<Label>>::deserialize(deserializer)? {
+                    None => StringWeightVariant::Infinity,
+                    Some(labels) => StringWeightVariant::Labels(labels),
+                };
+                Ok(<$semiring>::new(value))
+            }
+        }
+    };
+}
+
+impl_serde_string_weight!(StringWeightLeft);
+impl_serde_string_weight!(StringWeightRight);
+impl_serde_string_weight!(StringWeightRestrict);
+
+impl<W1, W2> Serialize for ProductWeight<W1, W2>
+where
+    W1: Semiring + Serialize,
+    W2: Semiring + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.value1(), self.value2()).serialize(serializer)
+    }
+}
+
+impl<'de, W1, W2> Deserialize<'de> for ProductWeight<W1, W2>
+where
+    W1: Semiring + Deserialize<'de>,
+    W2: Semiring + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ProductWeight::new(<(W1, W2)>::deserialize(deserializer)?))
+    }
+}
+
+// SYMBOL TABLE
+
+impl Serialize for SymbolTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.symbols())
+    }
+}
+
+impl<'de> Deserialize<'de> for SymbolTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let symbols = Vec::<String>::deserialize(deserializer)?;
+        let mut symt = SymbolTable::empty();
+        for (idx, symbol) in symbols.into_iter().enumerate() {
+            if symt.contains_symbol(&symbol) {
+                return Err(de::Error::custom(format!(
+                    "Duplicate symbol {:?} at index {} of the SymbolTable",
+                    symbol, idx
+                )));
+            }
+            symt.add_symbol(symbol);
+        }
+        Ok(symt)
+    }
+}
+
+// FSTS
+
+#[derive(Serialize)]
+struct SerializedState<'a, W: Serialize> {
+    final_weight: Option<W>,
+    trs: &'a [Tr<W>],
+}
+
+struct SerializedStates<'a, W, F>(&'a F, PhantomData<W>);
+
+impl<W, F> Serialize for SerializedStates<'_, W, F>
+where
+    W: Semiring + Serialize,
+    F: ExpandedFst<W>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fst = self.0;
+        let mut seq = serializer.serialize_seq(Some(fst.num_states()))?;
+        for state in fst.states_range() {
+            let final_weight = fst.final_weight(state).map_err(ser::Error::custom)?;
+            let trs = fst.get_trs(state).map_err(ser::Error::custom)?;
+            seq.serialize_element(&SerializedState {
+                final_weight,
+                trs: trs.trs(),
+            })?;
+        }
+        seq.end()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(bound(serialize = "W: Semiring + Serialize, F: ExpandedFst<W>"))]
+struct SerializedFst<'a, W, F> {
+    start: Option<StateId>,
+    states: SerializedStates<'a, W, F>,
+    input_symbols: Option<&'a SymbolTable>,
+    output_symbols: Option<&'a SymbolTable>,
+}
+
+fn serialize_fst<W, F, S>(fst: &F, serializer: S) -> Result<S::Ok, S::Error>
+where
+    W: Semiring + Serialize,
+    F: ExpandedFst<W>,
+    S: Serializer,
+{
+    SerializedFst {
+        start: fst.start(),
+        states: SerializedStates(fst, PhantomData),
+        input_symbols: fst.input_symbols().map(|s| s.as_ref()),
+        output_symbols: fst.output_symbols().map(|s| s.as_ref()),
+    }
+    .serialize(serializer)
+}
+
+#[derive(Deserialize)]
+struct DeserializedState<W> {
+    final_weight: Option<W>,
+    trs: Vec<Tr<W>>,
+}
+
+#[derive(Deserialize)]
+struct DeserializedFst<W> {
+    start: Option<StateId>,
+    states: Vec<DeserializedState<W>>,
+    input_symbols: Option<SymbolTable>,
+    output_symbols: Option<SymbolTable>,
+}
+
+impl<W: Semiring> DeserializedFst<W> {
+    fn into_vector_fst<E: de::Error>(self) -> Result<VectorFst<W>, E> {
+        let num_states = self.states.len();
+        let mut fst = VectorFst::new();
+        fst.add_states(num_states);
+        if let Some(start) = self.start {
+            fst.set_start(start).map_err(E::custom)?;
+        }
+        for (state, deserialized_state) in self.states.into_iter().enumerate() {
+            let state = state as StateId;
+            for tr in deserialized_state.trs {
+                if tr.nextstate as usize >= num_states {
+                    return Err(E::custom(Error::InvalidStateId(tr.nextstate)));
+                }
+                fst.add_tr(state, tr).map_err(E::custom)?;
+            }
+            if let Some(final_weight) = deserialized_state.final_weight {
+                fst.set_final(state, final_weight).map_err(E::custom)?;
+            }
+        }
+        if let Some(symt) = self.input_symbols {
+            fst.set_input_symbols(Arc::new(symt));
+        }
+        if let Some(symt) = self.output_symbols {
+            fst.set_output_symbols(Arc::new(symt));
+        }
+        Ok(fst)
+    }
+}
+
+impl<W: Semiring + Serialize> Serialize for VectorFst<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_fst(self, serializer)
+    }
+}
+
+impl<'de, W: Semiring + Deserialize<'de>> Deserialize<'de> for VectorFst<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DeserializedFst::deserialize(deserializer)?.into_vector_fst()
+    }
+}
+
+impl<W: Semiring + Serialize> Serialize for ConstFst<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_fst(self, serializer)
+    }
+}
+
+impl<'de, W: Semiring + Deserialize<'de>> Deserialize<'de> for ConstFst<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(DeserializedFst::deserialize(deserializer)?
+            .into_vector_fst()?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::GallicWeightRestrict;
+
+    fn symt(symbols: &[&str]) -> Arc<SymbolTable> {
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(symbols.iter().copied());
+        Arc::new(symt)
+    }
+
+    fn fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_tr(0, Tr::new(1, 2, 1.5, 1))?;
+        fst.add_tr(0, Tr::new(2, 0, TropicalWeight::zero(), 2))?;
+        fst.add_tr(1, Tr::new(0, 1, 0.5, 1))?;
+        fst.set_final(1, 0.25)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.set_input_symbols(symt(&["a", "b"]));
+        fst.set_output_symbols(symt(&["x", "y"]));
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_serde_weights_json() -> Result<()> {
+        assert_eq!(serde_json::to_string(&TropicalWeight::new(1.5))?, "1.5");
+        assert_eq!(
+            serde_json::to_string(&TropicalWeight::zero())?,
+            "\"Infinity\""
+        );
+        assert_eq!(
+            serde_json::from_str::<TropicalWeight>("\"Infinity\"")?,
+            TropicalWeight::zero()
+        );
+        assert_eq!(
+            serde_json::from_str::<LogWeight64>("-2")?,
+            LogWeight64::new(-2.0)
+        );
+        assert!(serde_json::from_str::<LogWeight>("\"foo\"").is_err());
+
+        assert_eq!(serde_json::to_string(&IntegerWeight::new(3))?, "3");
+        assert_eq!(serde_json::to_string(&BooleanWeight::new(true))?, "true");
+        assert_eq!(
+            serde_json::to_string(&StringWeightLeft::from(vec![1, 2]))?,
+            "[1,2]"
+        );
+        assert_eq!(serde_json::to_string(&StringWeightLeft::zero())?, "null");
+        assert_eq!(
+            serde_json::from_str::<StringWeightRight>("null")?,
+            StringWeightRight::zero()
+        );
+
+        let w = GallicWeightRestrict::<TropicalWeight>::from((
+            StringWeightRestrict::from(vec![3]),
+            TropicalWeight::new(2.0),
+        ));
+        let product = w.value();
+        let json = serde_json::to_string(product)?;
+        assert_eq!(json, "[[3],2.0]");
+        assert_eq!(
+            &serde_json::from_str::<ProductWeight<_, _>>(&json)?,
+            product
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_symbol_table() -> Result<()> {
+        let symt = symt(&["a", "b"]);
+        let json = serde_json::to_string(symt.as_ref())?;
+        assert_eq!(json, r#"["<eps>","a","b"]"#);
+        assert_eq!(&serde_json::from_str::<SymbolTable>(&json)?, symt.as_ref());
+
+        assert!(serde_json::from_str::<SymbolTable>(r#"["<eps>","a","a"]"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_vector_fst() -> Result<()> {
+        let fst = fst()?;
+
+        let json = serde_json::to_string(&fst)?;
+        let deserialized: VectorFst<TropicalWeight> = serde_json::from_str(&json)?;
+        assert_eq!(deserialized, fst);
+        assert_eq!(deserialized.properties(), fst.properties());
+
+        let bin = bincode::serialize(&fst)?;
+        let deserialized: VectorFst<TropicalWeight> = bincode::deserialize(&bin)?;
+        assert_eq!(deserialized, fst);
+
+        let empty = VectorFst::<TropicalWeight>::new();
+        let json = serde_json::to_string(&empty)?;
+        assert_eq!(
+            json,
+            r#"{"start":null,"states":[],"input_symbols":null,"output_symbols":null}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<VectorFst<TropicalWeight>>(&json)?,
+            empty
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_const_fst() -> Result<()> {
+        let fst: ConstFst<_> = fst()?.into();
+
+        let json = serde_json::to_string(&fst)?;
+        assert_eq!(
+            serde_json::from_str::<ConstFst<TropicalWeight>>(&json)?,
+            fst
+        );
+
+        let bin = bincode::serialize(&fst)?;
+        assert_eq!(bincode::deserialize::<ConstFst<TropicalWeight>>(&bin)?, fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_invalid_fst() {
+        let json = r#"{"start":0,"states":[{"final_weight":null,"trs":[{"ilabel":1,"olabel":1,"weight":1.0,"nextstate":3}]}],"input_symbols":null,"output_symbols":null}"#;
+        assert!(serde_json::from_str::<VectorFst<TropicalWeight>>(json).is_err());
+
+        let json = r#"{"start":2,"states":[],"input_symbols":null,"output_symbols":null}"#;
+        assert!(serde_json::from_str::<VectorFst<TropicalWeight>>(json).is_err());
+    }
+}
@@ -1,5 +1,5 @@
-use crate::{FstPath, Label, Semiring, SymbolTable};
-use anyhow::{format_err, Result};
+use crate::{Error, FstPath, Label, Semiring, SymbolTable};
+use anyhow::Result;
 use std::sync::Arc;
 
 /// Wrapper around `FstPath` to nicely handle `SymbolTable`s.
@@ -32,14 +32,14 @@ impl<W: Semiring> StringPath<W> {
     }
 
     pub fn istring(&self) -> Result<String> {
-        let res: Result<Vec<_>> = self
+        let res: std::result::Result<Vec<_>, Error> = self
             .fst_path
             .ilabels
             .iter()
             .map(|e| {
-                self.isymt
-                    .get_symbol(*e)
-                    .ok_or_else(|| format_err!("Missing {} in symbol table", e))
+                self.isymt.get_symbol(*e).ok_or_else(|| {
+                    Error::IncompatibleSymbols(format!("Missing {} in symbol table", e))
+                })
                 // .map_err()
             })
             .collect();
@@ -48,14 +48,14 @@ impl<W: Semiring> StringPath<W> {
     }
 
     pub fn ostring(&self) -> Result<String> {
-        let res: Result<Vec<_>> = self
+        let res: std::result::Result<Vec<_>, Error> = self
             .fst_path
             .olabels
             .iter()
             .map(|e| {
-                self.osymt
-                    .get_symbol(*e)
-                    .ok_or_else(|| format_err!("Missing {} in symbol table", e))
+                self.osymt.get_symbol(*e).ok_or_else(|| {
+                    Error::IncompatibleSymbols(format!("Missing {} in symbol table", e))
+                })
                 // .map_err()
             })
             .collect();
@@ -7,9 +7,9 @@ use anyhow::{Context, Result};
 use itertools::Itertools;
 
 use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
-use crate::parsers::nom_utils::NomCustomError;
+use crate::parsers::nom_utils::binary_parse_error;
 use crate::parsers::text_symt::parsed_text_symt::ParsedTextSymt;
-use crate::{Label, EPS_SYMBOL};
+use crate::{Error, Label, EPS_SYMBOL};
 use std::collections::hash_map::{Entry, RandomState};
 use std::collections::HashMap;
 use std::hash::BuildHasher;
@@ -71,23 +71,17 @@ impl SymbolTable {
     }
 
     pub fn read<P: AsRef<Path>>(path_bin_symt: P) -> Result<Self> {
-        let data = read(path_bin_symt.as_ref()).with_context(|| {
-            format!(
-                "Can't open SymbolTable binary file : {:?}",
-                path_bin_symt.as_ref()
-            )
-        })?;
-
-        let (_, symt) = parse_symbol_table_bin(&data).map_err(|e| {
-            e.map(|e_inner| match e_inner {
-                NomCustomError::Nom(_, k) => {
-                    format_err!("Error while parsing binary SymbolTable. Error kind {:?}", k)
-                }
-                NomCustomError::SymbolTableError(e) => {
-                    format_err!("Error while parsing symbolTable from binary : {}", e)
-                }
-            })
-        })?;
+        let data = read(path_bin_symt.as_ref())
+            .map_err(Error::Io)
+            .with_context(|| {
+                format!(
+                    "Can't open SymbolTable binary file : {:?}",
+                    path_bin_symt.as_ref()
+                )
+            })?;
+
+        let (_, symt) =
+            parse_symbol_table_bin(&data).map_err(|e| binary_parse_error(e, "SymbolTable"))?;
 
         Ok(symt)
     }
@@ -264,7 +258,7 @@ impl<H: BuildHasher> SymbolTable<H> {
     }
 
     pub fn write_text<P: AsRef<Path>>(&self, path_output: P) -> Result<()> {
-        let buffer = File::create(path_output.as_ref())?;
+        let buffer = File::create(path_output.as_ref()).map_err(Error::Io)?;
         let mut writer = BufWriter::new(LineWriter::new(buffer));
 
         write!(writer, "{}", self)?;
@@ -273,7 +267,7 @@ impl<H: BuildHasher> SymbolTable<H> {
     }
 
     pub fn write<P: AsRef<Path>>(&self, path_bin_symt: P) -> Result<()> {
-        let buffer = File::create(path_bin_symt.as_ref())?;
+        let buffer = File::create(path_bin_symt.as_ref()).map_err(Error::Io)?;
         let mut writer = BufWriter::new(LineWriter::new(buffer));
 
         write_bin_symt(&mut writer, self)?;
@@ -70,9 +70,11 @@ fn do_test_compose<W>(
 where
     W: SerializableSemiring + WeightQuantize + WeaklyDivisibleSemiring,
 {
-    let mut config = ComposeConfig::default();
-    config.connect = false;
-    config.compose_filter = filter;
+    let config = ComposeConfig {
+        connect: false,
+        compose_filter: filter,
+        ..Default::default()
+    };
 
     let fst_res_static: VectorFst<_> = compose_with_config::<W, VectorFst<_>, VectorFst<_>, _, _, _>(
         Arc::new(fst_raw.clone()),
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+
+use crate::algorithms::compose::lookahead_matchers::{LabelLookAheadMatcher, OLabelLookAheadFlags};
+use crate::algorithms::compose::matchers::SortedMatcher;
+use crate::algorithms::compose::{LabelReachableData, MatcherFst};
+use crate::fst_impls::{ConstFst, VectorFst};
+use crate::fst_traits::SerializableFst;
+use crate::semirings::{SerializableSemiring, WeightQuantize};
+use crate::tests_openfst::utils::test_eq_fst;
+use crate::tests_openfst::FstTestData;
+
+type OLabelLookAheadFst<W> = MatcherFst<
+    W,
+    ConstFst<W>,
+    Arc<ConstFst<W>>,
+    LabelLookAheadMatcher<
+        W,
+        ConstFst<W>,
+        Arc<ConstFst<W>>,
+        SortedMatcher<W, ConstFst<W>, Arc<ConstFst<W>>>,
+        OLabelLookAheadFlags,
+    >,
+    LabelReachableData,
+>;
+
+// Test parsing an olabel_lookahead MatcherFst written by OpenFST in the add-on format.
+pub fn test_olabel_lookahead_fst_bin_deserializer<W>(
+    test_data: &FstTestData<W, VectorFst<W>>,
+) -> Result<()>
+where
+    W: SerializableSemiring + WeightQuantize,
+{
+    let parsed_fst_bin = OLabelLookAheadFst::<W>::read(&test_data.raw_olabel_lookahead_bin_path)
+        .with_context(|| format_err!("Failed parsing olabel_lookahead MatcherFst"))?;
+    let fst_ref = OLabelLookAheadFst::<W>::from_fst(test_data.raw.clone())?;
+
+    test_eq_fst(
+        fst_ref.fst(),
+        parsed_fst_bin.fst(),
+        "Deserializer olabel_lookahead MatcherFst Bin",
+    );
+    assert_eq!(fst_ref.addon(), parsed_fst_bin.addon());
+    Ok(())
+}
@@ -9,6 +9,7 @@ pub mod const_fst_bin_deserializer;
 pub mod const_fst_bin_serializer;
 pub mod const_fst_text_deserialization;
 pub mod const_fst_text_serialization;
+pub mod matcher_fst_bin_deserializer;
 pub mod vector_fst_bin_deserializer;
 pub mod vector_fst_bin_serializer;
 pub mod vector_fst_text_deserialization;
@@ -15,7 +15,9 @@ use serde::{Deserialize, Serialize};
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::SerializableFst;
-use crate::semirings::{LogWeight, ProductWeight, SerializableSemiring, TropicalWeight};
+use crate::semirings::{
+    LogWeight, LogWeight64, ProductWeight, SerializableSemiring, TropicalWeight, TropicalWeight64,
+};
 use crate::tests_openfst::algorithms::closure::{
     test_closure_plus, test_closure_plus_lazy, test_closure_star, test_closure_star_lazy,
     SimpleStaticLazyOperationResult, SimpleStaticLazyTestData,
@@ -55,6 +57,7 @@ use crate::tests_openfst::io::const_fst_bin_serializer::test_const_fst_bin_seria
 use crate::tests_openfst::io::const_fst_text_deserialization::test_const_fst_text_deserialization;
 use crate::tests_openfst::io::const_fst_text_serialization::test_const_fst_text_serialization;
 use crate::tests_openfst::io::const_fst_text_serialization::test_const_fst_text_serialization_with_symt;
+use crate::tests_openfst::io::matcher_fst_bin_deserializer::test_olabel_lookahead_fst_bin_deserializer;
 use crate::tests_openfst::io::vector_fst_bin_deserializer::test_vector_fst_bin_deserializer;
 use crate::tests_openfst::io::vector_fst_bin_deserializer::test_vector_fst_bin_with_symt_deserializer;
 use crate::tests_openfst::io::vector_fst_bin_serializer::{
@@ -161,6 +164,7 @@ pub struct ParsedFstTestData {
     raw_vector_bin_path: String,
     raw_const_bin_path: String,
     raw_const_aligned_bin_path: String,
+    raw_olabel_lookahead_bin_path: String,
     shortest_distance: Vec<ShorestDistanceOperationResult>,
     shortest_path: Vec<ShorestPathOperationResult>,
     gallic_encode_decode: Vec<GallicOperationResult>,
@@ -218,6 +222,7 @@ where
     pub raw_vector_bin_path: PathBuf,
     pub raw_const_bin_path: PathBuf,
     pub raw_const_aligned_bin_path: PathBuf,
+    pub raw_olabel_lookahead_bin_path: PathBuf,
     pub shortest_distance: Vec<ShortestDistanceTestData<W>>,
     pub shortest_path: Vec<ShortestPathTestData<W, F>>,
     pub gallic_encode_decode: Vec<GallicTestData<W, F>>,
@@ -299,6 +304,9 @@ where
             raw_const_aligned_bin_path: absolute_path_folder
                 .join(&data.raw_const_aligned_bin_path)
                 .to_path_buf(),
+            raw_olabel_lookahead_bin_path: absolute_path_folder
+                .join(&data.raw_olabel_lookahead_bin_path)
+                .to_path_buf(),
             shortest_distance: data.shortest_distance.iter().map(|v| v.parse()).collect(),
             shortest_path: data
                 .shortest_path
@@ -386,6 +394,16 @@ macro_rules! do_run {
                     FstTestData::new(&parsed_test_data, absolute_path_folder.as_path());
                 $f(&test_data)?;
             }
+            "tropical64" => {
+                let test_data: FstTestData<TropicalWeight64, VectorFst<TropicalWeight64>> =
+                    FstTestData::new(&parsed_test_data, absolute_path_folder.as_path());
+                $f(&test_data)?;
+            }
+            "log64" => {
+                let test_data: FstTestData<LogWeight64, VectorFst<LogWeight64>> =
+                    FstTestData::new(&parsed_test_data, absolute_path_folder.as_path());
+                $f(&test_data)?;
+            }
             "tropical_X_log" => {
                 let test_data: FstTestData<
                     ProductWeight<TropicalWeight, LogWeight>,
@@ -722,6 +740,12 @@ macro_rules! test_fst {
                 Ok(())
             }
 
+            #[test]
+            fn test_olabel_lookahead_fst_bin_deserializer_openfst() -> Result<()> {
+                do_run!(test_olabel_lookahead_fst_bin_deserializer, $fst_name);
+                Ok(())
+            }
+
             #[test]
             fn test_const_fst_bin_serializer_openfst() -> Result<()> {
                 do_run!(test_const_fst_bin_serializer, $fst_name);
@@ -855,3 +879,5 @@ test_fst!(test_openfst_fst_017, "fst_017");
 test_fst!(test_openfst_fst_018, "fst_018");
 test_fst!(test_openfst_fst_019, "fst_019");
 test_fst!(test_openfst_fst_020, "fst_020");
+test_fst!(test_openfst_fst_021, "fst_021");
+test_fst!(test_openfst_fst_022, "fst_022");
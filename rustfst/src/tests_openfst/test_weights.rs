@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::semirings::{
     GallicWeight, GallicWeightLeft, GallicWeightMin, GallicWeightRestrict, GallicWeightRight,
-    LogWeight, ProductWeight, ReverseBack, SerializableSemiring, StringWeightLeft,
-    StringWeightRestrict, StringWeightRight, TropicalWeight, WeightQuantize,
+    LogWeight, LogWeight64, ProductWeight, ReverseBack, SerializableSemiring, StringWeightLeft,
+    StringWeightRestrict, StringWeightRight, TropicalWeight, TropicalWeight64, WeightQuantize,
 };
 use crate::{Tr, KDELTA};
 
@@ -105,6 +105,14 @@ fn run_test_openfst_weight(test_name: &str) -> Result<()> {
             let parsed_test_data = parsed_operation_result.parse::<LogWeight>();
             do_run_test_openfst_weight(parsed_test_data)?;
         }
+        "tropical64" => {
+            let parsed_test_data = parsed_operation_result.parse::<TropicalWeight64>();
+            do_run_test_openfst_weight(parsed_test_data)?;
+        }
+        "log64" => {
+            let parsed_test_data = parsed_operation_result.parse::<LogWeight64>();
+            do_run_test_openfst_weight(parsed_test_data)?;
+        }
         "tropical_X_log" => {
             let parsed_test_data =
                 parsed_operation_result.parse::<ProductWeight<TropicalWeight, LogWeight>>();
@@ -219,3 +227,13 @@ fn test_openfst_weight_011() -> Result<()> {
 fn test_openfst_weight_012() -> Result<()> {
     run_test_openfst_weight("weight_012")
 }
+
+#[test]
+fn test_openfst_weight_013() -> Result<()> {
+    run_test_openfst_weight("weight_013")
+}
+
+#[test]
+fn test_openfst_weight_014() -> Result<()> {
+    run_test_openfst_weight("weight_014")
+}
@@ -3,6 +3,7 @@ use crate::{Label, StateId};
 
 /// Structure representing a transition from a state to another state in a FST.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tr<W> {
     /// Input label.
     pub ilabel: Label,